@@ -0,0 +1,219 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use chrono::NaiveDate;
+use serde_json::Value;
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::error::{AppError, AppResult};
+
+const TRAY_TODAY_TASK_LIMIT: usize = 10;
+const TRAY_GLOBAL_LIMIT: usize = 10;
+const TRAY_RECENT_SPARK_LIMIT: usize = 5;
+
+/// Last data.json content the backend loaded, saved or wrote itself. The tray reads from here instead of the disk.
+#[derive(Default)]
+pub(crate) struct AppDataState {
+    snapshot: Mutex<Option<Value>>,
+}
+
+pub(crate) fn remember_app_data<R: Runtime>(app: &AppHandle<R>, data: &Value) {
+    if let Some(state) = app.try_state::<AppDataState>() {
+        if let Ok(mut snapshot) = state.snapshot.lock() {
+            *snapshot = Some(data.clone());
+        }
+    }
+}
+
+pub(crate) fn app_data_snapshot<R: Runtime>(app: &AppHandle<R>) -> Option<Value> {
+    app.try_state::<AppDataState>()
+        .and_then(|state| state.snapshot.lock().ok().and_then(|snapshot| snapshot.clone()))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TrayEntry {
+    pub id: String,
+    pub title: String,
+}
+
+/// What the tray lists: today's active tasks, active globals and the most recently created sparks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct TrayDataSummary {
+    pub today_tasks: Vec<TrayEntry>,
+    pub active_globals: Vec<TrayEntry>,
+    pub recent_sparks: Vec<TrayEntry>,
+}
+
+fn entries<'a>(data: &'a Value, collection: &str) -> impl Iterator<Item = &'a Value> {
+    data.get(collection).and_then(Value::as_array).into_iter().flatten()
+}
+
+fn string_field<'a>(item: &'a Value, field: &str) -> Option<&'a str> {
+    item.get(field).and_then(Value::as_str)
+}
+
+fn tray_entry(item: &Value) -> Option<TrayEntry> {
+    Some(TrayEntry {
+        id: string_field(item, "id")?.to_string(),
+        title: string_field(item, "title")?.to_string(),
+    })
+}
+
+pub(crate) fn summarize_for_tray(data: &Value, today: NaiveDate) -> TrayDataSummary {
+    let today = today.format("%Y-%m-%d").to_string();
+    let today_tasks = entries(data, "tasks")
+        .filter(|task| string_field(task, "status") == Some("active"))
+        .filter(|task| string_field(task, "executionDate") == Some(today.as_str()))
+        .filter_map(tray_entry)
+        .take(TRAY_TODAY_TASK_LIMIT)
+        .collect();
+    let active_globals = entries(data, "globals")
+        .filter(|global| string_field(global, "status") == Some("active"))
+        .filter_map(tray_entry)
+        .take(TRAY_GLOBAL_LIMIT)
+        .collect();
+
+    // ISO timestamps sort chronologically as strings.
+    let mut sparks = entries(data, "sparks")
+        .filter_map(|spark| Some((string_field(spark, "createdAt").unwrap_or_default(), tray_entry(spark)?)))
+        .collect::<Vec<_>>();
+    sparks.sort_by(|(left, _), (right, _)| right.cmp(left));
+    let recent_sparks = sparks
+        .into_iter()
+        .map(|(_, spark)| spark)
+        .take(TRAY_RECENT_SPARK_LIMIT)
+        .collect();
+
+    TrayDataSummary {
+        today_tasks,
+        active_globals,
+        recent_sparks,
+    }
+}
+
+/// Task counts shown in the tray tooltip and badge.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct TaskCounts {
+    pub active_today: usize,
+    /// Active tasks whose latest log entry is an exception.
+    pub exceptions: usize,
+    /// Active tasks scheduled before today.
+    pub overdue: usize,
+}
+
+pub(crate) fn count_tasks(data: &Value, today: NaiveDate) -> TaskCounts {
+    let today = today.format("%Y-%m-%d").to_string();
+    let mut latest_log_types = HashMap::new();
+    for log in entries(data, "taskLogs") {
+        let (Some(task_id), Some(log_type)) = (string_field(log, "taskId"), string_field(log, "type")) else {
+            continue;
+        };
+        let created_at = string_field(log, "createdAt").unwrap_or_default();
+        let latest = latest_log_types.entry(task_id).or_insert((created_at, log_type));
+        if created_at >= latest.0 {
+            *latest = (created_at, log_type);
+        }
+    }
+
+    let mut counts = TaskCounts::default();
+    for task in entries(data, "tasks").filter(|task| string_field(task, "status") == Some("active")) {
+        match string_field(task, "executionDate") {
+            Some(date) if date == today => counts.active_today += 1,
+            Some(date) if date < today.as_str() => counts.overdue += 1,
+            _ => {}
+        }
+        let latest_type = string_field(task, "id").and_then(|id| latest_log_types.get(id)).map(|(_, kind)| *kind);
+        if latest_type == Some("exception") {
+            counts.exceptions += 1;
+        }
+    }
+    counts
+}
+
+/// Marks the task `task_id` completed in `data`, stamping `updated_at`. Completing a completed task is a no-op.
+pub(crate) fn complete_task(data: &mut Value, task_id: &str, updated_at: &str) -> AppResult<bool> {
+    let task = data
+        .get_mut("tasks")
+        .and_then(Value::as_array_mut)
+        .and_then(|tasks| tasks.iter_mut().find(|task| string_field(task, "id") == Some(task_id)))
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| AppError::not_found(format!("task `{task_id}` does not exist")).with_context("task", task_id))?;
+
+    if task.get("status").and_then(Value::as_str) == Some("completed") {
+        return Ok(false);
+    }
+    task.insert("status".into(), Value::String("completed".into()));
+    task.insert("updatedAt".into(), Value::String(updated_at.to_string()));
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_data() -> Value {
+        serde_json::json!({
+            "schemaVersion": 2,
+            "tasks": [
+                { "id": "t1", "title": "Today", "executionDate": "2026-10-18", "status": "active" },
+                { "id": "t2", "title": "Done", "executionDate": "2026-10-18", "status": "completed" },
+                { "id": "t3", "title": "Tomorrow", "executionDate": "2026-10-19", "status": "active" },
+            ],
+            "globals": [
+                { "id": "g1", "title": "Running", "status": "active" },
+                { "id": "g2", "title": "Stopped", "status": "terminated" },
+            ],
+            "taskLogs": [],
+            "sparks": [
+                { "id": "s1", "title": "Old", "createdAt": "2026-10-01T08:00:00.000Z" },
+                { "id": "s2", "title": "New", "createdAt": "2026-10-17T08:00:00.000Z" },
+            ],
+        })
+    }
+
+    #[test]
+    fn tray_summary_lists_today_active_globals_and_newest_sparks() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 18).expect("valid date");
+        let summary = summarize_for_tray(&sample_data(), today);
+        let ids = |entries: &[TrayEntry]| entries.iter().map(|entry| entry.id.clone()).collect::<Vec<_>>();
+
+        assert_eq!(ids(&summary.today_tasks), vec!["t1"]);
+        assert_eq!(ids(&summary.active_globals), vec!["g1"]);
+        assert_eq!(ids(&summary.recent_sparks), vec!["s2", "s1"]);
+        assert_eq!(summarize_for_tray(&Value::Null, today), TrayDataSummary::default());
+    }
+
+    #[test]
+    fn counts_today_overdue_and_tasks_whose_latest_log_is_an_exception() {
+        let mut data = sample_data();
+        let late = serde_json::json!({ "id": "t4", "title": "Late", "executionDate": "2026-10-10", "status": "active" });
+        data["tasks"].as_array_mut().expect("tasks list").push(late);
+        data["taskLogs"] = serde_json::json!([
+            { "taskId": "t1", "type": "exception", "createdAt": "2026-10-18T08:00:00.000Z" },
+            { "taskId": "t4", "type": "exception", "createdAt": "2026-10-11T08:00:00.000Z" },
+            { "taskId": "t4", "type": "progress", "createdAt": "2026-10-12T08:00:00.000Z" },
+            { "taskId": "t2", "type": "exception", "createdAt": "2026-10-18T08:00:00.000Z" },
+        ]);
+
+        let today = NaiveDate::from_ymd_opt(2026, 10, 18).expect("valid date");
+        assert_eq!(
+            count_tasks(&data, today),
+            TaskCounts {
+                active_today: 1,
+                exceptions: 1,
+                overdue: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn completing_a_task_updates_status_once() {
+        let mut data = sample_data();
+        let stamp = "2026-10-18T10:00:00.000Z";
+
+        assert!(complete_task(&mut data, "t1", stamp).expect("task exists"));
+        assert_eq!(data["tasks"][0]["status"], "completed");
+        assert_eq!(data["tasks"][0]["updatedAt"], stamp);
+        assert!(!complete_task(&mut data, "t1", stamp).expect("task exists"));
+        assert!(complete_task(&mut data, "missing", stamp).is_err());
+    }
+}
@@ -0,0 +1,896 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    error::{AppError, AppResult},
+    logging::now_unix_millis,
+    read_app_config_file, write_app_config_file,
+};
+
+pub(crate) const CONFIG_SCHEMA_VERSION: u32 = 1;
+pub(crate) const WIDGET_MIN_SCALE_PERCENT: u16 = 70;
+pub(crate) const WIDGET_MAX_SCALE_PERCENT: u16 = 300;
+const WIDGET_DEFAULT_SCALE_PERCENT: u16 = 100;
+pub(crate) const WIDGET_MAX_OPACITY_PERCENT: u8 = 100;
+const WIDGET_DEFAULT_OPACITY_PERCENT: u8 = 20;
+const WIDGET_DEFAULT_HOVER_OPACITY_PERCENT: u8 = 38;
+/// Positions further than this from the origin cannot belong to any real monitor layout.
+const WIDGET_POSITION_LIMIT: i32 = 100_000;
+const WIDGET_SIZE_LIMIT: u32 = 20_000;
+pub(crate) const SHORTCUT_TOGGLE_WIDGET_LOCK: &str = "toggleWidgetLock";
+const DEFAULT_TOGGLE_WIDGET_LOCK_SHORTCUT: &str = "ctrl+shift+l";
+const SHORTCUT_QUICK_CAPTURE_SPARK: &str = "quickCaptureSpark";
+const DEFAULT_QUICK_CAPTURE_SPARK_SHORTCUT: &str = "ctrl+alt+n";
+/// Label of the original widget window, whose state lives in the flat `widget*` fields.
+pub(crate) const PRIMARY_WIDGET_LABEL: &str = "widget";
+const WIDGET_INSTANCE_LABEL_PREFIX: &str = "widget-";
+const WIDGET_LABEL_MAX_LEN: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct WidgetPosition {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// Logical outer size of the widget window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct WidgetSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum WidgetAlignMode {
+    Left,
+    #[default]
+    Right,
+}
+
+/// Whether the top or the bottom edge stays put when the widget is resized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum WidgetVerticalAnchor {
+    #[default]
+    Top,
+    Bottom,
+}
+
+/// Work-area edges the widget is snapped flush against; an axis left as `None` floats freely.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WidgetDock {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub horizontal: Option<WidgetAlignMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vertical: Option<WidgetVerticalAnchor>,
+}
+
+/// Which tasks a widget instance lists.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum WidgetView {
+    #[default]
+    Active,
+    All,
+    Today,
+    /// Tasks linked to the global through a spark.
+    #[serde(rename_all = "camelCase")]
+    Global { global_id: String },
+}
+
+impl WidgetView {
+    /// Derives the view from labels such as `widget-today` or `widget-global-<id>`.
+    pub(crate) fn for_label(label: &str) -> Self {
+        let Some(suffix) = label.strip_prefix(WIDGET_INSTANCE_LABEL_PREFIX) else {
+            return Self::default();
+        };
+        match suffix.strip_prefix("global-") {
+            Some(global_id) if !global_id.is_empty() => Self::Global {
+                global_id: global_id.to_string(),
+            },
+            _ if suffix == "today" => Self::Today,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// Per-window state of one widget. The primary widget is stored in the flat `widget*` fields of
+/// [`AppConfig`]; every other instance is kept in `widgetInstances`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WidgetInstanceConfig {
+    #[serde(default)]
+    pub view: WidgetView,
+    #[serde(default)]
+    pub visible: bool,
+    #[serde(default)]
+    pub position: Option<WidgetPosition>,
+    #[serde(default)]
+    pub size: Option<WidgetSize>,
+    #[serde(default)]
+    pub monitor: Option<String>,
+    #[serde(default)]
+    pub dock: Option<WidgetDock>,
+    #[serde(default = "default_widget_scale_percent")]
+    pub scale_percent: u16,
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default)]
+    pub align_mode: WidgetAlignMode,
+    /// Background opacity of the widget content, 0-100.
+    #[serde(default = "default_widget_opacity_percent")]
+    pub opacity_percent: u8,
+    /// Background opacity while the pointer is over an unlocked widget.
+    #[serde(default = "default_widget_hover_opacity_percent")]
+    pub hover_opacity_percent: u8,
+    /// Whether a locked widget lets clicks through to the windows below it.
+    #[serde(default = "default_true")]
+    pub click_through: bool,
+    /// Pins the widget below every other window, as part of the wallpaper, instead of above them.
+    #[serde(default)]
+    pub desktop_layer: bool,
+}
+
+impl Default for WidgetInstanceConfig {
+    fn default() -> Self {
+        Self {
+            view: WidgetView::default(),
+            visible: false,
+            position: None,
+            size: None,
+            monitor: None,
+            dock: None,
+            scale_percent: WIDGET_DEFAULT_SCALE_PERCENT,
+            locked: false,
+            align_mode: WidgetAlignMode::default(),
+            opacity_percent: WIDGET_DEFAULT_OPACITY_PERCENT,
+            hover_opacity_percent: WIDGET_DEFAULT_HOVER_OPACITY_PERCENT,
+            click_through: true,
+            desktop_layer: false,
+        }
+    }
+}
+
+impl WidgetInstanceConfig {
+    pub(crate) fn for_label(label: &str) -> Self {
+        Self {
+            view: WidgetView::for_label(label),
+            ..Self::default()
+        }
+    }
+
+    fn clamp_to_ranges(&mut self) {
+        self.scale_percent = self.scale_percent.clamp(WIDGET_MIN_SCALE_PERCENT, WIDGET_MAX_SCALE_PERCENT);
+        self.opacity_percent = self.opacity_percent.min(WIDGET_MAX_OPACITY_PERCENT);
+        self.hover_opacity_percent = self.hover_opacity_percent.min(WIDGET_MAX_OPACITY_PERCENT);
+        if self.position.is_some_and(|position| !is_position_in_range(position)) {
+            self.position = None;
+        }
+        if self.size.is_some_and(|size| !is_size_in_range(size)) {
+            self.size = None;
+        }
+    }
+
+    fn validate(&self, label: &str) -> AppResult<()> {
+        if !(WIDGET_MIN_SCALE_PERCENT..=WIDGET_MAX_SCALE_PERCENT).contains(&self.scale_percent) {
+            return Err(AppError::validation(format!(
+                "scalePercent of widget `{label}` must be between \
+                 {WIDGET_MIN_SCALE_PERCENT} and {WIDGET_MAX_SCALE_PERCENT}"
+            ))
+            .with_context("field", "widgetInstances")
+            .with_context("widget", label)
+            .with_context("value", self.scale_percent));
+        }
+        if let Some(value) = [self.opacity_percent, self.hover_opacity_percent]
+            .into_iter()
+            .find(|value| *value > WIDGET_MAX_OPACITY_PERCENT)
+        {
+            return Err(AppError::validation(format!(
+                "opacity of widget `{label}` must be at most {WIDGET_MAX_OPACITY_PERCENT}"
+            ))
+            .with_context("field", "widgetInstances")
+            .with_context("widget", label)
+            .with_context("value", value));
+        }
+        if self.position.is_some_and(|position| !is_position_in_range(position)) {
+            return Err(AppError::validation(format!("position of widget `{label}` is out of range"))
+                .with_context("field", "widgetInstances")
+                .with_context("widget", label));
+        }
+        if self.size.is_some_and(|size| !is_size_in_range(size)) {
+            return Err(AppError::validation(format!("size of widget `{label}` is out of range"))
+                .with_context("field", "widgetInstances")
+                .with_context("widget", label));
+        }
+
+        Ok(())
+    }
+}
+
+/// Accepts the primary label and `widget-<name>` labels made of ASCII letters, digits, `-` and `_`.
+pub(crate) fn is_widget_label(label: &str) -> bool {
+    label == PRIMARY_WIDGET_LABEL || is_widget_instance_label(label)
+}
+
+fn is_widget_instance_label(label: &str) -> bool {
+    label.len() <= WIDGET_LABEL_MAX_LEN
+        && label.strip_prefix(WIDGET_INSTANCE_LABEL_PREFIX).is_some_and(|name| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|character| character.is_ascii_alphanumeric() || character == '-' || character == '_')
+        })
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+/// Language of the texts the backend renders itself: tray labels, notifications and error summaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum Language {
+    #[serde(rename = "zh-CN", alias = "zh")]
+    ZhCn,
+    #[serde(rename = "en")]
+    En,
+}
+
+fn default_schema_version() -> u32 {
+    CONFIG_SCHEMA_VERSION
+}
+
+fn default_widget_scale_percent() -> u16 {
+    WIDGET_DEFAULT_SCALE_PERCENT
+}
+
+fn default_widget_opacity_percent() -> u8 {
+    WIDGET_DEFAULT_OPACITY_PERCENT
+}
+
+fn default_widget_hover_opacity_percent() -> u8 {
+    WIDGET_DEFAULT_HOVER_OPACITY_PERCENT
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_shortcuts() -> BTreeMap<String, String> {
+    BTreeMap::from([
+        (
+            SHORTCUT_TOGGLE_WIDGET_LOCK.to_string(),
+            DEFAULT_TOGGLE_WIDGET_LOCK_SHORTCUT.to_string(),
+        ),
+        (
+            SHORTCUT_QUICK_CAPTURE_SPARK.to_string(),
+            DEFAULT_QUICK_CAPTURE_SPARK_SHORTCUT.to_string(),
+        ),
+    ])
+}
+
+/// Typed view of config.json. Keys the backend does not model (e.g. `logging`) are kept in `extra`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AppConfig {
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+    #[serde(default)]
+    pub widget_view: WidgetView,
+    #[serde(default)]
+    pub widget_visible: bool,
+    #[serde(default)]
+    pub widget_position: Option<WidgetPosition>,
+    #[serde(default)]
+    pub widget_size: Option<WidgetSize>,
+    /// Name of the monitor the widget was last placed on.
+    #[serde(default)]
+    pub widget_monitor: Option<String>,
+    /// Set when the last drag ended next to a work-area edge; kept through scale and resolution changes.
+    #[serde(default)]
+    pub widget_dock: Option<WidgetDock>,
+    #[serde(default = "default_widget_scale_percent")]
+    pub widget_scale_percent: u16,
+    #[serde(default)]
+    pub widget_locked: bool,
+    #[serde(default)]
+    pub widget_align_mode: WidgetAlignMode,
+    #[serde(default = "default_widget_opacity_percent")]
+    pub widget_opacity_percent: u8,
+    #[serde(default = "default_widget_hover_opacity_percent")]
+    pub widget_hover_opacity_percent: u8,
+    #[serde(default = "default_true")]
+    pub widget_click_through: bool,
+    #[serde(default)]
+    pub widget_desktop_layer: bool,
+    /// Widgets opened next to the primary one, keyed by window label.
+    #[serde(default)]
+    pub widget_instances: BTreeMap<String, WidgetInstanceConfig>,
+    #[serde(default)]
+    pub theme: Theme,
+    /// `None` follows the system locale.
+    #[serde(default)]
+    pub language: Option<Language>,
+    #[serde(default = "default_shortcuts")]
+    pub shortcuts: BTreeMap<String, String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            schema_version: CONFIG_SCHEMA_VERSION,
+            widget_view: WidgetView::default(),
+            widget_visible: false,
+            widget_position: None,
+            widget_size: None,
+            widget_monitor: None,
+            widget_dock: None,
+            widget_scale_percent: WIDGET_DEFAULT_SCALE_PERCENT,
+            widget_locked: false,
+            widget_align_mode: WidgetAlignMode::default(),
+            widget_opacity_percent: WIDGET_DEFAULT_OPACITY_PERCENT,
+            widget_hover_opacity_percent: WIDGET_DEFAULT_HOVER_OPACITY_PERCENT,
+            widget_click_through: true,
+            widget_desktop_layer: false,
+            widget_instances: BTreeMap::new(),
+            theme: Theme::default(),
+            language: None,
+            shortcuts: default_shortcuts(),
+            extra: serde_json::Map::new(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// Reads a stored document leniently: fields that cannot be read fall back to their defaults and
+    /// out-of-range values are clamped, so a hand-edited file never blocks startup or loses its other
+    /// settings. Returns the paths of the fields that were dropped.
+    pub(crate) fn from_stored_value(value: serde_json::Value) -> AppResult<(Self, Vec<String>)> {
+        let serde_json::Value::Object(mut document) = value else {
+            return Err(AppError::parse("app config must be a JSON object"));
+        };
+        let mut unreadable_fields = Vec::new();
+        if let Some(serde_json::Value::Object(instances)) = document.get_mut("widgetInstances") {
+            for (label, instance) in instances.iter_mut() {
+                if let serde_json::Value::Object(fields) = instance {
+                    unreadable_fields.extend(
+                        drop_unreadable_fields::<WidgetInstanceConfig>(fields)
+                            .into_iter()
+                            .map(|field| format!("widgetInstances.{label}.{field}")),
+                    );
+                }
+            }
+            unreadable_fields.extend(
+                drop_unreadable_fields::<BTreeMap<String, WidgetInstanceConfig>>(instances)
+                    .into_iter()
+                    .map(|label| format!("widgetInstances.{label}")),
+            );
+        }
+        if let Some(serde_json::Value::Object(shortcuts)) = document.get_mut("shortcuts") {
+            unreadable_fields.extend(
+                drop_unreadable_fields::<BTreeMap<String, String>>(shortcuts)
+                    .into_iter()
+                    .map(|action| format!("shortcuts.{action}")),
+            );
+        }
+        unreadable_fields.extend(drop_unreadable_fields::<Self>(&mut document));
+
+        let mut config = serde_json::from_value::<Self>(serde_json::Value::Object(document))
+            .map_err(|error| AppError::parse(format!("failed to parse app config: {error}")))?;
+        config.schema_version = CONFIG_SCHEMA_VERSION;
+        config.widget_scale_percent = config
+            .widget_scale_percent
+            .clamp(WIDGET_MIN_SCALE_PERCENT, WIDGET_MAX_SCALE_PERCENT);
+        config.widget_opacity_percent = config.widget_opacity_percent.min(WIDGET_MAX_OPACITY_PERCENT);
+        config.widget_hover_opacity_percent = config.widget_hover_opacity_percent.min(WIDGET_MAX_OPACITY_PERCENT);
+        if config.widget_position.is_some_and(|position| !is_position_in_range(position)) {
+            config.widget_position = None;
+        }
+        if config.widget_size.is_some_and(|size| !is_size_in_range(size)) {
+            config.widget_size = None;
+        }
+        config.widget_instances.retain(|label, _| is_widget_instance_label(label));
+        for instance in config.widget_instances.values_mut() {
+            instance.clamp_to_ranges();
+        }
+        config.shortcuts.retain(|_, accelerator| !accelerator.trim().is_empty());
+
+        Ok((config, unreadable_fields))
+    }
+
+    pub(crate) fn validate(&self) -> AppResult<()> {
+        if !(WIDGET_MIN_SCALE_PERCENT..=WIDGET_MAX_SCALE_PERCENT).contains(&self.widget_scale_percent) {
+            return Err(AppError::validation(format!(
+                "widgetScalePercent must be between {WIDGET_MIN_SCALE_PERCENT} and {WIDGET_MAX_SCALE_PERCENT}"
+            ))
+            .with_context("field", "widgetScalePercent")
+            .with_context("value", self.widget_scale_percent));
+        }
+        for (field, value) in [
+            ("widgetOpacityPercent", self.widget_opacity_percent),
+            ("widgetHoverOpacityPercent", self.widget_hover_opacity_percent),
+        ] {
+            if value > WIDGET_MAX_OPACITY_PERCENT {
+                return Err(AppError::validation(format!("{field} must be at most {WIDGET_MAX_OPACITY_PERCENT}"))
+                    .with_context("field", field)
+                    .with_context("value", value));
+            }
+        }
+        if let Some(position) = self.widget_position {
+            if !is_position_in_range(position) {
+                return Err(AppError::validation("widgetPosition is out of range")
+                    .with_context("field", "widgetPosition")
+                    .with_context("x", position.x)
+                    .with_context("y", position.y));
+            }
+        }
+        if let Some(size) = self.widget_size {
+            if !is_size_in_range(size) {
+                return Err(AppError::validation("widgetSize is out of range")
+                    .with_context("field", "widgetSize")
+                    .with_context("width", size.width)
+                    .with_context("height", size.height));
+            }
+        }
+        for (label, instance) in &self.widget_instances {
+            if !is_widget_instance_label(label) {
+                return Err(AppError::validation(format!("`{label}` is not a valid widget label"))
+                    .with_context("field", "widgetInstances")
+                    .with_context("widget", label.as_str()));
+            }
+            instance.validate(label)?;
+        }
+        if let Some((action, _)) = self.shortcuts.iter().find(|(_, accelerator)| accelerator.trim().is_empty()) {
+            return Err(AppError::validation(format!("shortcut `{action}` has an empty accelerator"))
+                .with_context("field", "shortcuts")
+                .with_context("action", action.as_str()));
+        }
+
+        Ok(())
+    }
+
+    /// Labels of every configured widget, primary first.
+    pub(crate) fn widget_labels(&self) -> Vec<String> {
+        std::iter::once(PRIMARY_WIDGET_LABEL.to_string())
+            .chain(self.widget_instances.keys().cloned())
+            .collect()
+    }
+
+    /// State of the widget with `label`, or `None` when no such instance was opened yet.
+    pub(crate) fn widget(&self, label: &str) -> Option<WidgetInstanceConfig> {
+        if label != PRIMARY_WIDGET_LABEL {
+            return self.widget_instances.get(label).cloned();
+        }
+
+        Some(WidgetInstanceConfig {
+            view: self.widget_view.clone(),
+            visible: self.widget_visible,
+            position: self.widget_position,
+            size: self.widget_size,
+            monitor: self.widget_monitor.clone(),
+            dock: self.widget_dock,
+            scale_percent: self.widget_scale_percent,
+            locked: self.widget_locked,
+            align_mode: self.widget_align_mode,
+            opacity_percent: self.widget_opacity_percent,
+            hover_opacity_percent: self.widget_hover_opacity_percent,
+            click_through: self.widget_click_through,
+            desktop_layer: self.widget_desktop_layer,
+        })
+    }
+
+    /// Returns a copy with the widget `label` set to `instance`, adding the instance when it is new.
+    pub(crate) fn with_widget(&self, label: &str, instance: WidgetInstanceConfig) -> Self {
+        if label != PRIMARY_WIDGET_LABEL {
+            let mut next = self.clone();
+            next.widget_instances.insert(label.to_string(), instance);
+            return next;
+        }
+
+        Self {
+            widget_view: instance.view,
+            widget_visible: instance.visible,
+            widget_position: instance.position,
+            widget_size: instance.size,
+            widget_monitor: instance.monitor,
+            widget_dock: instance.dock,
+            widget_scale_percent: instance.scale_percent,
+            widget_locked: instance.locked,
+            widget_align_mode: instance.align_mode,
+            widget_opacity_percent: instance.opacity_percent,
+            widget_hover_opacity_percent: instance.hover_opacity_percent,
+            widget_click_through: instance.click_through,
+            widget_desktop_layer: instance.desktop_layer,
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    /// Applies a JSON merge patch (RFC 7396): objects merge key by key, `null` removes a key.
+    pub(crate) fn patched(&self, patch: &serde_json::Value) -> AppResult<Self> {
+        if !patch.is_object() {
+            return Err(AppError::validation("config patch must be a JSON object"));
+        }
+
+        let mut merged = self.to_value();
+        merge_patch(&mut merged, patch);
+        let mut next = serde_json::from_value::<Self>(merged)
+            .map_err(|error| AppError::validation(format!("invalid config patch: {error}")))?;
+        next.schema_version = CONFIG_SCHEMA_VERSION;
+        next.validate()?;
+
+        Ok(next)
+    }
+}
+
+/// Removes the entries of `object` that `T` cannot read on their own, so each of them falls back to its
+/// default instead of failing the whole document. Returns the removed keys.
+fn drop_unreadable_fields<T: DeserializeOwned>(object: &mut serde_json::Map<String, serde_json::Value>) -> Vec<String> {
+    let unreadable = object
+        .iter()
+        .filter(|(key, value)| {
+            let single_field = serde_json::Map::from_iter([((*key).clone(), (*value).clone())]);
+            serde_json::from_value::<T>(serde_json::Value::Object(single_field)).is_err()
+        })
+        .map(|(key, _)| key.clone())
+        .collect::<Vec<_>>();
+    for key in &unreadable {
+        object.remove(key);
+    }
+
+    unreadable
+}
+
+fn is_position_in_range(position: WidgetPosition) -> bool {
+    position.x.abs() <= WIDGET_POSITION_LIMIT && position.y.abs() <= WIDGET_POSITION_LIMIT
+}
+
+fn is_size_in_range(size: WidgetSize) -> bool {
+    (1..=WIDGET_SIZE_LIMIT).contains(&size.width) && (1..=WIDGET_SIZE_LIMIT).contains(&size.height)
+}
+
+fn merge_patch(target: &mut serde_json::Value, patch: &serde_json::Value) {
+    let serde_json::Value::Object(patch_object) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = serde_json::json!({});
+    }
+    let Some(target_object) = target.as_object_mut() else {
+        return;
+    };
+
+    for (key, value) in patch_object {
+        if value.is_null() {
+            target_object.remove(key);
+        } else {
+            merge_patch(target_object.entry(key.clone()).or_insert(serde_json::Value::Null), value);
+        }
+    }
+}
+
+/// Owns the in-memory config and serializes every read-modify-write of config.json.
+pub(crate) struct ConfigState {
+    path: PathBuf,
+    config: Mutex<AppConfig>,
+}
+
+/// Copies a config.json that could not be read in full to `config.json.corrupt-<ms>` beside it, since the next
+/// update rewrites the original from what was understood.
+fn preserve_unreadable_config(path: &Path, error: AppError) -> AppError {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".corrupt-{}", now_unix_millis()));
+    let copy_path = path.with_file_name(file_name);
+    match fs::copy(path, &copy_path) {
+        Ok(_) => error.with_context("copy", copy_path.display().to_string()),
+        Err(copy_error) => error.with_context("copyError", copy_error.to_string()),
+    }
+}
+
+impl ConfigState {
+    /// Loads config.json, writing defaults when the file is missing. A document that cannot be
+    /// parsed is reported alongside the defaults used in its place; single unreadable fields are
+    /// reported the same way while every other field is kept. Either way a copy of the file is
+    /// kept before any update overwrites it.
+    pub(crate) fn load(path: &Path) -> (Self, Option<AppError>) {
+        let (config, load_error) = match read_app_config_file(path) {
+            Ok(Some(value)) => match AppConfig::from_stored_value(value) {
+                Ok((config, unreadable_fields)) if unreadable_fields.is_empty() => (config, None),
+                Ok((config, unreadable_fields)) => {
+                    let error = AppError::parse("some config.json fields could not be read; they use their defaults")
+                        .with_context("fields", unreadable_fields);
+                    (config, Some(preserve_unreadable_config(path, error)))
+                }
+                Err(error) => (AppConfig::default(), Some(preserve_unreadable_config(path, error))),
+            },
+            Ok(None) => {
+                let config = AppConfig::default();
+                (config.clone(), write_app_config_file(path, &config.to_value()).err())
+            }
+            Err(error) => (AppConfig::default(), Some(preserve_unreadable_config(path, error))),
+        };
+
+        (
+            Self {
+                path: path.to_path_buf(),
+                config: Mutex::new(config),
+            },
+            load_error,
+        )
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn snapshot(&self) -> AppConfig {
+        self.config
+            .lock()
+            .map(|config| config.clone())
+            .unwrap_or_default()
+    }
+
+    /// Validates and persists the result of `mutate`; the in-memory copy only changes once the write succeeded.
+    pub(crate) fn update(&self, mutate: impl FnOnce(&AppConfig) -> AppResult<AppConfig>) -> AppResult<AppConfig> {
+        let mut config = self
+            .config
+            .lock()
+            .map_err(|_| AppError::internal("config state lock is poisoned"))?;
+        let next = mutate(&config)?;
+        next.validate()?;
+        write_app_config_file(&self.path, &next.to_value())?;
+        *config = next.clone();
+
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AppConfig, ConfigState, Language, Theme, WidgetAlignMode, WidgetInstanceConfig, WidgetPosition, WidgetView,
+        PRIMARY_WIDGET_LABEL, SHORTCUT_TOGGLE_WIDGET_LOCK,
+    };
+    use crate::error::ErrorCode;
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn stored_config_fills_defaults_and_clamps_ranges() {
+        let (config, _) = AppConfig::from_stored_value(json!({
+            "schemaVersion": 1,
+            "widgetVisible": true,
+            "widgetScalePercent": 900,
+            "widgetPosition": { "x": 5_000_000, "y": 0 },
+            "logging": { "level": "debug" }
+        }))
+        .expect("stored config should parse");
+
+        assert!(config.widget_visible);
+        assert_eq!(config.widget_scale_percent, 300);
+        assert_eq!(config.widget_position, None);
+        assert_eq!(config.widget_align_mode, WidgetAlignMode::Right);
+        assert_eq!(config.theme, Theme::System);
+        assert_eq!(config.language, None);
+        assert_eq!(config.shortcuts[SHORTCUT_TOGGLE_WIDGET_LOCK], "ctrl+shift+l");
+        assert_eq!(config.to_value()["logging"], json!({ "level": "debug" }));
+    }
+
+    #[test]
+    fn stored_config_drops_only_the_fields_it_cannot_read() {
+        let (config, unreadable_fields) = AppConfig::from_stored_value(json!({
+            "language": "en-US",
+            "widgetScalePercent": "150",
+            "widgetVisible": true,
+            "widgetPosition": { "x": 40, "y": 60 },
+            "shortcuts": { "toggleWidgetLock": "ctrl+alt+l", "quickCaptureSpark": 5 },
+            "widgetInstances": {
+                "widget-today": { "view": { "kind": "today" }, "locked": "yes", "visible": true },
+                "widget-broken": 3
+            }
+        }))
+        .expect("stored config should parse");
+
+        assert_eq!(
+            unreadable_fields,
+            vec![
+                "widgetInstances.widget-today.locked",
+                "widgetInstances.widget-broken",
+                "shortcuts.quickCaptureSpark",
+                "language",
+                "widgetScalePercent",
+            ]
+        );
+        assert_eq!(config.language, None);
+        assert_eq!(config.widget_scale_percent, 100);
+        assert!(config.widget_visible);
+        assert_eq!(config.widget_position, Some(WidgetPosition { x: 40, y: 60 }));
+        assert_eq!(config.shortcuts[SHORTCUT_TOGGLE_WIDGET_LOCK], "ctrl+alt+l");
+        assert!(!config.shortcuts.contains_key("quickCaptureSpark"));
+        let today = config.widget("widget-today").expect("readable instance should be kept");
+        assert!(today.visible && !today.locked);
+        assert_eq!(today.view, WidgetView::Today);
+        assert_eq!(config.widget("widget-broken"), None);
+    }
+
+    #[test]
+    fn patch_merges_fields_and_keeps_unmodelled_keys() {
+        let (config, _) = AppConfig::from_stored_value(json!({ "logging": { "level": "debug", "retainFiles": 3 } }))
+            .expect("stored config should parse");
+
+        let patched = config
+            .patched(&json!({
+                "widgetPosition": { "x": 10, "y": 20 },
+                "theme": "dark",
+                "language": "zh-CN",
+                "logging": { "level": "warn" }
+            }))
+            .expect("patch should apply");
+
+        assert_eq!(patched.widget_position, Some(WidgetPosition { x: 10, y: 20 }));
+        assert_eq!(patched.theme, Theme::Dark);
+        assert_eq!(patched.language, Some(Language::ZhCn));
+        assert_eq!(patched.to_value()["logging"], json!({ "level": "warn", "retainFiles": 3 }));
+
+        let cleared = patched
+            .patched(&json!({ "widgetPosition": null, "language": null }))
+            .expect("patch should apply");
+        assert_eq!(cleared.widget_position, None);
+        assert_eq!(cleared.language, None);
+        let short_language = config.patched(&json!({ "language": "zh" })).expect("patch should apply");
+        assert_eq!(short_language.language, Some(Language::ZhCn));
+    }
+
+    #[test]
+    fn patch_rejects_invalid_values() {
+        let config = AppConfig::default();
+
+        let error = config.patched(&json!({ "widgetScalePercent": 20 })).expect_err("scale should be rejected");
+        assert_eq!(error.code(), ErrorCode::Validation);
+
+        let error = config
+            .patched(&json!({ "widgetSize": { "width": 0, "height": 760 } }))
+            .expect_err("empty size should be rejected");
+        assert_eq!(error.code(), ErrorCode::Validation);
+
+        let error = config.patched(&json!({ "theme": "purple" })).expect_err("theme should be rejected");
+        assert_eq!(error.code(), ErrorCode::Validation);
+
+        let error = config.patched(&json!([1, 2])).expect_err("non-object patch should be rejected");
+        assert_eq!(error.code(), ErrorCode::Validation);
+    }
+
+    #[test]
+    fn widget_instances_are_sanitized_and_addressed_by_label() {
+        let (config, _) = AppConfig::from_stored_value(json!({
+            "widgetLocked": true,
+            "widgetInstances": {
+                "widget-today": {
+                    "view": { "kind": "today" },
+                    "scalePercent": 900,
+                    "opacityPercent": 250,
+                    "visible": true
+                },
+                "main": { "visible": true }
+            }
+        }))
+        .expect("stored config should parse");
+
+        assert_eq!(config.widget_labels(), vec![PRIMARY_WIDGET_LABEL.to_string(), "widget-today".to_string()]);
+        let today = config.widget("widget-today").expect("instance should exist");
+        assert_eq!(today.view, WidgetView::Today);
+        assert_eq!(today.scale_percent, 300);
+        assert_eq!(today.opacity_percent, 100);
+        assert!(today.click_through);
+        assert!(config
+            .widget(PRIMARY_WIDGET_LABEL)
+            .is_some_and(|widget| widget.locked && widget.view == WidgetView::Active));
+
+        let global = WidgetInstanceConfig::for_label("widget-global-g1");
+        assert_eq!(global.view, WidgetView::Global { global_id: "g1".to_string() });
+        let next = config
+            .with_widget("widget-global-g1", global)
+            .with_widget(
+                PRIMARY_WIDGET_LABEL,
+                WidgetInstanceConfig {
+                    view: WidgetView::All,
+                    ..WidgetInstanceConfig::default()
+                },
+            );
+        assert!(!next.widget_locked);
+        assert_eq!(next.to_value()["widgetView"], json!({ "kind": "all" }));
+        assert_eq!(
+            next.to_value()["widgetInstances"]["widget-global-g1"]["view"],
+            json!({ "kind": "global", "globalId": "g1" })
+        );
+
+        let error = next
+            .patched(&json!({ "widgetInstances": { "widget today": {} } }))
+            .expect_err("invalid label should be rejected");
+        assert_eq!(error.code(), ErrorCode::Validation);
+        let error = next
+            .patched(&json!({ "widgetHoverOpacityPercent": 101 }))
+            .expect_err("opacity above 100 should be rejected");
+        assert_eq!(error.code(), ErrorCode::Validation);
+    }
+
+    #[test]
+    fn config_state_writes_defaults_and_persists_updates() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let config_path = temp_dir.path().join("config.json");
+
+        let (state, load_error) = ConfigState::load(&config_path);
+        assert!(load_error.is_none());
+        assert!(config_path.exists());
+
+        state
+            .update(|config| config.patched(&json!({ "widgetVisible": true })))
+            .expect("patch should persist");
+        assert!(state
+            .update(|config| config.patched(&json!({ "widgetScalePercent": 1000 })))
+            .is_err());
+
+        let (reloaded, _) = ConfigState::load(&config_path);
+        assert!(reloaded.snapshot().widget_visible);
+        assert_eq!(reloaded.snapshot().widget_scale_percent, 100);
+    }
+
+    #[test]
+    fn config_state_reports_unreadable_fields_and_keeps_the_rest() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let config_path = temp_dir.path().join("config.json");
+        fs::write(&config_path, r#"{ "language": "en-US", "widgetLocked": true }"#).expect("write config");
+
+        let (state, load_error) = ConfigState::load(&config_path);
+
+        let load_error = load_error.expect("unreadable field should be reported");
+        assert_eq!(load_error.code(), ErrorCode::Parse);
+        assert_eq!(serde_json::Value::from(load_error)["context"]["fields"], json!(["language"]));
+        assert!(state.snapshot().widget_locked);
+    }
+
+    #[test]
+    fn config_state_keeps_unparseable_file_on_disk() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let config_path = temp_dir.path().join("config.json");
+        fs::write(&config_path, "{broken").expect("write broken config");
+
+        let (state, load_error) = ConfigState::load(&config_path);
+        let load_error = load_error.expect("a broken config should be reported");
+        assert_eq!(load_error.code(), ErrorCode::Parse);
+        assert_eq!(state.snapshot(), AppConfig::default());
+
+        state
+            .update(|config| {
+                Ok(AppConfig {
+                    widget_visible: true,
+                    ..config.clone()
+                })
+            })
+            .expect("update should succeed");
+        assert!(fs::read_to_string(&config_path).expect("read config").contains("\"widgetVisible\": true"));
+        let copy_path = serde_json::Value::from(load_error)["context"]["copy"]
+            .as_str()
+            .map(std::path::PathBuf::from)
+            .expect("the error should name the copy");
+        assert_eq!(copy_path.parent(), Some(temp_dir.path()));
+        assert_eq!(fs::read_to_string(copy_path).expect("read copy"), "{broken");
+    }
+}
@@ -0,0 +1,295 @@
+use std::{
+    backtrace::Backtrace,
+    fs,
+    panic::PanicHookInfo,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, Runtime};
+
+use crate::{
+    ensure_directory_exists,
+    error::{AppError, AppResult},
+    logging::{append_jsonl, create_log_entry, now_unix_millis, LoggingState, LOG_FILE_NAME},
+};
+
+const CRASH_REPORT_PREFIX: &str = "crash-";
+const CRASH_REPORT_SUFFIX: &str = ".json";
+
+/// Tells apart reports from threads that panic within the same millisecond.
+static CRASH_REPORT_SEQUENCE: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CrashLocation {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CrashReport {
+    pub timestamp: u64,
+    pub message: String,
+    pub location: Option<CrashLocation>,
+    pub thread: Option<String>,
+    pub backtrace: String,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    /// Last in-memory log entries before the panic, oldest first.
+    pub recent_events: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CrashReportSummary {
+    pub file_name: String,
+    pub timestamp: u64,
+    pub message: String,
+}
+
+fn panic_message(info: &PanicHookInfo<'_>) -> String {
+    if let Some(message) = info.payload().downcast_ref::<&str>() {
+        return (*message).to_string();
+    }
+    if let Some(message) = info.payload().downcast_ref::<String>() {
+        return message.clone();
+    }
+
+    "unknown panic payload".to_string()
+}
+
+fn is_crash_report_file_name(file_name: &str) -> bool {
+    file_name.starts_with(CRASH_REPORT_PREFIX)
+        && file_name.ends_with(CRASH_REPORT_SUFFIX)
+        && Path::new(file_name).file_name().is_some_and(|name| name == file_name)
+}
+
+pub(crate) fn write_crash_report(logs_dir: &Path, report: &CrashReport) -> AppResult<PathBuf> {
+    ensure_directory_exists(logs_dir)?;
+
+    let sequence = CRASH_REPORT_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let path = logs_dir.join(format!(
+        "{CRASH_REPORT_PREFIX}{}-{}-{sequence}{CRASH_REPORT_SUFFIX}",
+        report.timestamp,
+        std::process::id()
+    ));
+    let serialized = serde_json::to_string_pretty(report)
+        .map_err(|error| AppError::internal(format!("failed to serialize crash report: {error}")))?;
+    fs::write(&path, serialized)
+        .map_err(|error| AppError::io(format!("failed to write crash report `{}`: {error}", path.display())))?;
+
+    Ok(path)
+}
+
+pub(crate) fn read_crash_report(logs_dir: &Path, file_name: &str) -> AppResult<CrashReport> {
+    if !is_crash_report_file_name(file_name) {
+        return Err(AppError::validation(format!("`{file_name}` is not a crash report file name")));
+    }
+
+    let path = logs_dir.join(file_name);
+    if !path.exists() {
+        return Err(AppError::not_found(format!("crash report `{file_name}` does not exist")));
+    }
+    let raw = fs::read_to_string(&path)
+        .map_err(|error| AppError::io(format!("failed to read crash report `{}`: {error}", path.display())))?;
+    serde_json::from_str::<CrashReport>(&raw)
+        .map_err(|error| AppError::parse(format!("failed to parse crash report `{}`: {error}", path.display())))
+}
+
+pub(crate) fn list_crash_report_file_names(logs_dir: &Path) -> AppResult<Vec<String>> {
+    if !logs_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(logs_dir)
+        .map_err(|error| AppError::io(format!("failed to read log directory `{}`: {error}", logs_dir.display())))?;
+    Ok(entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|file_name| is_crash_report_file_name(file_name))
+        .collect())
+}
+
+/// Lists readable crash reports, newest first.
+pub(crate) fn list_crash_reports(logs_dir: &Path) -> AppResult<Vec<CrashReportSummary>> {
+    let mut summaries = list_crash_report_file_names(logs_dir)?
+        .into_iter()
+        .filter_map(|file_name| {
+            let report = read_crash_report(logs_dir, &file_name).ok()?;
+            Some(CrashReportSummary {
+                file_name,
+                timestamp: report.timestamp,
+                message: report.message,
+            })
+        })
+        .collect::<Vec<_>>();
+    summaries.sort_by_key(|summary| std::cmp::Reverse(summary.timestamp));
+
+    Ok(summaries)
+}
+
+pub(crate) fn clear_crash_reports(logs_dir: &Path) -> AppResult<usize> {
+    let file_names = list_crash_report_file_names(logs_dir)?;
+    for file_name in &file_names {
+        let path = logs_dir.join(file_name);
+        fs::remove_file(&path)
+            .map_err(|error| AppError::io(format!("failed to remove crash report `{}`: {error}", path.display())))?;
+    }
+
+    Ok(file_names.len())
+}
+
+/// Completes an atomic write that stopped between removing the old file and renaming its temp file. Runs at startup,
+/// before anything reads the file; the panic hook leaves the temp file alone because the write may still be running.
+pub(crate) fn recover_interrupted_write(path: &Path) -> AppResult<bool> {
+    let temp_path = path.with_extension("json.tmp");
+    if path.exists() || !temp_path.exists() {
+        return Ok(false);
+    }
+
+    fs::rename(&temp_path, path)
+        .map_err(|error| AppError::io(format!("failed to recover `{}`: {error}", path.display())))?;
+    Ok(true)
+}
+
+/// Writes `crash-<ts>-<pid>-<n>.json` to the logs directory on panic, then defers to the previous hook.
+///
+/// The panicking thread may hold any logging lock, so the hook only ever `try_lock`s them and appends its own
+/// `app.panic` entry straight to `events.jsonl` instead of going through `log_project_event`.
+pub(crate) fn install_panic_hook<R: Runtime>(app: &tauri::AppHandle<R>, logs_dir: &Path) {
+    let app_handle = app.clone();
+    let logs_dir = logs_dir.to_path_buf();
+    let app_version = app.package_info().version.to_string();
+    let previous_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        let logging_state = app_handle.try_state::<LoggingState>();
+        let report = CrashReport {
+            timestamp: u64::try_from(now_unix_millis()).unwrap_or(u64::MAX),
+            message: panic_message(info),
+            location: info.location().map(|location| CrashLocation {
+                file: location.file().to_string(),
+                line: location.line(),
+                column: location.column(),
+            }),
+            thread: std::thread::current().name().map(str::to_string),
+            backtrace: Backtrace::force_capture().to_string(),
+            app_version: app_version.clone(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            recent_events: logging_state
+                .as_ref()
+                .map(|state| state.recent_entries())
+                .unwrap_or_default(),
+        };
+
+        // Queued entries go first so the panic entry stays last in the file.
+        if let Some(logging_state) = logging_state {
+            logging_state.try_flush();
+        }
+        match write_crash_report(&logs_dir, &report) {
+            Ok(path) => {
+                let entry = create_log_entry(
+                    "error",
+                    "app.panic",
+                    "Application panicked; crash report written.",
+                    Some(serde_json::json!({
+                        "path": path.display().to_string(),
+                        "message": report.message,
+                        "location": report.location,
+                    })),
+                );
+                if let Err(error) = append_jsonl(&logs_dir.join(LOG_FILE_NAME), &entry) {
+                    eprintln!("[crash] {error}");
+                }
+            }
+            Err(error) => eprintln!("[crash] {error}"),
+        }
+
+        previous_hook(info);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        clear_crash_reports, list_crash_reports, read_crash_report, recover_interrupted_write, write_crash_report,
+        CrashReport,
+    };
+    use crate::error::ErrorCode;
+    use serde_json::json;
+    use std::fs;
+
+    fn make_report(timestamp: u64, message: &str) -> CrashReport {
+        CrashReport {
+            timestamp,
+            message: message.to_string(),
+            location: None,
+            thread: Some("main".to_string()),
+            backtrace: String::new(),
+            app_version: "0.1.0".to_string(),
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            recent_events: vec![json!({ "event": "app.startup" })],
+        }
+    }
+
+    #[test]
+    fn crash_reports_are_listed_newest_first_and_cleared() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        write_crash_report(temp_dir.path(), &make_report(10, "older")).expect("write should succeed");
+        write_crash_report(temp_dir.path(), &make_report(20, "newer")).expect("write should succeed");
+        fs::write(temp_dir.path().join("events.jsonl"), "{}\n").expect("write log file");
+
+        let summaries = list_crash_reports(temp_dir.path()).expect("list should succeed");
+        assert_eq!(
+            summaries.iter().map(|summary| summary.message.as_str()).collect::<Vec<_>>(),
+            vec!["newer", "older"]
+        );
+
+        let report = read_crash_report(temp_dir.path(), &summaries[0].file_name).expect("read should succeed");
+        assert_eq!(report.recent_events[0]["event"], "app.startup");
+
+        assert_eq!(clear_crash_reports(temp_dir.path()).expect("clear should succeed"), 2);
+        assert!(list_crash_reports(temp_dir.path()).expect("list should succeed").is_empty());
+        assert!(temp_dir.path().join("events.jsonl").exists());
+    }
+
+    #[test]
+    fn crash_reports_from_the_same_millisecond_do_not_overwrite_each_other() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let first = write_crash_report(temp_dir.path(), &make_report(30, "first")).expect("write should succeed");
+        let second = write_crash_report(temp_dir.path(), &make_report(30, "second")).expect("write should succeed");
+
+        assert_ne!(first, second);
+        assert_eq!(list_crash_reports(temp_dir.path()).expect("list should succeed").len(), 2);
+    }
+
+    #[test]
+    fn read_crash_report_rejects_paths_outside_logs_dir() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+
+        let error = read_crash_report(temp_dir.path(), "../crash-1.json").expect_err("should reject path");
+        assert_eq!(error.code(), ErrorCode::Validation);
+        assert!(error.message().contains("not a crash report file name"));
+
+        let error = read_crash_report(temp_dir.path(), "crash-1.json").expect_err("should report missing file");
+        assert_eq!(error.code(), ErrorCode::NotFound);
+    }
+
+    #[test]
+    fn recover_interrupted_write_restores_orphaned_temp_file() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let data_file_path = temp_dir.path().join("data.json");
+        fs::write(data_file_path.with_extension("json.tmp"), "{\"value\":1}").expect("write temp file");
+
+        assert!(recover_interrupted_write(&data_file_path).expect("recover should succeed"));
+        assert_eq!(fs::read_to_string(&data_file_path).expect("data file should exist"), "{\"value\":1}");
+        assert!(!recover_interrupted_write(&data_file_path).expect("recover should succeed"));
+    }
+}
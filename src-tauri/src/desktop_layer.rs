@@ -0,0 +1,172 @@
+use serde::Serialize;
+use tauri::WebviewWindow;
+
+use crate::{error::AppResult, widget_geometry::WidgetBounds};
+
+/// How a widget window was pinned to the desktop layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DesktopLayerBackend {
+    /// `_NET_WM_WINDOW_TYPE_DESKTOP` plus the below, sticky and skip-taskbar/pager states.
+    X11,
+    /// A bottom-layer surface of the wlr layer-shell protocol, through libgtk-layer-shell.
+    LayerShell,
+    /// Only "always on bottom" from the window builder: other platforms, or Wayland without layer-shell.
+    KeepBelow,
+}
+
+/// Pins a widget window that has been built hidden to the desktop layer. Runs on the main thread before the first
+/// `show()`, because window types and layer surfaces are fixed once the window is mapped. `bounds` places a
+/// layer-shell surface, which ignores regular window positioning.
+#[cfg(target_os = "linux")]
+pub(crate) fn apply_desktop_layer(
+    window: &WebviewWindow,
+    bounds: Option<WidgetBounds>,
+) -> AppResult<DesktopLayerBackend> {
+    linux::apply(window, bounds)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn apply_desktop_layer(
+    _window: &WebviewWindow,
+    _bounds: Option<WidgetBounds>,
+) -> AppResult<DesktopLayerBackend> {
+    Ok(DesktopLayerBackend::KeepBelow)
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{
+        ffi::{c_char, c_int},
+        sync::OnceLock,
+    };
+
+    use gtk::{
+        gdk,
+        glib::{self, translate::ToGlibPtr},
+        prelude::*,
+    };
+    use tauri::WebviewWindow;
+
+    use super::DesktopLayerBackend;
+    use crate::{error::AppResult, widget_geometry::WidgetBounds};
+
+    const LAYER_SHELL_LIBRARY: &str = "libgtk-layer-shell.so.0";
+    const LAYER_SHELL_NAMESPACE: &[u8] = b"daily-widget\0";
+    const LAYER_BOTTOM: c_int = 1;
+    const EDGE_LEFT: c_int = 0;
+    const EDGE_RIGHT: c_int = 1;
+    const EDGE_TOP: c_int = 2;
+
+    type GtkWindowPtr = *mut gtk::ffi::GtkWindow;
+
+    /// Entry points of libgtk-layer-shell, loaded at runtime so the app still starts where it is not installed.
+    struct LayerShell {
+        _library: libloading::Library,
+        is_supported: Option<unsafe extern "C" fn() -> glib::ffi::gboolean>,
+        init_for_window: unsafe extern "C" fn(GtkWindowPtr),
+        set_namespace: unsafe extern "C" fn(GtkWindowPtr, *const c_char),
+        set_layer: unsafe extern "C" fn(GtkWindowPtr, c_int),
+        set_anchor: unsafe extern "C" fn(GtkWindowPtr, c_int, glib::ffi::gboolean),
+        set_margin: unsafe extern "C" fn(GtkWindowPtr, c_int, c_int),
+        set_keyboard_interactivity: unsafe extern "C" fn(GtkWindowPtr, glib::ffi::gboolean),
+    }
+
+    impl LayerShell {
+        fn load() -> Option<Self> {
+            // SAFETY: the library is only initialized by the dynamic loader, and every symbol is looked up with the
+            // signature documented by gtk-layer-shell 0.x.
+            unsafe {
+                let library = libloading::Library::new(LAYER_SHELL_LIBRARY).ok()?;
+                let is_supported = library.get(b"gtk_layer_is_supported\0").ok().map(|symbol| *symbol);
+                let init_for_window = *library.get(b"gtk_layer_init_for_window\0").ok()?;
+                let set_namespace = *library.get(b"gtk_layer_set_namespace\0").ok()?;
+                let set_layer = *library.get(b"gtk_layer_set_layer\0").ok()?;
+                let set_anchor = *library.get(b"gtk_layer_set_anchor\0").ok()?;
+                let set_margin = *library.get(b"gtk_layer_set_margin\0").ok()?;
+                let set_keyboard_interactivity = *library.get(b"gtk_layer_set_keyboard_interactivity\0").ok()?;
+                Some(Self {
+                    _library: library,
+                    is_supported,
+                    init_for_window,
+                    set_namespace,
+                    set_layer,
+                    set_anchor,
+                    set_margin,
+                    set_keyboard_interactivity,
+                })
+            }
+        }
+
+        fn shared() -> Option<&'static Self> {
+            static LAYER_SHELL: OnceLock<Option<LayerShell>> = OnceLock::new();
+            LAYER_SHELL.get_or_init(Self::load).as_ref()
+        }
+
+        fn supported(&self) -> bool {
+            // SAFETY: takes no arguments; only called on the main thread after GTK is initialized.
+            self.is_supported.is_none_or(|is_supported| unsafe { is_supported() } != glib::ffi::GFALSE)
+        }
+
+        /// Turns the unrealized `window` into a bottom-layer surface anchored at `bounds`, or at the top-right
+        /// corner of its output when nothing was saved yet.
+        fn attach(&self, window: &gtk::Window, bounds: Option<WidgetBounds>) {
+            let pointer: GtkWindowPtr = window.to_glib_none().0;
+            let margins = bounds.and_then(|bounds| monitor_offset(window, bounds));
+            // SAFETY: `pointer` is a live GtkWindow owned by `window` and the calls happen on the main thread,
+            // before the window is realized, as gtk_layer_init_for_window requires.
+            unsafe {
+                (self.init_for_window)(pointer);
+                (self.set_namespace)(pointer, LAYER_SHELL_NAMESPACE.as_ptr().cast());
+                (self.set_layer)(pointer, LAYER_BOTTOM);
+                (self.set_keyboard_interactivity)(pointer, glib::ffi::GFALSE);
+                (self.set_anchor)(pointer, EDGE_TOP, glib::ffi::GTRUE);
+                match margins {
+                    Some((left, top)) => {
+                        (self.set_anchor)(pointer, EDGE_LEFT, glib::ffi::GTRUE);
+                        (self.set_margin)(pointer, EDGE_LEFT, left);
+                        (self.set_margin)(pointer, EDGE_TOP, top);
+                    }
+                    None => (self.set_anchor)(pointer, EDGE_RIGHT, glib::ffi::GTRUE),
+                }
+            }
+        }
+    }
+
+    /// Offset of `bounds` from the monitor containing its top-left corner, in logical pixels.
+    fn monitor_offset(window: &gtk::Window, bounds: WidgetBounds) -> Option<(c_int, c_int)> {
+        let (x, y) = (bounds.x.round() as i32, bounds.y.round() as i32);
+        let geometry = window.display().monitor_at_point(x, y)?.geometry();
+        Some(((x - geometry.x()).max(0), (y - geometry.y()).max(0)))
+    }
+
+    fn is_wayland(window: &gtk::Window) -> bool {
+        window.display().type_().name() == "GdkWaylandDisplay"
+    }
+
+    pub(super) fn apply(window: &WebviewWindow, bounds: Option<WidgetBounds>) -> AppResult<DesktopLayerBackend> {
+        let application_window = window.gtk_window()?;
+        let gtk_window = application_window.upcast_ref::<gtk::Window>();
+        if gtk_window.is_visible() {
+            gtk_window.hide();
+        }
+
+        if is_wayland(gtk_window) {
+            let Some(layer_shell) = LayerShell::shared().filter(|layer_shell| layer_shell.supported()) else {
+                return Ok(DesktopLayerBackend::KeepBelow);
+            };
+            if gtk_window.is_realized() {
+                gtk_window.unrealize();
+            }
+            layer_shell.attach(gtk_window, bounds);
+            return Ok(DesktopLayerBackend::LayerShell);
+        }
+
+        gtk_window.set_type_hint(gdk::WindowTypeHint::Desktop);
+        gtk_window.set_keep_below(true);
+        gtk_window.set_skip_taskbar_hint(true);
+        gtk_window.set_skip_pager_hint(true);
+        gtk_window.stick();
+        Ok(DesktopLayerBackend::X11)
+    }
+}
@@ -0,0 +1,355 @@
+use std::{
+    fs,
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Component, Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+    crash,
+    error::{AppError, AppResult},
+    logging, ProjectDirectories, CONFIG_FILE_NAME, DATA_FILE_NAME,
+};
+
+const DIAGNOSTICS_FILE_PREFIX: &str = "daily-diagnostics-";
+const REDACTED_DATA_FILE_NAME: &str = "data.redacted.json";
+const SYSTEM_INFO_FILE_NAME: &str = "system.json";
+/// Free-text fields in data.json that are replaced by a hash in the exported copy.
+const REDACTED_DATA_KEYS: [&str; 3] = ["title", "description", "content"];
+
+fn hash_text(text: &str) -> String {
+    let digest = Sha256::digest(text.as_bytes());
+    let hex = digest.iter().map(|byte| format!("{byte:02x}")).collect::<String>();
+    format!("sha256:{hex}")
+}
+
+/// Replaces user-entered text with stable hashes so equal titles still compare equal.
+pub(crate) fn redact_app_data(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(object) => serde_json::Value::Object(
+            object
+                .iter()
+                .map(|(key, value)| {
+                    let redacted = match value {
+                        serde_json::Value::String(text) if REDACTED_DATA_KEYS.contains(&key.as_str()) => {
+                            serde_json::Value::String(hash_text(text))
+                        }
+                        _ => redact_app_data(value),
+                    };
+                    (key.clone(), redacted)
+                })
+                .collect(),
+        ),
+        serde_json::Value::Array(items) => serde_json::Value::Array(items.iter().map(redact_app_data).collect()),
+        _ => value.clone(),
+    }
+}
+
+fn read_os_version() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        let os_release = fs::read_to_string("/etc/os-release").ok()?;
+        os_release
+            .lines()
+            .find_map(|line| line.strip_prefix("PRETTY_NAME="))
+            .map(|value| value.trim_matches('"').to_string())
+    }
+
+    #[cfg(windows)]
+    {
+        read_windows_version()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let output = std::process::Command::new("/usr/bin/sw_vers").arg("-productVersion").output().ok()?;
+        let version = String::from_utf8(output.stdout).ok()?;
+        let version = version.trim();
+        (output.status.success() && !version.is_empty()).then(|| format!("macOS {version}"))
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+    {
+        None
+    }
+}
+
+/// Reads the edition, release and build from `HKLM\SOFTWARE\Microsoft\Windows NT\CurrentVersion`, e.g.
+/// `Windows 10 Pro 23H2 (build 22631)`. Windows 11 still calls itself Windows 10 there; the build tells them apart.
+#[cfg(windows)]
+fn read_windows_version() -> Option<String> {
+    use windows_sys::Win32::System::Registry::{RegGetValueW, HKEY_LOCAL_MACHINE, RRF_RT_REG_SZ};
+
+    fn wide(text: &str) -> Vec<u16> {
+        text.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    let key = wide(r"SOFTWARE\Microsoft\Windows NT\CurrentVersion");
+    let read_value = |name: &str| {
+        let name = wide(name);
+        let mut buffer = [0u16; 256];
+        let mut size = std::mem::size_of_val(&buffer) as u32;
+        let status = unsafe {
+            RegGetValueW(
+                HKEY_LOCAL_MACHINE,
+                key.as_ptr(),
+                name.as_ptr(),
+                RRF_RT_REG_SZ,
+                std::ptr::null_mut(),
+                buffer.as_mut_ptr().cast(),
+                &mut size,
+            )
+        };
+        // The size is in bytes and includes the terminating null; 0 is ERROR_SUCCESS.
+        let length = size as usize / 2;
+        (status == 0 && length > 1).then(|| String::from_utf16_lossy(&buffer[..length - 1]))
+    };
+
+    let product = read_value("ProductName")?;
+    let release = read_value("DisplayVersion").map_or(String::new(), |release| format!(" {release}"));
+    let build = read_value("CurrentBuild").map_or(String::new(), |build| format!(" (build {build})"));
+    Some(format!("{product}{release}{build}"))
+}
+
+fn build_system_info(directories: &ProjectDirectories, app_version: &str) -> serde_json::Value {
+    serde_json::json!({
+        "generatedAt": logging::now_unix_millis(),
+        "appVersion": app_version,
+        "os": std::env::consts::OS,
+        "osFamily": std::env::consts::FAMILY,
+        "osVersion": read_os_version(),
+        "arch": std::env::consts::ARCH,
+        "directories": {
+            "root": directories.root.display().to_string(),
+            "data": directories.data.display().to_string(),
+            "logs": directories.logs.display().to_string(),
+            "backup": directories.backup.display().to_string(),
+        }
+    })
+}
+
+fn add_bytes(
+    writer: &mut ZipWriter<File>,
+    name: &str,
+    bytes: &[u8],
+    compression: CompressionMethod,
+) -> AppResult<()> {
+    let options = SimpleFileOptions::default().compression_method(compression);
+    writer
+        .start_file(name, options)
+        .map_err(|error| AppError::io(format!("failed to add `{name}` to diagnostics archive: {error}")))?;
+    writer
+        .write_all(bytes)
+        .map_err(|error| AppError::io(format!("failed to write `{name}` to diagnostics archive: {error}")))
+}
+
+fn add_file(writer: &mut ZipWriter<File>, name: &str, path: &Path) -> AppResult<()> {
+    let bytes = fs::read(path).map_err(|error| AppError::io(format!("failed to read `{}`: {error}", path.display())))?;
+    let compression = if path.extension().is_some_and(|extension| extension == "gz") {
+        CompressionMethod::Stored
+    } else {
+        CompressionMethod::Deflated
+    };
+    add_bytes(writer, name, &bytes, compression)
+}
+
+fn archive_entry_name(directory: &str, path: &Path) -> Option<String> {
+    let file_name = path.file_name()?.to_str()?;
+    Some(format!("{directory}/{file_name}"))
+}
+
+/// Resolves the part of `path` that exists through symlinks, so a link into the data directory is caught too.
+fn resolve_existing_ancestor(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find_map(|ancestor| Some(fs::canonicalize(ancestor).ok()?.join(path.strip_prefix(ancestor).ok()?)))
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// The destination comes from the webview, so it must be an absolute path outside the app's data directory.
+/// Existing files are refused when the archive is created.
+fn check_destination(directories: &ProjectDirectories, destination: &Path) -> AppResult<()> {
+    let path = destination.display().to_string();
+    if !destination.is_absolute() || destination.components().any(|component| component == Component::ParentDir) {
+        return Err(AppError::validation("diagnostics destination must be an absolute path").with_context("path", path));
+    }
+
+    let data_dir = fs::canonicalize(&directories.data).unwrap_or_else(|_| directories.data.clone());
+    if destination.starts_with(&directories.data) || resolve_existing_ancestor(destination).starts_with(&data_dir) {
+        return Err(AppError::validation("diagnostics destination must be outside the data directory")
+            .with_context("path", path));
+    }
+
+    Ok(())
+}
+
+/// Bundles logs, crash reports, config, system info and a redacted data.json into one zip archive.
+pub(crate) fn export_diagnostics(
+    directories: &ProjectDirectories,
+    app_version: &str,
+    destination: Option<&Path>,
+) -> AppResult<PathBuf> {
+    let archive_path = match destination {
+        Some(destination) => {
+            check_destination(directories, destination)?;
+            destination.to_path_buf()
+        }
+        None => directories.backup.join(format!(
+            "{DIAGNOSTICS_FILE_PREFIX}{}.zip",
+            logging::now_unix_millis()
+        )),
+    };
+    if let Some(parent_dir) = archive_path.parent() {
+        crate::ensure_directory_exists(parent_dir)?;
+    }
+
+    let archive = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&archive_path)
+        .map_err(|error| {
+            AppError::from_io(
+                format!("failed to create diagnostics archive `{}`: {error}", archive_path.display()),
+                &error,
+            )
+        })?;
+    let mut writer = ZipWriter::new(archive);
+
+    let system_info = serde_json::to_vec_pretty(&build_system_info(directories, app_version))
+        .map_err(|error| AppError::internal(format!("failed to serialize system info: {error}")))?;
+    add_bytes(&mut writer, SYSTEM_INFO_FILE_NAME, &system_info, CompressionMethod::Deflated)?;
+
+    for log_path in logging::list_log_files_newest_first(&directories.logs)? {
+        if let Some(name) = archive_entry_name("logs", &log_path) {
+            add_file(&mut writer, &name, &log_path)?;
+        }
+    }
+    for file_name in crash::list_crash_report_file_names(&directories.logs)? {
+        add_file(&mut writer, &format!("crashes/{file_name}"), &directories.logs.join(&file_name))?;
+    }
+
+    let config_path = directories.data.join(CONFIG_FILE_NAME);
+    if config_path.exists() {
+        add_file(&mut writer, CONFIG_FILE_NAME, &config_path)?;
+    }
+
+    let data_path = directories.data.join(DATA_FILE_NAME);
+    if data_path.exists() {
+        let raw = fs::read_to_string(&data_path)
+            .map_err(|error| AppError::io(format!("failed to read `{}`: {error}", data_path.display())))?;
+        let redacted = match serde_json::from_str::<serde_json::Value>(&raw) {
+            Ok(data) => redact_app_data(&data),
+            Err(error) => serde_json::json!({ "error": format!("data.json is not valid JSON: {error}") }),
+        };
+        let serialized = serde_json::to_vec_pretty(&redacted)
+            .map_err(|error| AppError::internal(format!("failed to serialize redacted data: {error}")))?;
+        add_bytes(&mut writer, REDACTED_DATA_FILE_NAME, &serialized, CompressionMethod::Deflated)?;
+    }
+
+    writer
+        .finish()
+        .map_err(|error| AppError::io(format!("failed to finalize diagnostics archive: {error}")))?;
+
+    Ok(archive_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export_diagnostics, hash_text, redact_app_data};
+    use crate::{error::ErrorCode, ProjectDirectories};
+    use serde_json::json;
+    use std::{fs, io::Read};
+
+    #[test]
+    fn redact_app_data_hashes_free_text_fields_only() {
+        let data = json!({
+            "schemaVersion": 2,
+            "tasks": [{ "id": "t-1", "title": "Buy milk", "tags": ["home"], "status": "active" }],
+            "taskLogs": [{ "id": "l-1", "content": "called the shop" }]
+        });
+
+        let redacted = redact_app_data(&data);
+
+        assert_eq!(redacted["tasks"][0]["title"], json!(hash_text("Buy milk")));
+        assert_eq!(redacted["tasks"][0]["tags"], json!(["home"]));
+        assert_eq!(redacted["tasks"][0]["id"], "t-1");
+        assert_eq!(redacted["taskLogs"][0]["content"], json!(hash_text("called the shop")));
+        assert!(hash_text("Buy milk").starts_with("sha256:"));
+    }
+
+    fn test_directories(temp_dir: &tempfile::TempDir) -> ProjectDirectories {
+        let root = temp_dir.path().join(".daily");
+        ProjectDirectories {
+            data: root.join("data"),
+            logs: root.join("logs"),
+            backup: root.join("backup"),
+            root,
+        }
+    }
+
+    #[test]
+    fn export_diagnostics_bundles_logs_config_and_redacted_data() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let directories = test_directories(&temp_dir);
+        fs::create_dir_all(&directories.data).expect("create data dir");
+        fs::create_dir_all(&directories.logs).expect("create logs dir");
+        fs::write(directories.logs.join("events.jsonl"), "{}\n").expect("write live log");
+        fs::write(directories.logs.join("events-2026-10-16.jsonl"), "{}\n").expect("write rotated log");
+        fs::write(directories.logs.join("crash-1.json"), "{}").expect("write crash report");
+        fs::write(directories.data.join("config.json"), "{\"widgetVisible\":true}").expect("write config");
+        fs::write(directories.data.join("data.json"), "{\"tasks\":[{\"title\":\"secret\"}]}").expect("write data");
+
+        let archive_path = export_diagnostics(&directories, "0.1.0", None).expect("export should succeed");
+
+        let mut archive = zip::ZipArchive::new(fs::File::open(&archive_path).expect("open archive"))
+            .expect("read archive");
+        let mut names = archive.file_names().map(str::to_string).collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "config.json",
+                "crashes/crash-1.json",
+                "data.redacted.json",
+                "logs/events-2026-10-16.jsonl",
+                "logs/events.jsonl",
+                "system.json",
+            ]
+        );
+
+        let mut redacted = String::new();
+        archive
+            .by_name("data.redacted.json")
+            .expect("redacted data entry")
+            .read_to_string(&mut redacted)
+            .expect("read redacted data");
+        assert!(!redacted.contains("secret"));
+    }
+    #[test]
+    fn export_diagnostics_refuses_existing_files_and_the_data_directory() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let directories = test_directories(&temp_dir);
+        fs::create_dir_all(&directories.data).expect("create data dir");
+        let existing = temp_dir.path().join("notes.txt");
+        fs::write(&existing, "keep me").expect("write existing file");
+
+        let code = |destination: &std::path::Path| {
+            export_diagnostics(&directories, "0.1.0", Some(destination))
+                .expect_err("export should be refused")
+                .code()
+        };
+        assert_eq!(code(&existing), ErrorCode::Conflict);
+        assert_eq!(fs::read_to_string(&existing).expect("read existing file"), "keep me");
+        assert_eq!(code(&directories.data.join("bundle.zip")), ErrorCode::Validation);
+        assert_eq!(code(&directories.data.join("nested/../bundle.zip")), ErrorCode::Validation);
+        assert_eq!(code(std::path::Path::new("bundle.zip")), ErrorCode::Validation);
+        assert!(!directories.data.join("bundle.zip").exists());
+
+        let destination = temp_dir.path().join("exports/bundle.zip");
+        let archive_path =
+            export_diagnostics(&directories, "0.1.0", Some(&destination)).expect("export should succeed");
+        assert_eq!(archive_path, destination);
+    }
+}
@@ -0,0 +1,198 @@
+use std::fmt;
+
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+use crate::i18n::{self, Message};
+
+/// Machine-readable error category the frontend can branch on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ErrorCode {
+    Io,
+    Parse,
+    Validation,
+    Conflict,
+    NotFound,
+    Locked,
+    WindowUnavailable,
+    Internal,
+}
+
+impl ErrorCode {
+    fn summary(self) -> Message {
+        match self {
+            Self::Io => Message::ErrorIo,
+            Self::Parse => Message::ErrorParse,
+            Self::Validation => Message::ErrorValidation,
+            Self::Conflict => Message::ErrorConflict,
+            Self::NotFound => Message::ErrorNotFound,
+            Self::Locked => Message::ErrorLocked,
+            Self::WindowUnavailable => Message::ErrorWindowUnavailable,
+            Self::Internal => Message::ErrorInternal,
+        }
+    }
+}
+
+/// Error returned by every Tauri command, serialized as `{ code, message, summary, context }`. `message` is the
+/// English detail that also goes to the logs; `summary` describes the code in the configured language.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AppError {
+    code: ErrorCode,
+    message: String,
+    context: serde_json::Map<String, serde_json::Value>,
+}
+
+impl AppError {
+    pub(crate) fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            context: serde_json::Map::new(),
+        }
+    }
+
+    pub(crate) fn io(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Io, message)
+    }
+
+    /// Maps the io error kind onto a code: a file held by another process becomes `Locked`,
+    /// an existing target becomes `Conflict`, everything else stays `Io`.
+    pub(crate) fn from_io(message: impl Into<String>, error: &std::io::Error) -> Self {
+        let code = match error.kind() {
+            std::io::ErrorKind::NotFound => ErrorCode::NotFound,
+            std::io::ErrorKind::PermissionDenied | std::io::ErrorKind::ResourceBusy => ErrorCode::Locked,
+            std::io::ErrorKind::AlreadyExists => ErrorCode::Conflict,
+            _ => ErrorCode::Io,
+        };
+        Self::new(code, message)
+    }
+
+    pub(crate) fn parse(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Parse, message)
+    }
+
+    pub(crate) fn validation(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Validation, message)
+    }
+
+    pub(crate) fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    pub(crate) fn window_unavailable(label: &str) -> Self {
+        Self::new(ErrorCode::WindowUnavailable, format!("window `{label}` is not available"))
+            .with_context("label", label)
+    }
+
+    pub(crate) fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+
+    pub(crate) fn with_context(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.context.insert(key.to_string(), value.into());
+        self
+    }
+
+    pub(crate) fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    pub(crate) fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut error = serializer.serialize_struct("AppError", 4)?;
+        error.serialize_field("code", &self.code)?;
+        error.serialize_field("message", &self.message)?;
+        error.serialize_field("summary", i18n::text(i18n::current_language(), self.code.summary()))?;
+        if self.context.is_empty() {
+            error.skip_field("context")?;
+        } else {
+            error.serialize_field("context", &self.context)?;
+        }
+        error.end()
+    }
+}
+
+impl From<tauri::Error> for AppError {
+    fn from(error: tauri::Error) -> Self {
+        match error {
+            tauri::Error::WindowNotFound | tauri::Error::WebviewNotFound => {
+                Self::new(ErrorCode::WindowUnavailable, error.to_string())
+            }
+            error => Self::internal(error.to_string()),
+        }
+    }
+}
+
+impl From<AppError> for serde_json::Value {
+    fn from(error: AppError) -> Self {
+        serde_json::to_value(error).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+pub(crate) type AppResult<T> = Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::{AppError, ErrorCode};
+    use crate::i18n::{self, Message};
+    use serde_json::json;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn app_error_serializes_code_message_and_context() {
+        let error = AppError::io("failed to write data.json").with_context("path", "/tmp/data.json");
+
+        assert_eq!(
+            serde_json::to_value(&error).expect("serialize error"),
+            json!({
+                "code": "io",
+                "message": "failed to write data.json",
+                "summary": i18n::text(i18n::current_language(), Message::ErrorIo),
+                "context": { "path": "/tmp/data.json" }
+            })
+        );
+    }
+
+    #[test]
+    fn app_error_omits_empty_context() {
+        let error = AppError::window_unavailable("main");
+        assert_eq!(error.code(), ErrorCode::WindowUnavailable);
+
+        let serialized = serde_json::to_value(AppError::validation("bad scale")).expect("serialize error");
+        assert_eq!(
+            serialized,
+            json!({
+                "code": "validation",
+                "message": "bad scale",
+                "summary": i18n::text(i18n::current_language(), Message::ErrorValidation),
+            })
+        );
+    }
+
+    #[test]
+    fn from_io_maps_error_kinds_to_codes() {
+        let cases = [
+            (ErrorKind::NotFound, ErrorCode::NotFound),
+            (ErrorKind::PermissionDenied, ErrorCode::Locked),
+            (ErrorKind::AlreadyExists, ErrorCode::Conflict),
+            (ErrorKind::UnexpectedEof, ErrorCode::Io),
+        ];
+        for (kind, code) in cases {
+            let error = AppError::from_io("failed to write data.json", &std::io::Error::from(kind));
+            assert_eq!(error.code(), code);
+        }
+    }
+}
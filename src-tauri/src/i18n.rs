@@ -0,0 +1,249 @@
+use std::{
+    fmt,
+    sync::{OnceLock, RwLock},
+};
+
+use crate::config::Language;
+
+/// Language configured in config.json; `None` until the config is loaded or while it follows the system.
+static CONFIGURED_LANGUAGE: RwLock<Option<Language>> = RwLock::new(None);
+static SYSTEM_LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+/// Every string the backend shows to the user. Placeholders are written `{name}` and filled by `format_text`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Message {
+    TrayNewSpark,
+    TrayOpenMainWindow,
+    TrayShowWidget,
+    TrayHideWidget,
+    TrayLockWidget,
+    TrayUnlockWidget,
+    TrayPeekWidgets,
+    TrayExit,
+    TrayToday,
+    TrayNothingToday,
+    TrayOpenGlobal,
+    TrayNoGlobals,
+    TrayRecentSparks,
+    TrayNoSparks,
+    TrayTooltipActiveToday,
+    TrayTooltipException,
+    TrayTooltipExceptions,
+    TrayTooltipOverdue,
+    WidgetRecovering,
+    WidgetRecovered,
+    WidgetGaveUp,
+    ErrorIo,
+    ErrorParse,
+    ErrorValidation,
+    ErrorConflict,
+    ErrorNotFound,
+    ErrorLocked,
+    ErrorWindowUnavailable,
+    ErrorInternal,
+}
+
+impl Message {
+    #[cfg(test)]
+    const ALL: [Self; 29] = [
+        Self::TrayNewSpark,
+        Self::TrayOpenMainWindow,
+        Self::TrayShowWidget,
+        Self::TrayHideWidget,
+        Self::TrayLockWidget,
+        Self::TrayUnlockWidget,
+        Self::TrayPeekWidgets,
+        Self::TrayExit,
+        Self::TrayToday,
+        Self::TrayNothingToday,
+        Self::TrayOpenGlobal,
+        Self::TrayNoGlobals,
+        Self::TrayRecentSparks,
+        Self::TrayNoSparks,
+        Self::TrayTooltipActiveToday,
+        Self::TrayTooltipException,
+        Self::TrayTooltipExceptions,
+        Self::TrayTooltipOverdue,
+        Self::WidgetRecovering,
+        Self::WidgetRecovered,
+        Self::WidgetGaveUp,
+        Self::ErrorIo,
+        Self::ErrorParse,
+        Self::ErrorValidation,
+        Self::ErrorConflict,
+        Self::ErrorNotFound,
+        Self::ErrorLocked,
+        Self::ErrorWindowUnavailable,
+        Self::ErrorInternal,
+    ];
+}
+
+fn english(message: Message) -> &'static str {
+    match message {
+        Message::TrayNewSpark => "New Spark…",
+        Message::TrayOpenMainWindow => "Open Main Window",
+        Message::TrayShowWidget => "Show Widget",
+        Message::TrayHideWidget => "Hide Widget",
+        Message::TrayLockWidget => "Lock Widget",
+        Message::TrayUnlockWidget => "Unlock Widget",
+        Message::TrayPeekWidgets => "Peek Widgets ({seconds}s)",
+        Message::TrayExit => "Exit",
+        Message::TrayToday => "Today ({count})",
+        Message::TrayNothingToday => "Nothing left for today",
+        Message::TrayOpenGlobal => "Open Global",
+        Message::TrayNoGlobals => "No active globals",
+        Message::TrayRecentSparks => "Recent Sparks",
+        Message::TrayNoSparks => "No sparks yet",
+        Message::TrayTooltipActiveToday => "{count} active today",
+        Message::TrayTooltipException => "{count} exception",
+        Message::TrayTooltipExceptions => "{count} exceptions",
+        Message::TrayTooltipOverdue => "{count} overdue",
+        Message::WidgetRecovering => "Widget \"{label}\" stopped responding; reopening it (attempt {attempt}).",
+        Message::WidgetRecovered => "Widget \"{label}\" is responding again.",
+        Message::WidgetGaveUp => "Widget \"{label}\" could not be recovered. Check the logs and reopen it manually.",
+        Message::ErrorIo => "Reading or writing the disk failed",
+        Message::ErrorParse => "The content could not be parsed",
+        Message::ErrorValidation => "The value is not valid",
+        Message::ErrorConflict => "This conflicts with existing data",
+        Message::ErrorNotFound => "The target does not exist",
+        Message::ErrorLocked => "The file is used by another program or cannot be written",
+        Message::ErrorWindowUnavailable => "The window is not available",
+        Message::ErrorInternal => "An unexpected internal error occurred",
+    }
+}
+
+fn simplified_chinese(message: Message) -> &'static str {
+    match message {
+        Message::TrayNewSpark => "新建灵感…",
+        Message::TrayOpenMainWindow => "打开标准窗口",
+        Message::TrayShowWidget => "显示 Widget",
+        Message::TrayHideWidget => "隐藏 Widget",
+        Message::TrayLockWidget => "锁定 Widget",
+        Message::TrayUnlockWidget => "解锁 Widget",
+        Message::TrayPeekWidgets => "临时解锁 Widget（{seconds} 秒）",
+        Message::TrayExit => "退出应用",
+        Message::TrayToday => "今日任务（{count}）",
+        Message::TrayNothingToday => "今天没有待办了",
+        Message::TrayOpenGlobal => "打开全局",
+        Message::TrayNoGlobals => "没有进行中的全局",
+        Message::TrayRecentSparks => "最近灵感",
+        Message::TrayNoSparks => "还没有灵感",
+        Message::TrayTooltipActiveToday => "今日进行中 {count} 项",
+        Message::TrayTooltipException | Message::TrayTooltipExceptions => "异常 {count} 项",
+        Message::TrayTooltipOverdue => "逾期 {count} 项",
+        Message::WidgetRecovering => "Widget「{label}」无响应，正在重新打开（第 {attempt} 次）。",
+        Message::WidgetRecovered => "Widget「{label}」已恢复响应。",
+        Message::WidgetGaveUp => "Widget「{label}」无法恢复，请查看日志后手动重新打开。",
+        Message::ErrorIo => "磁盘读写失败",
+        Message::ErrorParse => "内容无法解析",
+        Message::ErrorValidation => "输入的值无效",
+        Message::ErrorConflict => "与现有数据冲突",
+        Message::ErrorNotFound => "目标不存在",
+        Message::ErrorLocked => "文件被其他程序占用或没有写入权限",
+        Message::ErrorWindowUnavailable => "窗口不可用",
+        Message::ErrorInternal => "发生内部错误",
+    }
+}
+
+/// The catalog entry for `message`, placeholders included.
+pub(crate) fn text(language: Language, message: Message) -> &'static str {
+    match language {
+        Language::En => english(message),
+        Language::ZhCn => simplified_chinese(message),
+    }
+}
+
+/// The catalog entry for `message` with every `{name}` placeholder replaced by its value from `args`.
+pub(crate) fn format_text(language: Language, message: Message, args: &[(&str, &dyn fmt::Display)]) -> String {
+    args.iter().fold(text(language, message).to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{name}}}"), &value.to_string())
+    })
+}
+
+/// Maps a locale name such as `zh_CN.UTF-8`, `zh-Hans-CN` or `en-US` onto a catalog. Unknown locales get English.
+fn language_for_locale(locale: &str) -> Language {
+    if locale.to_ascii_lowercase().starts_with("zh") {
+        Language::ZhCn
+    } else {
+        Language::En
+    }
+}
+
+#[cfg(not(windows))]
+fn system_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+}
+
+#[cfg(windows)]
+fn system_locale() -> Option<String> {
+    use windows_sys::Win32::Globalization::GetUserDefaultLocaleName;
+
+    // LOCALE_NAME_MAX_LENGTH
+    let mut buffer = [0u16; 85];
+    let length = unsafe { GetUserDefaultLocaleName(buffer.as_mut_ptr(), buffer.len() as i32) };
+    // The length includes the terminating null; 0 means the call failed.
+    (length > 1).then(|| String::from_utf16_lossy(&buffer[..length as usize - 1]))
+}
+
+/// Language of the user's session, read once.
+pub(crate) fn system_language() -> Language {
+    *SYSTEM_LANGUAGE.get_or_init(|| system_locale().map_or(Language::En, |locale| language_for_locale(&locale)))
+}
+
+/// The configured language, or the system one when config.json leaves it unset.
+pub(crate) fn resolve_language(configured: Option<Language>) -> Language {
+    configured.unwrap_or_else(system_language)
+}
+
+/// Records the language from config.json; called when the config is loaded and after every change.
+pub(crate) fn set_configured_language(configured: Option<Language>) {
+    if let Ok(mut language) = CONFIGURED_LANGUAGE.write() {
+        *language = configured;
+    }
+}
+
+/// Language for strings produced without an app handle at hand, such as serialized errors.
+pub(crate) fn current_language() -> Language {
+    resolve_language(CONFIGURED_LANGUAGE.read().ok().and_then(|language| *language))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names = text
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn catalogs_cover_every_message_with_the_same_placeholders() {
+        for message in Message::ALL {
+            let (english, chinese) = (text(Language::En, message), text(Language::ZhCn, message));
+            assert!(!english.is_empty() && !chinese.is_empty(), "{message:?} needs text in both catalogs");
+            assert_eq!(placeholders(english), placeholders(chinese), "{message:?} placeholders differ");
+        }
+    }
+
+    #[test]
+    fn formats_placeholders_and_maps_locales() {
+        assert_eq!(format_text(Language::En, Message::TrayToday, &[("count", &3)]), "Today (3)");
+        assert_eq!(
+            format_text(Language::ZhCn, Message::WidgetRecovering, &[("label", &"widget-1"), ("attempt", &2)]),
+            "Widget「widget-1」无响应，正在重新打开（第 2 次）。"
+        );
+        assert_eq!(language_for_locale("zh_CN.UTF-8"), Language::ZhCn);
+        assert_eq!(language_for_locale("zh-Hans-CN"), Language::ZhCn);
+        assert_eq!(language_for_locale("en_US.UTF-8"), Language::En);
+        assert_eq!(language_for_locale("de-DE"), Language::En);
+        assert_eq!(resolve_language(Some(Language::ZhCn)), Language::ZhCn);
+    }
+}
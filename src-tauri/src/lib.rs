@@ -1,36 +1,50 @@
+mod app_data;
+mod config;
+mod crash;
+mod desktop_layer;
+mod diagnostics;
+mod error;
+mod i18n;
+mod logging;
+mod quick_capture;
+mod shortcuts;
+mod tray;
+mod widget_geometry;
+mod widget_health;
+mod widget_interaction;
 mod window;
 
 use std::{
     fs,
-    fs::OpenOptions,
-    io::Write,
     path::{Path, PathBuf},
     sync::atomic::{AtomicBool, Ordering},
-    time::{SystemTime, UNIX_EPOCH},
+    time::Duration,
 };
 
 use tauri::{
-    menu::{Menu, MenuItem},
-    tray::TrayIconBuilder,
+    webview::PageLoadEvent,
     Emitter, Manager, Runtime,
 };
-use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, ShortcutState};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+use config::{
+    is_widget_label, AppConfig, ConfigState, WidgetAlignMode, WidgetInstanceConfig, WidgetVerticalAnchor, WidgetView,
+    PRIMARY_WIDGET_LABEL,
+};
+use crash::{CrashReport, CrashReportSummary};
+use error::{AppError, AppResult};
+use logging::{log_project_event, LogQuery, LogQueryPage, LoggingState};
+use tray::TRAY_ID;
 
-const TRAY_ID: &str = "daily-tray";
-const TRAY_MENU_OPEN_MAIN: &str = "tray-open-main";
-const TRAY_MENU_TOGGLE_WIDGET_VISIBILITY: &str = "tray-toggle-widget-visibility";
-const TRAY_MENU_TOGGLE_WIDGET_LOCK: &str = "tray-toggle-widget-lock";
-const TRAY_MENU_EXIT: &str = "tray-exit";
 const EVENT_WIDGET_SET_LOCK: &str = "widget-set-lock-state";
 const EVENT_WIDGET_SET_VISIBILITY: &str = "widget-set-visibility-state";
+const EVENT_APP_CONFIG_UPDATED: &str = "app-config-updated";
 const PROJECT_ROOT_DIR_NAME: &str = ".daily";
 const PROJECT_DATA_DIR_NAME: &str = "data";
 const PROJECT_LOGS_DIR_NAME: &str = "logs";
 const PROJECT_BACKUP_DIR_NAME: &str = "backup";
 const DATA_FILE_NAME: &str = "data.json";
 const CONFIG_FILE_NAME: &str = "config.json";
-const LOG_FILE_NAME: &str = "events.jsonl";
-const CONFIG_SCHEMA_VERSION: u32 = 1;
 #[cfg(windows)]
 const WIN_UNREGISTER_CLASS_ERROR_TOKEN: &str =
     "Failed to unregister class Chrome_WidgetWin_0. Error = 1412";
@@ -39,12 +53,6 @@ static EXIT_SEQUENCE_REQUESTED: AtomicBool = AtomicBool::new(false);
 #[derive(Default)]
 struct ExitState(AtomicBool);
 
-#[derive(Default)]
-struct WidgetLockState(AtomicBool);
-
-#[derive(Default)]
-struct WidgetVisibilityState(AtomicBool);
-
 struct ProjectDirectories {
     root: PathBuf,
     data: PathBuf,
@@ -52,192 +60,239 @@ struct ProjectDirectories {
     backup: PathBuf,
 }
 
-#[allow(dead_code)]
-fn tray_toggle_lock_label(locked: bool) -> &'static str {
-    if locked {
-        "解锁 Widget"
-    } else {
-        "锁定 Widget"
-    }
-}
-
-#[allow(dead_code)]
-fn build_tray_menu<R: Runtime>(
-    app: &tauri::AppHandle<R>,
-    widget_locked: bool,
-) -> tauri::Result<Menu<R>> {
-    let tray_open_item = MenuItem::with_id(
-        app,
-        TRAY_MENU_OPEN_MAIN,
-        "打开标准窗口",
-        true,
-        None::<&str>,
-    )?;
-    let tray_toggle_lock_item = MenuItem::with_id(
-        app,
-        TRAY_MENU_TOGGLE_WIDGET_LOCK,
-        tray_toggle_lock_label(widget_locked),
-        true,
-        None::<&str>,
-    )?;
-    let tray_exit_item = MenuItem::with_id(app, TRAY_MENU_EXIT, "退出应用", true, None::<&str>)?;
-
-    Menu::with_items(
-        app,
-        &[&tray_open_item, &tray_toggle_lock_item, &tray_exit_item],
-    )
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WidgetInstanceSummary {
+    label: String,
+    open: bool,
+    #[serde(flatten)]
+    config: WidgetInstanceConfig,
 }
 
-#[allow(dead_code)]
-fn refresh_tray_menu<R: Runtime>(app: &tauri::AppHandle<R>, widget_locked: bool) {
-    let Some(tray) = app.tray_by_id(TRAY_ID) else {
-        return;
-    };
-
-    if let Ok(tray_menu) = build_tray_menu(app, widget_locked) {
-        let _ = tray.set_menu(Some(tray_menu));
+pub(crate) fn read_app_config_file(path: &Path) -> AppResult<Option<serde_json::Value>> {
+    if !path.exists() {
+        return Ok(None);
     }
-}
 
-#[allow(dead_code)]
-fn toggle_widget_lock<R: Runtime>(app: &tauri::AppHandle<R>) {
-    let Some(widget_lock_state) = app.try_state::<WidgetLockState>() else {
-        return;
-    };
+    let raw = fs::read_to_string(path)
+        .map_err(|error| AppError::from_io(format!("failed to read app config file: {error}"), &error))?;
+    let config = serde_json::from_str::<serde_json::Value>(&raw)
+        .map_err(|error| AppError::parse(format!("failed to parse app config file: {error}")))?;
 
-    let next_locked = !widget_lock_state.0.load(Ordering::SeqCst);
-    widget_lock_state.0.store(next_locked, Ordering::SeqCst);
-    refresh_tray_menu(app, next_locked);
-    let _ = app.emit(EVENT_WIDGET_SET_LOCK, next_locked);
-    log_project_event(
-        app,
-        "info",
-        "widget.lock.toggled",
-        "Widget lock state changed.",
-        Some(serde_json::json!({ "locked": next_locked })),
-    );
+    Ok(Some(config))
 }
 
-fn tray_toggle_lock_label_v2(locked: bool) -> &'static str {
-    if locked {
-        "Unlock Widget"
-    } else {
-        "Lock Widget"
+pub(crate) fn write_app_config_file(path: &Path, config: &serde_json::Value) -> AppResult<()> {
+    if let Some(parent_dir) = path.parent() {
+        ensure_directory_exists(parent_dir)?;
     }
-}
 
-fn tray_toggle_visibility_label_v2(visible: bool) -> &'static str {
-    if visible {
-        "Hide Widget"
-    } else {
-        "Show Widget"
+    let temp_path = path.with_extension("json.tmp");
+    let serialized = serde_json::to_string_pretty(config)
+        .map_err(|error| AppError::internal(format!("failed to serialize app config: {error}")))?;
+
+    fs::write(&temp_path, serialized)
+        .map_err(|error| AppError::from_io(format!("failed to write app config temp file: {error}"), &error))?;
+
+    if path.exists() {
+        fs::remove_file(path)
+            .map_err(|error| AppError::from_io(format!("failed to replace app config file: {error}"), &error))?;
     }
-}
 
-fn build_tray_menu_v2<R: Runtime>(app: &tauri::AppHandle<R>, widget_locked: bool, widget_visible: bool) -> tauri::Result<Menu<R>> {
-    let tray_open_item = MenuItem::with_id(app, TRAY_MENU_OPEN_MAIN, "Open Main Window", true, None::<&str>)?;
-    let tray_toggle_visibility_item = MenuItem::with_id(
-        app,
-        TRAY_MENU_TOGGLE_WIDGET_VISIBILITY,
-        tray_toggle_visibility_label_v2(widget_visible),
-        true,
-        None::<&str>,
-    )?;
-    let tray_toggle_lock_item = MenuItem::with_id(
-        app,
-        TRAY_MENU_TOGGLE_WIDGET_LOCK,
-        tray_toggle_lock_label_v2(widget_locked),
-        true,
-        None::<&str>,
-    )?;
-    let tray_exit_item = MenuItem::with_id(app, TRAY_MENU_EXIT, "Exit", true, None::<&str>)?;
+    fs::rename(&temp_path, path)
+        .map_err(|error| AppError::from_io(format!("failed to finalize app config file: {error}"), &error))?;
 
-    Menu::with_items(app, &[&tray_open_item, &tray_toggle_visibility_item, &tray_toggle_lock_item, &tray_exit_item])
+    Ok(())
 }
 
-fn refresh_tray_menu_v2<R: Runtime>(app: &tauri::AppHandle<R>) {
-    let Some(tray) = app.tray_by_id(TRAY_ID) else {
-        return;
+/// Persists a config change, then pushes the result to the logger and every window.
+pub(crate) fn update_app_config_with<R: Runtime>(
+    app: &tauri::AppHandle<R>,
+    mutate: impl FnOnce(&AppConfig) -> AppResult<AppConfig>,
+) -> AppResult<AppConfig> {
+    let Some(config_state) = app.try_state::<ConfigState>() else {
+        return Err(AppError::internal("Config state is not initialized."));
     };
 
-    let widget_locked = app
-        .try_state::<WidgetLockState>()
-        .map(|state| state.0.load(Ordering::SeqCst))
-        .unwrap_or(false);
-    let widget_visible = app
-        .try_state::<WidgetVisibilityState>()
-        .map(|state| state.0.load(Ordering::SeqCst))
-        .unwrap_or(false);
-
-    if let Ok(tray_menu) = build_tray_menu_v2(app, widget_locked, widget_visible) {
-        let _ = tray.set_menu(Some(tray_menu));
+    match config_state.update(mutate) {
+        Ok(config) => {
+            if let Some(logging_state) = app.try_state::<LoggingState>() {
+                logging_state.apply_config(Some(&config.to_value()));
+            }
+            i18n::set_configured_language(config.language);
+            let _ = app.emit(EVENT_APP_CONFIG_UPDATED, &config);
+            log_project_event(
+                app,
+                "info",
+                "config.save.success",
+                "Saved config.json successfully.",
+                Some(serde_json::json!({
+                    "path": config_state.path().display().to_string()
+                })),
+            );
+            Ok(config)
+        }
+        Err(error) => {
+            let final_error = AppError::new(
+                error.code(),
+                format!("failed to update config.json at `{}`: {error}", config_state.path().display()),
+            )
+            .with_context("path", config_state.path().display().to_string());
+            log_project_event(
+                app,
+                "error",
+                "config.save.failure",
+                "Failed to save config.json.",
+                Some(serde_json::json!({
+                    "path": config_state.path().display().to_string(),
+                    "error": final_error.message()
+                })),
+            );
+            Err(final_error)
+        }
     }
 }
 
-fn resolve_app_config_file_path<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
-    let directories = ensure_project_directories(app)?;
-    Ok(directories.data.join(CONFIG_FILE_NAME))
+fn current_config<R: Runtime>(app: &tauri::AppHandle<R>) -> AppConfig {
+    app.try_state::<ConfigState>()
+        .map(|state| state.snapshot())
+        .unwrap_or_default()
 }
 
-fn read_app_config_file(path: &Path) -> Result<Option<serde_json::Value>, String> {
-    if !path.exists() {
-        return Ok(None);
-    }
-
-    let raw = fs::read_to_string(path).map_err(|error| format!("failed to read app config file: {error}"))?;
-    let config = serde_json::from_str::<serde_json::Value>(&raw)
-        .map_err(|error| format!("failed to parse app config file: {error}"))?;
+fn unknown_widget_error(label: &str) -> AppError {
+    AppError::not_found(format!("widget `{label}` does not exist")).with_context("widget", label)
+}
 
-    Ok(Some(config))
+/// Applies `mutate` to the saved state of the widget `label` and persists the result.
+pub(crate) fn update_widget_config<R: Runtime>(
+    app: &tauri::AppHandle<R>,
+    label: &str,
+    mutate: impl FnOnce(&mut WidgetInstanceConfig),
+) -> AppResult<AppConfig> {
+    update_app_config_with(app, |config| {
+        let mut widget = config.widget(label).ok_or_else(|| unknown_widget_error(label))?;
+        mutate(&mut widget);
+        Ok(config.with_widget(label, widget))
+    })
 }
 
-fn write_app_config_file(path: &Path, config: &serde_json::Value) -> Result<(), String> {
-    if let Some(parent_dir) = path.parent() {
-        ensure_directory_exists(parent_dir)?;
+/// Resolves the widgets a command addresses: the one named by `label`, or every configured widget.
+fn resolve_widget_targets<R: Runtime>(app: &tauri::AppHandle<R>, label: Option<&str>) -> AppResult<Vec<String>> {
+    let config = current_config(app);
+    match label {
+        None => Ok(config.widget_labels()),
+        Some(label) if config.widget(label).is_some() => Ok(vec![label.to_string()]),
+        Some(label) => Err(unknown_widget_error(label)),
     }
+}
 
-    let temp_path = path.with_extension("json.tmp");
-    let serialized =
-        serde_json::to_string_pretty(config).map_err(|error| format!("failed to serialize app config: {error}"))?;
+fn all_widgets_locked(config: &AppConfig) -> bool {
+    config
+        .widget_labels()
+        .iter()
+        .all(|label| config.widget(label).is_some_and(|widget| widget.locked))
+}
 
-    fs::write(&temp_path, serialized).map_err(|error| format!("failed to write app config temp file: {error}"))?;
+fn any_widget_visible(config: &AppConfig) -> bool {
+    config
+        .widget_labels()
+        .iter()
+        .any(|label| config.widget(label).is_some_and(|widget| widget.visible))
+}
 
-    if path.exists() {
-        fs::remove_file(path).map_err(|error| format!("failed to replace app config file: {error}"))?;
+fn apply_widget_interaction_to_window<R: Runtime>(app: &tauri::AppHandle<R>, label: &str) {
+    if let Err(error) = widget_interaction::apply_widget_interaction(app, label) {
+        log_project_event(
+            app,
+            "warn",
+            "widget.lock.apply_failure",
+            "Failed to apply widget lock state to the window.",
+            Some(serde_json::json!({ "widget": label, "error": error })),
+        );
     }
+}
 
-    fs::rename(&temp_path, path).map_err(|error| format!("failed to finalize app config file: {error}"))?;
-
+/// An explicit lock change always wins over a running peek.
+fn set_widget_locked<R: Runtime>(app: &tauri::AppHandle<R>, label: &str, locked: bool) -> AppResult<()> {
+    update_widget_config(app, label, |widget| widget.locked = locked)?;
+    widget_interaction::end_widget_peek(app, label);
+    apply_widget_interaction_to_window(app, label);
     Ok(())
 }
 
-fn persist_widget_visibility_v2<R: Runtime>(app: &tauri::AppHandle<R>, visible: bool) -> Result<(), String> {
-    let config_file_path = resolve_app_config_file_path(app)?;
-    let mut next_config = read_app_config_file(&config_file_path)?.unwrap_or_else(|| serde_json::json!({}));
-
-    if !next_config.is_object() {
-        next_config = serde_json::json!({});
+fn peek_widgets<R: Runtime>(
+    app: &tauri::AppHandle<R>,
+    labels: &[String],
+    duration: Duration,
+) -> AppResult<Vec<String>> {
+    let mut peeked = Vec::new();
+    for label in labels {
+        if widget_interaction::start_widget_peek(app, label, duration)? {
+            peeked.push(label.clone());
+        }
     }
+    log_project_event(
+        app,
+        "info",
+        "widget.peek.started",
+        "Locked widgets made interactive for a limited time.",
+        Some(serde_json::json!({ "widgets": peeked, "durationMs": duration.as_millis() as u64 })),
+    );
+    Ok(peeked)
+}
 
-    if let Some(config_object) = next_config.as_object_mut() {
-        config_object.insert("schemaVersion".to_string(), serde_json::json!(CONFIG_SCHEMA_VERSION));
-        config_object.insert("widgetVisible".to_string(), serde_json::json!(visible));
+/// Persists the visibility of `label` and shows, creates or hides its window from here, so a hung widget page
+/// cannot block the change. `widget-set-visibility-state` only tells the main window what happened.
+fn set_widget_visible(app: &tauri::AppHandle, label: &str, visible: bool) -> AppResult<()> {
+    let config = update_widget_config(app, label, |widget| widget.visible = visible)?;
+    if visible {
+        let widget = config.widget(label).ok_or_else(|| unknown_widget_error(label))?;
+        window::ensure_widget_window(app, label, &widget)?;
+    } else {
+        window::hide_widget_window(app, label)?;
     }
 
-    write_app_config_file(&config_file_path, &next_config)
+    if label == PRIMARY_WIDGET_LABEL {
+        let _ = app.emit(EVENT_WIDGET_SET_VISIBILITY, visible);
+    }
+    Ok(())
 }
 
-fn toggle_widget_visibility_v2<R: Runtime>(app: &tauri::AppHandle<R>) {
-    let Some(widget_visibility_state) = app.try_state::<WidgetVisibilityState>() else {
-        return;
-    };
+/// Reopens every widget that was visible at exit.
+fn restore_visible_widgets(app: &tauri::AppHandle) {
+    let config = current_config(app);
+    for label in config.widget_labels() {
+        let Some(widget) = config.widget(&label).filter(|widget| widget.visible) else {
+            continue;
+        };
+        if let Err(error) = window::ensure_widget_window(app, &label, &widget) {
+            log_project_event(
+                app,
+                "warn",
+                "widget.restore_failure",
+                "Failed to reopen a widget that was visible at exit.",
+                Some(serde_json::json!({ "widget": label, "error": error })),
+            );
+        }
+    }
+}
 
-    let next_visible = !widget_visibility_state.0.load(Ordering::SeqCst);
-    widget_visibility_state.0.store(next_visible, Ordering::SeqCst);
+fn toggle_widget_visibility(app: &tauri::AppHandle) {
+    let config = current_config(app);
+    let next_visible = !any_widget_visible(&config);
+    for label in config.widget_labels() {
+        if let Err(error) = set_widget_visible(app, &label, next_visible) {
+            log_project_event(
+                app,
+                "warn",
+                "widget.visibility.apply_failure",
+                "Failed to apply widget visibility.",
+                Some(serde_json::json!({ "widget": label, "visible": next_visible, "error": error })),
+            );
+        }
+    }
 
-    let _ = persist_widget_visibility_v2(app, next_visible);
-    refresh_tray_menu_v2(app);
-    let _ = app.emit(EVENT_WIDGET_SET_VISIBILITY, next_visible);
+    tray::refresh_tray_menu(app);
     log_project_event(
         app,
         "info",
@@ -247,14 +302,14 @@ fn toggle_widget_visibility_v2<R: Runtime>(app: &tauri::AppHandle<R>) {
     );
 }
 
-fn toggle_widget_lock_v2<R: Runtime>(app: &tauri::AppHandle<R>) {
-    let Some(widget_lock_state) = app.try_state::<WidgetLockState>() else {
-        return;
-    };
+fn toggle_widget_lock<R: Runtime>(app: &tauri::AppHandle<R>) {
+    let config = current_config(app);
+    let next_locked = !all_widgets_locked(&config);
+    for label in config.widget_labels() {
+        let _ = set_widget_locked(app, &label, next_locked);
+    }
 
-    let next_locked = !widget_lock_state.0.load(Ordering::SeqCst);
-    widget_lock_state.0.store(next_locked, Ordering::SeqCst);
-    refresh_tray_menu_v2(app);
+    tray::refresh_tray_menu(app);
     let _ = app.emit(EVENT_WIDGET_SET_LOCK, next_locked);
     log_project_event(
         app,
@@ -265,12 +320,57 @@ fn toggle_widget_lock_v2<R: Runtime>(app: &tauri::AppHandle<R>) {
     );
 }
 
-fn perform_exit_sequence_on_main_thread<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<(), String> {
+fn run_shortcut_action(app: &tauri::AppHandle, action: shortcuts::ShortcutAction, shortcut: &str) {
+    use shortcuts::ShortcutAction;
+
+    let result = match action {
+        ShortcutAction::ToggleWidgetLock => {
+            toggle_widget_lock(app);
+            Ok(())
+        }
+        ShortcutAction::ToggleWidgetVisibility => {
+            toggle_widget_visibility(app);
+            Ok(())
+        }
+        ShortcutAction::OpenMainWindow => window::focus_main_window(app),
+        ShortcutAction::PeekWidgets => {
+            peek_widgets(app, &current_config(app).widget_labels(), widget_interaction::WIDGET_DEFAULT_PEEK).map(drop)
+        }
+        ShortcutAction::QuickAddTask => window::open_quick_capture_window(app, quick_capture::CaptureKind::Task),
+        ShortcutAction::QuickCaptureSpark => window::open_quick_capture_window(app, quick_capture::CaptureKind::Spark),
+    };
+    log_project_event(
+        app,
+        if result.is_ok() { "info" } else { "warn" },
+        "shortcut.triggered",
+        "Global shortcut triggered.",
+        Some(serde_json::json!({ "action": action, "shortcut": shortcut, "error": result.err() })),
+    );
+}
+
+/// Registers the shortcuts of `config` and logs every one that could not be bound.
+fn apply_global_shortcuts<R: Runtime>(app: &tauri::AppHandle<R>, config: &AppConfig) -> Vec<shortcuts::ShortcutStatus> {
+    let statuses = shortcuts::register_shortcuts(app, &config.shortcuts);
+    for status in &statuses {
+        if status.state != shortcuts::ShortcutBindingState::Registered {
+            log_project_event(
+                app,
+                "warn",
+                "shortcut.register.failure",
+                "A global shortcut could not be registered.",
+                Some(serde_json::json!(status)),
+            );
+        }
+    }
+    statuses
+}
+
+fn perform_exit_sequence_on_main_thread<R: Runtime>(app: &tauri::AppHandle<R>) -> AppResult<()> {
     let app_handle = app.clone();
-    let (sender, receiver) = std::sync::mpsc::channel::<Result<(), String>>();
+    let (sender, receiver) = std::sync::mpsc::channel::<AppResult<()>>();
 
     app.run_on_main_thread(move || {
-        let result = (|| -> Result<(), String> {
+        let result = (|| -> AppResult<()> {
             log_project_event(
                 &app_handle,
                 "info",
@@ -295,7 +395,7 @@ fn perform_exit_sequence_on_main_thread<R: Runtime>(app: &tauri::AppHandle<R>) -
                     "Global shortcut unregistration failed.",
                     Some(serde_json::json!({ "error": error.to_string() })),
                 );
-                return Err(message);
+                return Err(AppError::internal(message));
             }
             log_project_event(
                 &app_handle,
@@ -320,7 +420,7 @@ fn perform_exit_sequence_on_main_thread<R: Runtime>(app: &tauri::AppHandle<R>) -
                     "Tray resource removal failed.",
                     Some(serde_json::json!({ "tray_id": TRAY_ID })),
                 );
-                return Err(format!("tray resource `{TRAY_ID}` was not found"));
+                return Err(AppError::not_found(format!("tray resource `{TRAY_ID}` was not found")));
             }
             log_project_event(
                 &app_handle,
@@ -358,7 +458,7 @@ fn perform_exit_sequence_on_main_thread<R: Runtime>(app: &tauri::AppHandle<R>) -
                             "error": error.to_string()
                         })),
                     );
-                    return Err(message);
+                    return Err(AppError::internal(message).with_context("label", label));
                 }
                 log_project_event(
                     &app_handle,
@@ -396,11 +496,58 @@ fn perform_exit_sequence_on_main_thread<R: Runtime>(app: &tauri::AppHandle<R>) -
 
         let _ = sender.send(result);
     })
-    .map_err(|error| format!("failed to dispatch exit sequence to main thread: {error}"))?;
+    .map_err(|error| AppError::internal(format!("failed to dispatch exit sequence to main thread: {error}")))?;
 
     receiver
         .recv()
-        .map_err(|error| format!("failed to receive exit sequence result: {error}"))?
+        .map_err(|error| AppError::internal(format!("failed to receive exit sequence result: {error}")))?
+}
+
+/// Starts the exit sequence on a cleanup thread; repeated requests while it runs are ignored.
+fn request_app_exit(app: &tauri::AppHandle) {
+    let already_exiting = app
+        .try_state::<ExitState>()
+        .map(|state| state.0.swap(true, Ordering::SeqCst))
+        .unwrap_or(false);
+    if already_exiting {
+        log_project_event(
+            app,
+            "info",
+            "tray.exit.duplicate_request",
+            "Ignored duplicate tray exit request while exit is in progress.",
+            None,
+        );
+        return;
+    }
+
+    EXIT_SEQUENCE_REQUESTED.store(true, Ordering::SeqCst);
+    log_project_event(
+        app,
+        "info",
+        "tray.exit.requested",
+        "Tray exit requested; scheduling cleanup thread.",
+        None,
+    );
+
+    let app_handle = app.clone();
+    if let Err(error) = std::thread::Builder::new()
+        .name("tray-exit-cleanup".to_string())
+        .spawn(move || {
+            release_resources_and_exit(app_handle);
+        })
+    {
+        EXIT_SEQUENCE_REQUESTED.store(false, Ordering::SeqCst);
+        if let Some(exit_state) = app.try_state::<ExitState>() {
+            exit_state.0.store(false, Ordering::SeqCst);
+        }
+        log_project_event(
+            app,
+            "error",
+            "tray.exit.thread_spawn_failed",
+            "Failed to spawn cleanup thread for exit sequence.",
+            Some(serde_json::json!({ "error": error.to_string() })),
+        );
+    }
 }
 
 fn release_resources_and_exit<R: Runtime>(app: tauri::AppHandle<R>) {
@@ -448,19 +595,20 @@ fn is_ignorable_shutdown_error(_error: &tauri::Error) -> bool {
     false
 }
 
-fn ensure_directory_exists(path: &Path) -> Result<(), String> {
-    fs::create_dir_all(path).map_err(|error| format!("failed to create directory `{}`: {error}", path.display()))
+pub(crate) fn ensure_directory_exists(path: &Path) -> AppResult<()> {
+    fs::create_dir_all(path)
+        .map_err(|error| AppError::io(format!("failed to create directory `{}`: {error}", path.display())))
 }
 
-fn resolve_project_root_dir<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+fn resolve_project_root_dir<R: Runtime>(app: &tauri::AppHandle<R>) -> AppResult<PathBuf> {
     let home_dir = app
         .path()
         .home_dir()
-        .map_err(|error| format!("failed to resolve user home directory: {error}"))?;
+        .map_err(|error| AppError::io(format!("failed to resolve user home directory: {error}")))?;
     Ok(home_dir.join(PROJECT_ROOT_DIR_NAME))
 }
 
-fn resolve_project_directories<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<ProjectDirectories, String> {
+fn resolve_project_directories<R: Runtime>(app: &tauri::AppHandle<R>) -> AppResult<ProjectDirectories> {
     let root = resolve_project_root_dir(app)?;
     Ok(ProjectDirectories {
         data: root.join(PROJECT_DATA_DIR_NAME),
@@ -470,7 +618,7 @@ fn resolve_project_directories<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<
     })
 }
 
-fn ensure_project_directories<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<ProjectDirectories, String> {
+pub(crate) fn ensure_project_directories<R: Runtime>(app: &tauri::AppHandle<R>) -> AppResult<ProjectDirectories> {
     let directories = resolve_project_directories(app)?;
     ensure_directory_exists(&directories.root)?;
     ensure_directory_exists(&directories.data)?;
@@ -479,141 +627,282 @@ fn ensure_project_directories<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<P
     Ok(directories)
 }
 
-fn resolve_app_data_file_path<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+fn resolve_app_data_file_path<R: Runtime>(app: &tauri::AppHandle<R>) -> AppResult<PathBuf> {
     let directories = ensure_project_directories(app)?;
     Ok(directories.data.join(DATA_FILE_NAME))
 }
 
-fn resolve_project_log_file_path<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
-    let directories = ensure_project_directories(app)?;
-    Ok(directories.logs.join(LOG_FILE_NAME))
-}
-
-fn now_unix_millis() -> u128 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|duration| duration.as_millis())
-        .unwrap_or(0)
-}
-
-fn create_log_entry(
-    level: &str,
-    event: &str,
-    message: &str,
-    data: Option<serde_json::Value>,
-) -> serde_json::Value {
-    serde_json::json!({
-        "timestamp": now_unix_millis(),
-        "level": level,
-        "event": event,
-        "message": message,
-        "data": data
-    })
+fn read_app_data_file(path: &Path) -> AppResult<Option<serde_json::Value>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let raw = fs::read_to_string(path)
+        .map_err(|error| AppError::from_io(format!("failed to read app data file: {error}"), &error))?;
+    let data = serde_json::from_str::<serde_json::Value>(&raw)
+        .map_err(|error| AppError::parse(format!("failed to parse app data file: {error}")))?;
+
+    Ok(Some(data))
 }
 
-fn append_jsonl(path: &Path, line: &serde_json::Value) -> Result<(), String> {
+fn write_app_data_file(path: &Path, data: &serde_json::Value) -> AppResult<()> {
     if let Some(parent_dir) = path.parent() {
         ensure_directory_exists(parent_dir)?;
     }
 
-    let mut file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(path)
-        .map_err(|error| format!("failed to open log file `{}`: {error}", path.display()))?;
+    let temp_path = path.with_extension("json.tmp");
+    let serialized = serde_json::to_string_pretty(data)
+        .map_err(|error| AppError::internal(format!("failed to serialize app data: {error}")))?;
+
+    fs::write(&temp_path, serialized)
+        .map_err(|error| AppError::from_io(format!("failed to write app data temp file: {error}"), &error))?;
+
+    if path.exists() {
+        fs::remove_file(path)
+            .map_err(|error| AppError::from_io(format!("failed to replace app data file: {error}"), &error))?;
+    }
 
-    let serialized = serde_json::to_string(line).map_err(|error| format!("failed to serialize log line: {error}"))?;
-    writeln!(file, "{serialized}")
-        .map_err(|error| format!("failed to append log line `{}`: {error}", path.display()))?;
+    fs::rename(&temp_path, path)
+        .map_err(|error| AppError::from_io(format!("failed to finalize app data file: {error}"), &error))?;
 
     Ok(())
 }
 
-fn log_project_event<R: Runtime>(
-    app: &tauri::AppHandle<R>,
-    level: &str,
-    event: &str,
-    message: &str,
-    data: Option<serde_json::Value>,
-) {
-    let Ok(log_file_path) = resolve_project_log_file_path(app) else {
-        return;
-    };
-    let _ = append_jsonl(&log_file_path, &create_log_entry(level, event, message, data));
-}
+/// Opens the widget `label` (the primary widget by default), creating its config entry on first use.
+/// `view`, when given, replaces the tasks the widget lists.
+#[tauri::command]
+fn ensure_widget_window(
+    app: tauri::AppHandle,
+    label: Option<String>,
+    view: Option<WidgetView>,
+) -> AppResult<window::WidgetWindowOutcome> {
+    let label = label.unwrap_or_else(|| PRIMARY_WIDGET_LABEL.to_string());
+    if !is_widget_label(&label) {
+        return Err(AppError::validation(format!("`{label}` is not a valid widget label"))
+            .with_context("widget", label.as_str()));
+    }
 
-fn read_app_data_file(path: &Path) -> Result<Option<serde_json::Value>, String> {
-    if !path.exists() {
-        return Ok(None);
+    let config = update_app_config_with(&app, |config| {
+        let mut widget = config
+            .widget(&label)
+            .unwrap_or_else(|| WidgetInstanceConfig::for_label(&label));
+        if let Some(view) = view {
+            widget.view = view;
+        }
+        widget.visible = true;
+        Ok(config.with_widget(&label, widget))
+    })?;
+    let widget = config.widget(&label).ok_or_else(|| unknown_widget_error(&label))?;
+    let outcome = window::ensure_widget_window(&app, &label, &widget)?;
+    if label == PRIMARY_WIDGET_LABEL {
+        let _ = app.emit(EVENT_WIDGET_SET_VISIBILITY, true);
     }
+    tray::refresh_tray_menu(&app);
+    Ok(outcome)
+}
 
-    let raw = fs::read_to_string(path).map_err(|error| format!("failed to read app data file: {error}"))?;
-    let data = serde_json::from_str::<serde_json::Value>(&raw)
-        .map_err(|error| format!("failed to parse app data file: {error}"))?;
+/// Closes the widget `label`; with `forget` an additional widget is also removed from config.json.
+#[tauri::command]
+fn close_widget_window(app: tauri::AppHandle, label: String, forget: Option<bool>) -> AppResult<()> {
+    let forget = forget.unwrap_or(false);
+    if forget && label == PRIMARY_WIDGET_LABEL {
+        return Err(AppError::validation("the primary widget cannot be removed").with_context("widget", label));
+    }
+    resolve_widget_targets(&app, Some(label.as_str()))?;
+
+    window::close_widget_window(&app, &label)?;
+    if forget {
+        update_app_config_with(&app, |config| {
+            let mut next = config.clone();
+            next.widget_instances.remove(&label);
+            Ok(next)
+        })?;
+    } else {
+        update_widget_config(&app, &label, |widget| widget.visible = false)?;
+    }
+    tray::refresh_tray_menu(&app);
+    log_project_event(
+        &app,
+        "info",
+        "widget.instance.closed",
+        "Widget window closed.",
+        Some(serde_json::json!({ "widget": label, "forget": forget })),
+    );
+    Ok(())
+}
 
-    Ok(Some(data))
+/// Handshake sent by a widget page once it has rendered; widgets that never send it are recreated.
+#[tauri::command]
+fn widget_ready(webview_window: tauri::WebviewWindow) -> AppResult<()> {
+    let label = webview_window.label();
+    if !is_widget_label(label) {
+        return Err(AppError::validation(format!("`{label}` is not a widget window")).with_context("widget", label));
+    }
+    widget_health::mark_widget_ready(webview_window.app_handle(), label);
+    Ok(())
 }
 
-fn write_app_data_file(path: &Path, data: &serde_json::Value) -> Result<(), String> {
-    if let Some(parent_dir) = path.parent() {
-        ensure_directory_exists(parent_dir)?;
+/// Starts a native drag of the calling widget; the widget snaps to nearby work-area edges once the move settles.
+#[tauri::command]
+fn start_widget_drag(webview_window: tauri::WebviewWindow) -> AppResult<()> {
+    let label = webview_window.label();
+    if !is_widget_label(label) {
+        return Err(AppError::validation(format!("`{label}` is not a widget window")).with_context("widget", label));
     }
+    widget_geometry::arm_widget_drag(webview_window.app_handle(), label);
+    webview_window.start_dragging()?;
+    Ok(())
+}
 
-    let temp_path = path.with_extension("json.tmp");
-    let serialized =
-        serde_json::to_string_pretty(data).map_err(|error| format!("failed to serialize app data: {error}"))?;
+#[tauri::command]
+fn list_widget_instances(app: tauri::AppHandle) -> Vec<WidgetInstanceSummary> {
+    let config = current_config(&app);
+    config
+        .widget_labels()
+        .into_iter()
+        .filter_map(|label| {
+            let widget = config.widget(&label)?;
+            Some(WidgetInstanceSummary {
+                open: app.get_webview_window(&label).is_some(),
+                label,
+                config: widget,
+            })
+        })
+        .collect()
+}
 
-    fs::write(&temp_path, serialized).map_err(|error| format!("failed to write app data temp file: {error}"))?;
+#[tauri::command]
+fn focus_main_window(app: tauri::AppHandle) -> AppResult<()> {
+    window::focus_main_window(&app)
+}
 
-    if path.exists() {
-        fs::remove_file(path).map_err(|error| format!("failed to replace app data file: {error}"))?;
-    }
+#[tauri::command]
+fn resize_widget_window_atomic(
+    app: tauri::AppHandle,
+    label: Option<String>,
+    scale_percent: u16,
+    align_mode: Option<WidgetAlignMode>,
+    vertical_anchor: Option<WidgetVerticalAnchor>,
+) -> AppResult<()> {
+    let label = label.unwrap_or_else(|| PRIMARY_WIDGET_LABEL.to_string());
+    let widget = current_config(&app).widget(&label).unwrap_or_default();
+    // A docked edge wins over the requested anchor so the widget stays flush against it.
+    let dock = widget.dock.unwrap_or_default();
+    let align_mode = dock.horizontal.or(align_mode).unwrap_or(widget.align_mode);
+    let vertical_anchor = dock.vertical.or(vertical_anchor).unwrap_or_default();
+    window::resize_widget_window_atomic(&app, &label, scale_percent, align_mode, vertical_anchor)
+}
 
-    fs::rename(&temp_path, path).map_err(|error| format!("failed to finalize app data file: {error}"))?;
+#[tauri::command]
+fn list_monitors(app: tauri::AppHandle) -> Vec<widget_geometry::MonitorArea> {
+    window::list_monitors(&app)
+}
 
+#[tauri::command]
+fn move_widget_to_monitor(app: tauri::AppHandle, label: Option<String>, monitor_name: String) -> AppResult<()> {
+    let label = label.unwrap_or_else(|| PRIMARY_WIDGET_LABEL.to_string());
+    window::move_widget_to_monitor(&app, &label, &monitor_name)?;
+    log_project_event(
+        &app,
+        "info",
+        "widget.monitor.moved",
+        "Widget moved to another monitor.",
+        Some(serde_json::json!({ "widget": label, "monitor": monitor_name })),
+    );
     Ok(())
 }
 
+/// Sets the lock of the widget `label`, or of every widget when no label is given.
 #[tauri::command]
-fn ensure_widget_window(app: tauri::AppHandle) -> Result<(), String> {
-    window::ensure_widget_window(&app)
+fn sync_widget_locked_state(app: tauri::AppHandle, label: Option<String>, locked: bool) -> AppResult<()> {
+    for label in resolve_widget_targets(&app, label.as_deref())? {
+        set_widget_locked(&app, &label, locked)?;
+    }
+    tray::refresh_tray_menu(&app);
+    Ok(())
 }
 
+/// Sets the content opacity of the widget `label`, or of every widget when no label is given.
 #[tauri::command]
-fn focus_main_window(app: tauri::AppHandle) -> Result<(), String> {
-    window::focus_main_window(&app)
+fn set_widget_opacity(
+    app: tauri::AppHandle,
+    label: Option<String>,
+    opacity_percent: u8,
+    hover_opacity_percent: Option<u8>,
+) -> AppResult<()> {
+    for label in resolve_widget_targets(&app, label.as_deref())? {
+        update_widget_config(&app, &label, |widget| {
+            widget.opacity_percent = opacity_percent;
+            if let Some(hover_opacity_percent) = hover_opacity_percent {
+                widget.hover_opacity_percent = hover_opacity_percent;
+            }
+        })?;
+    }
+    Ok(())
 }
 
+/// Chooses whether the locked widget `label` (or every widget) lets clicks through to the windows below.
 #[tauri::command]
-fn resize_widget_window_atomic(app: tauri::AppHandle, scale_percent: u16) -> Result<(), String> {
-    window::resize_widget_window_atomic(&app, scale_percent)
+fn set_widget_click_through(app: tauri::AppHandle, label: Option<String>, enabled: bool) -> AppResult<()> {
+    for label in resolve_widget_targets(&app, label.as_deref())? {
+        update_widget_config(&app, &label, |widget| widget.click_through = enabled)?;
+        apply_widget_interaction_to_window(&app, &label);
+    }
+    Ok(())
 }
 
+/// Moves the widget `label` (or every widget) between the desktop layer and the always-on-top layer. Open windows
+/// are rebuilt, because the window type cannot change once a window is mapped.
 #[tauri::command]
-fn sync_widget_locked_state(app: tauri::AppHandle, locked: bool) -> Result<(), String> {
-    let Some(widget_lock_state) = app.try_state::<WidgetLockState>() else {
-        return Err("Widget lock state is not initialized.".to_string());
-    };
-
-    widget_lock_state.0.store(locked, Ordering::SeqCst);
-    refresh_tray_menu_v2(&app);
+fn set_widget_desktop_layer(app: tauri::AppHandle, label: Option<String>, enabled: bool) -> AppResult<()> {
+    for label in resolve_widget_targets(&app, label.as_deref())? {
+        let config = update_widget_config(&app, &label, |widget| widget.desktop_layer = enabled)?;
+        let reopened = app.get_webview_window(&label).is_some();
+        if reopened {
+            let widget = config.widget(&label).ok_or_else(|| unknown_widget_error(&label))?;
+            window::close_widget_window(&app, &label)?;
+            window::ensure_widget_window(&app, &label, &widget)?;
+        }
+        log_project_event(
+            &app,
+            "info",
+            "widget.desktop_layer.changed",
+            "Widget desktop-layer mode changed.",
+            Some(serde_json::json!({ "widget": label, "enabled": enabled, "reopened": reopened })),
+        );
+    }
     Ok(())
 }
 
+/// Makes the locked widget `label` (or every locked widget) interactive for `seconds`, then relocks it.
+/// Returns the labels that were actually peeked.
 #[tauri::command]
-fn sync_widget_visibility_state(app: tauri::AppHandle, visible: bool) -> Result<(), String> {
-    let Some(widget_visibility_state) = app.try_state::<WidgetVisibilityState>() else {
-        return Err("Widget visibility state is not initialized.".to_string());
-    };
+fn peek_widget(app: tauri::AppHandle, label: Option<String>, seconds: Option<u64>) -> AppResult<Vec<String>> {
+    let duration = seconds.map_or(widget_interaction::WIDGET_DEFAULT_PEEK, Duration::from_secs);
+    if duration.is_zero() || duration > widget_interaction::WIDGET_MAX_PEEK {
+        return Err(AppError::validation(format!(
+            "peek duration must be between 1 and {} seconds",
+            widget_interaction::WIDGET_MAX_PEEK.as_secs()
+        ))
+        .with_context("value", duration.as_secs()));
+    }
 
-    widget_visibility_state.0.store(visible, Ordering::SeqCst);
-    persist_widget_visibility_v2(&app, visible)?;
-    refresh_tray_menu_v2(&app);
+    let labels = resolve_widget_targets(&app, label.as_deref())?;
+    peek_widgets(&app, &labels, duration)
+}
+
+/// Sets the visibility of the widget `label`, or of every widget when no label is given.
+#[tauri::command]
+fn sync_widget_visibility_state(app: tauri::AppHandle, label: Option<String>, visible: bool) -> AppResult<()> {
+    for label in resolve_widget_targets(&app, label.as_deref())? {
+        set_widget_visible(&app, &label, visible)?;
+    }
+    tray::refresh_tray_menu(&app);
     Ok(())
 }
 
 #[tauri::command]
-fn load_app_data(app: tauri::AppHandle) -> Result<Option<serde_json::Value>, String> {
+fn load_app_data(app: tauri::AppHandle) -> AppResult<Option<serde_json::Value>> {
     let app_data_path = resolve_app_data_file_path(&app)?;
     match read_app_data_file(&app_data_path) {
         Ok(data) => {
@@ -627,6 +916,10 @@ fn load_app_data(app: tauri::AppHandle) -> Result<Option<serde_json::Value>, Str
                     "has_data": data.is_some()
                 })),
             );
+            if let Some(data) = &data {
+                app_data::remember_app_data(&app, data);
+                tray::refresh_tray_menu(&app);
+            }
             Ok(data)
         }
         Err(error) => {
@@ -646,7 +939,7 @@ fn load_app_data(app: tauri::AppHandle) -> Result<Option<serde_json::Value>, Str
 }
 
 #[tauri::command]
-fn save_app_data(app: tauri::AppHandle, data: serde_json::Value) -> Result<(), String> {
+fn save_app_data(app: tauri::AppHandle, data: serde_json::Value) -> AppResult<()> {
     let app_data_path = resolve_app_data_file_path(&app)?;
     match write_app_data_file(&app_data_path, &data) {
         Ok(()) => {
@@ -659,13 +952,16 @@ fn save_app_data(app: tauri::AppHandle, data: serde_json::Value) -> Result<(), S
                     "path": app_data_path.display().to_string()
                 })),
             );
+            app_data::remember_app_data(&app, &data);
+            tray::refresh_tray_menu(&app);
             Ok(())
         }
         Err(error) => {
-            let final_error = format!(
-                "failed to write data.json at `{}`: {error}",
-                app_data_path.display()
-            );
+            let final_error = AppError::new(
+                error.code(),
+                format!("failed to write data.json at `{}`: {error}", app_data_path.display()),
+            )
+            .with_context("path", app_data_path.display().to_string());
             log_project_event(
                 &app,
                 "error",
@@ -673,7 +969,7 @@ fn save_app_data(app: tauri::AppHandle, data: serde_json::Value) -> Result<(), S
                 "Failed to save data.json.",
                 Some(serde_json::json!({
                     "path": app_data_path.display().to_string(),
-                    "error": final_error
+                    "error": final_error.message()
                 })),
             );
             Err(final_error)
@@ -682,70 +978,204 @@ fn save_app_data(app: tauri::AppHandle, data: serde_json::Value) -> Result<(), S
 }
 
 #[tauri::command]
-fn load_app_config(app: tauri::AppHandle) -> Result<Option<serde_json::Value>, String> {
-    let app_config_path = resolve_app_config_file_path(&app)?;
-    match read_app_config_file(&app_config_path) {
-        Ok(config) => {
-            log_project_event(
-                &app,
-                "info",
-                "config.load.success",
-                "Loaded config.json successfully.",
-                Some(serde_json::json!({
-                    "path": app_config_path.display().to_string(),
-                    "has_data": config.is_some()
-                })),
-            );
-            Ok(config)
-        }
-        Err(error) => {
-            log_project_event(
-                &app,
-                "error",
-                "config.load.failure",
-                "Failed to load config.json.",
-                Some(serde_json::json!({
-                    "path": app_config_path.display().to_string(),
-                    "error": error
-                })),
-            );
-            Err(error)
-        }
+fn load_app_config(app: tauri::AppHandle) -> AppResult<AppConfig> {
+    let Some(config_state) = app.try_state::<ConfigState>() else {
+        return Err(AppError::internal("Config state is not initialized."));
+    };
+
+    let config = config_state.snapshot();
+    log_project_event(
+        &app,
+        "info",
+        "config.load.success",
+        "Loaded config.json successfully.",
+        Some(serde_json::json!({
+            "path": config_state.path().display().to_string()
+        })),
+    );
+    Ok(config)
+}
+
+#[tauri::command]
+fn update_app_config(app: tauri::AppHandle, patch: serde_json::Value) -> AppResult<AppConfig> {
+    let previous_shortcuts = current_config(&app).shortcuts;
+    let config = update_app_config_with(&app, |config| config.patched(&patch))?;
+    if config.shortcuts != previous_shortcuts {
+        apply_global_shortcuts(&app, &config);
     }
+    // A patch can switch the language or the widget lock and visibility the tray shows.
+    tray::refresh_tray_menu(&app);
+    Ok(config)
 }
 
+/// Status of every configured global shortcut as of the last registration.
 #[tauri::command]
-fn save_app_config(app: tauri::AppHandle, config: serde_json::Value) -> Result<(), String> {
-    let app_config_path = resolve_app_config_file_path(&app)?;
-    match write_app_config_file(&app_config_path, &config) {
-        Ok(()) => {
+fn list_global_shortcuts(app: tauri::AppHandle) -> Vec<shortcuts::ShortcutStatus> {
+    app.try_state::<shortcuts::ShortcutRegistry>()
+        .map(|registry| registry.statuses())
+        .unwrap_or_default()
+}
+
+/// Binds `action` to `accelerator` (or unbinds it when `None`) and re-registers every shortcut.
+#[tauri::command]
+fn set_global_shortcut(
+    app: tauri::AppHandle,
+    action: String,
+    accelerator: Option<String>,
+) -> AppResult<Vec<shortcuts::ShortcutStatus>> {
+    if shortcuts::ShortcutAction::parse(&action).is_none() {
+        return Err(AppError::validation(format!("`{action}` is not a shortcut action"))
+            .with_context("action", action.as_str()));
+    }
+    let config = update_app_config_with(&app, |config| {
+        let mut next = config.clone();
+        match accelerator.as_deref().map(str::trim).filter(|accelerator| !accelerator.is_empty()) {
+            Some(accelerator) => next.shortcuts.insert(action.clone(), accelerator.to_string()),
+            None => next.shortcuts.remove(&action),
+        };
+        Ok(next)
+    })?;
+    Ok(apply_global_shortcuts(&app, &config))
+}
+
+/// Re-reads the shortcut map from config.json and registers it again, e.g. after another app released a key.
+#[tauri::command]
+fn reload_global_shortcuts(app: tauri::AppHandle) -> Vec<shortcuts::ShortcutStatus> {
+    apply_global_shortcuts(&app, &current_config(&app))
+}
+
+#[tauri::command]
+fn open_quick_capture(app: tauri::AppHandle, kind: Option<quick_capture::CaptureKind>) -> AppResult<()> {
+    window::open_quick_capture_window(&app, kind.unwrap_or(quick_capture::CaptureKind::Spark))
+}
+
+/// Parses a capture line into a task or spark, prepends it to data.json and closes the capture window.
+/// Open windows merge the item from the `quick-capture-saved` event instead of reloading their data.
+#[tauri::command]
+fn submit_quick_capture(
+    app: tauri::AppHandle,
+    webview_window: tauri::WebviewWindow,
+    input: String,
+    kind: quick_capture::CaptureKind,
+) -> AppResult<quick_capture::CapturedItem> {
+    let today = chrono::Utc::now().date_naive();
+    let draft = quick_capture::parse_capture(&input, today)?;
+    let app_data_path = resolve_app_data_file_path(&app)?;
+    let (data, item) = quick_capture::apply_capture(read_app_data_file(&app_data_path)?, kind, &draft, today)?;
+    if let Err(error) = write_app_data_file(&app_data_path, &data) {
+        log_project_event(
+            &app,
+            "error",
+            "quick_capture.save.failure",
+            "Failed to save a quick capture to data.json.",
+            Some(serde_json::json!({ "kind": kind, "error": error })),
+        );
+        return Err(error);
+    }
+
+    app_data::remember_app_data(&app, &data);
+    tray::refresh_tray_menu(&app);
+    let captured = quick_capture::CapturedItem { kind, item };
+    log_project_event(
+        &app,
+        "info",
+        "quick_capture.saved",
+        "Saved a quick capture.",
+        Some(serde_json::json!({
+            "kind": kind,
+            "tags": draft.tags.len(),
+            "hasDate": draft.date.is_some(),
+            "hasGlobal": draft.global.is_some(),
+        })),
+    );
+    let _ = app.emit(quick_capture::EVENT_QUICK_CAPTURE_SAVED, &captured);
+    if webview_window.label() == quick_capture::QUICK_CAPTURE_LABEL {
+        webview_window.close()?;
+    }
+    Ok(captured)
+}
+
+#[tauri::command]
+fn query_logs(app: tauri::AppHandle, query: Option<LogQuery>) -> AppResult<LogQueryPage> {
+    let directories = ensure_project_directories(&app)?;
+    logging::query_logs(&directories.logs, &query.unwrap_or_default())
+}
+
+#[tauri::command]
+fn start_log_tail(app: tauri::AppHandle, window: tauri::WebviewWindow) -> AppResult<()> {
+    let Some(logging_state) = app.try_state::<LoggingState>() else {
+        return Err(AppError::internal("Logging state is not initialized."));
+    };
+
+    logging_state.subscribe_tail(window.label());
+    Ok(())
+}
+
+#[tauri::command]
+fn stop_log_tail(app: tauri::AppHandle, window: tauri::WebviewWindow) -> AppResult<()> {
+    let Some(logging_state) = app.try_state::<LoggingState>() else {
+        return Err(AppError::internal("Logging state is not initialized."));
+    };
+
+    logging_state.unsubscribe_tail(window.label());
+    Ok(())
+}
+
+#[tauri::command]
+fn list_crash_reports(app: tauri::AppHandle) -> AppResult<Vec<CrashReportSummary>> {
+    let directories = ensure_project_directories(&app)?;
+    crash::list_crash_reports(&directories.logs)
+}
+
+#[tauri::command]
+fn read_crash_report(app: tauri::AppHandle, file_name: String) -> AppResult<CrashReport> {
+    let directories = ensure_project_directories(&app)?;
+    crash::read_crash_report(&directories.logs, &file_name)
+}
+
+#[tauri::command]
+fn clear_crash_reports(app: tauri::AppHandle) -> AppResult<usize> {
+    let directories = ensure_project_directories(&app)?;
+    let removed = crash::clear_crash_reports(&directories.logs)?;
+    log_project_event(
+        &app,
+        "info",
+        "app.crash_report.cleared",
+        "Crash reports cleared.",
+        Some(serde_json::json!({ "count": removed })),
+    );
+    Ok(removed)
+}
+
+#[tauri::command]
+fn export_diagnostics(app: tauri::AppHandle, destination: Option<String>) -> AppResult<String> {
+    let directories = ensure_project_directories(&app)?;
+    let app_version = app.package_info().version.to_string();
+    if let Some(logging_state) = app.try_state::<LoggingState>() {
+        logging_state.flush();
+    }
+    match diagnostics::export_diagnostics(&directories, &app_version, destination.as_deref().map(Path::new)) {
+        Ok(archive_path) => {
             log_project_event(
                 &app,
                 "info",
-                "config.save.success",
-                "Saved config.json successfully.",
+                "diagnostics.export.success",
+                "Exported diagnostic bundle.",
                 Some(serde_json::json!({
-                    "path": app_config_path.display().to_string()
+                    "path": archive_path.display().to_string()
                 })),
             );
-            Ok(())
+            Ok(archive_path.display().to_string())
         }
         Err(error) => {
-            let final_error = format!(
-                "failed to write config.json at `{}`: {error}",
-                app_config_path.display()
-            );
             log_project_event(
                 &app,
                 "error",
-                "config.save.failure",
-                "Failed to save config.json.",
-                Some(serde_json::json!({
-                    "path": app_config_path.display().to_string(),
-                    "error": final_error
-                })),
+                "diagnostics.export.failure",
+                "Failed to export diagnostic bundle.",
+                Some(serde_json::json!({ "error": error })),
             );
-            Err(final_error)
+            Err(error)
         }
     }
 }
@@ -754,7 +1184,37 @@ fn save_app_config(app: tauri::AppHandle, config: serde_json::Value) -> Result<(
 pub fn run() {
     let result = tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .on_page_load(|webview, payload| {
+            if is_widget_label(webview.label()) {
+                widget_health::record_widget_page_load(webview.app_handle(), webview.label(), payload.event());
+            }
+            // A freshly built widget window starts without its saved lock, so it is applied once its page loads.
+            if is_widget_label(webview.label()) && payload.event() == PageLoadEvent::Started {
+                apply_widget_interaction_to_window(webview.app_handle(), webview.label());
+            }
+        })
         .on_window_event(|window, event| {
+            if let tauri::WindowEvent::Destroyed = event {
+                if let Some(logging_state) = window.app_handle().try_state::<LoggingState>() {
+                    logging_state.unsubscribe_tail(window.label());
+                }
+            }
+
+            if is_widget_label(window.label()) {
+                match event {
+                    tauri::WindowEvent::Moved(_) => {
+                        widget_geometry::schedule_widget_geometry_persist(window.app_handle(), window.label(), true);
+                    }
+                    tauri::WindowEvent::Resized(_) => {
+                        widget_geometry::schedule_widget_geometry_persist(window.app_handle(), window.label(), false);
+                    }
+                    tauri::WindowEvent::Destroyed => {
+                        widget_health::forget_widget_page_load(window.app_handle(), window.label());
+                    }
+                    _ => {}
+                }
+            }
+
             if window.label() != "main" {
                 return;
             }
@@ -773,95 +1233,43 @@ pub fn run() {
                 let _ = window.minimize();
             }
         })
-        .on_menu_event(|app, event| match event.id().as_ref() {
-            TRAY_MENU_OPEN_MAIN => {
-                if let Some(main_window) = app.get_webview_window("main") {
-                    let _ = main_window.show();
-                    let _ = main_window.unminimize();
-                    let _ = main_window.set_focus();
-                }
-                log_project_event(
-                    app,
-                    "info",
-                    "tray.open_main",
-                    "Tray menu requested opening main window.",
-                    None,
-                );
-            }
-            TRAY_MENU_TOGGLE_WIDGET_LOCK => {
-                toggle_widget_lock_v2(app);
-                log_project_event(
-                    app,
-                    "info",
-                    "tray.toggle_widget_lock",
-                    "Tray menu requested toggling widget lock.",
-                    None,
-                );
-            }
-            TRAY_MENU_TOGGLE_WIDGET_VISIBILITY => {
-                toggle_widget_visibility_v2(app);
-                log_project_event(
-                    app,
-                    "info",
-                    "tray.toggle_widget_visibility",
-                    "Tray menu requested toggling widget visibility.",
-                    None,
-                );
-            }
-            TRAY_MENU_EXIT => {
-                let already_exiting = app
-                    .try_state::<ExitState>()
-                    .map(|state| state.0.swap(true, Ordering::SeqCst))
-                    .unwrap_or(false);
-                if already_exiting {
-                    log_project_event(
-                        app,
-                        "info",
-                        "tray.exit.duplicate_request",
-                        "Ignored duplicate tray exit request while exit is in progress.",
-                        None,
-                    );
-                    return;
-                }
-
-                EXIT_SEQUENCE_REQUESTED.store(true, Ordering::SeqCst);
-                log_project_event(
-                    app,
-                    "info",
-                    "tray.exit.requested",
-                    "Tray exit requested; scheduling cleanup thread.",
-                    None,
-                );
-
-                let app_handle = app.clone();
-                if let Err(error) = std::thread::Builder::new()
-                    .name("tray-exit-cleanup".to_string())
-                    .spawn(move || {
-                        release_resources_and_exit(app_handle);
-                    })
-                {
-                    EXIT_SEQUENCE_REQUESTED.store(false, Ordering::SeqCst);
-                    if let Some(exit_state) = app.try_state::<ExitState>() {
-                        exit_state.0.store(false, Ordering::SeqCst);
-                    }
-                    log_project_event(
-                        app,
-                        "error",
-                        "tray.exit.thread_spawn_failed",
-                        "Failed to spawn cleanup thread for exit sequence.",
-                        Some(serde_json::json!({ "error": error.to_string() })),
-                    );
-                }
-            }
-            _ => {}
-        })
+        .on_menu_event(|app, event| tray::handle_tray_menu_event(app, event.id().as_ref()))
         .setup(|app| {
-            let directories = ensure_project_directories(&app.handle())?;
+            let directories = ensure_project_directories(app.handle())?;
             println!("[daily] project data root: {}", directories.root.display());
             println!("[daily] data file path: {}", directories.data.join(DATA_FILE_NAME).display());
             println!("[daily] config file path: {}", directories.data.join(CONFIG_FILE_NAME).display());
+            // A crash between removing a file and renaming its temp file leaves only `*.json.tmp` behind.
+            let recovered_files = [DATA_FILE_NAME, CONFIG_FILE_NAME]
+                .into_iter()
+                .filter(|file_name| {
+                    crash::recover_interrupted_write(&directories.data.join(file_name)).unwrap_or_else(|error| {
+                        eprintln!("[daily] {error}");
+                        false
+                    })
+                })
+                .collect::<Vec<_>>();
+            let (config_state, config_load_error) = ConfigState::load(&directories.data.join(CONFIG_FILE_NAME));
+            let initial_config = config_state.snapshot();
+            i18n::set_configured_language(initial_config.language);
+            app.manage(config_state);
+            app.manage(widget_geometry::WidgetGeometryState::default());
+            app.manage(widget_health::WidgetHealthState::default());
+            app.manage(widget_interaction::WidgetPeekState::default());
+            app.manage(app_data::AppDataState::default());
+            app.manage(tray::TrayBadgeState::default());
+            // The tray lists tasks before any window has loaded data.json.
+            if let Ok(Some(data)) = read_app_data_file(&directories.data.join(DATA_FILE_NAME)) {
+                app_data::remember_app_data(app.handle(), &data);
+            }
+            let logging_state = LoggingState::from_config(Some(&initial_config.to_value()));
+            if let Err(error) = logging_state.start_writer(&directories.logs) {
+                eprintln!("[log] falling back to synchronous logging: {error}");
+            }
+            app.manage(logging_state);
+            crash::install_panic_hook(app.handle(), &directories.logs);
             log_project_event(
-                &app.handle(),
+                app.handle(),
                 "info",
                 "app.startup",
                 "Application startup completed and directories ensured.",
@@ -872,77 +1280,133 @@ pub fn run() {
                     "backup_dir": directories.backup.display().to_string(),
                 })),
             );
+            if !recovered_files.is_empty() {
+                log_project_event(
+                    app.handle(),
+                    "warn",
+                    "app.interrupted_write.recovered",
+                    "Restored files whose last write was interrupted.",
+                    Some(serde_json::json!({ "files": recovered_files })),
+                );
+            }
+            if let Some(error) = config_load_error {
+                log_project_event(
+                    app.handle(),
+                    "error",
+                    "config.load.failure",
+                    "Failed to load config.json; unreadable values use their defaults and a copy of the file is kept.",
+                    Some(serde_json::json!({
+                        "path": directories.data.join(CONFIG_FILE_NAME).display().to_string(),
+                        "error": error
+                    })),
+                );
+            }
+            if let Ok(crash_reports) = crash::list_crash_reports(&directories.logs) {
+                if let Some(latest) = crash_reports.first() {
+                    log_project_event(
+                        app.handle(),
+                        "warn",
+                        "app.crash_report.detected",
+                        "Crash reports from a previous run were found.",
+                        Some(serde_json::json!({
+                            "count": crash_reports.len(),
+                            "latest": latest.file_name,
+                        })),
+                    );
+                }
+            }
 
             #[cfg(desktop)]
             {
                 app.manage(ExitState::default());
-                app.manage(WidgetLockState::default());
-                let initial_widget_visible = read_app_config_file(&directories.data.join(CONFIG_FILE_NAME))
-                    .ok()
-                    .flatten()
-                    .and_then(|config| config.get("widgetVisible").and_then(serde_json::Value::as_bool))
-                    .unwrap_or(false);
-                app.manage(WidgetVisibilityState(AtomicBool::new(initial_widget_visible)));
 
-                let widget_locked = app
-                    .try_state::<WidgetLockState>()
-                    .map(|state| state.0.load(Ordering::SeqCst))
-                    .unwrap_or(false);
-                let widget_visible = app
-                    .try_state::<WidgetVisibilityState>()
-                    .map(|state| state.0.load(Ordering::SeqCst))
-                    .unwrap_or(false);
-                let tray_menu = build_tray_menu_v2(&app.handle(), widget_locked, widget_visible)?;
+                tray::create_tray(app.handle())?;
 
-                let mut tray_builder = TrayIconBuilder::with_id(TRAY_ID)
-                    .menu(&tray_menu)
-                    .show_menu_on_left_click(false)
-                    .tooltip("daily");
+                if let Err(error) = window::start_monitor_watcher(app.handle()) {
+                    eprintln!("[widget] {error}");
+                }
 
-                if let Some(default_icon) = app.default_window_icon().cloned() {
-                    tray_builder = tray_builder.icon(default_icon);
+                let restore_handle = app.handle().clone();
+                if let Err(error) = std::thread::Builder::new()
+                    .name("daily-widget-restore".to_string())
+                    .spawn(move || restore_visible_widgets(&restore_handle))
+                {
+                    eprintln!("[widget] failed to spawn widget restore thread: {error}");
                 }
 
-                tray_builder.build(app)?;
-
-                app.handle().plugin(
-                    tauri_plugin_global_shortcut::Builder::new()
-                        .with_shortcuts(["ctrl+shift+l"])?
-                        .with_handler(|app, shortcut, event| {
-                            if event.state == ShortcutState::Pressed
-                                && shortcut
-                                    .matches(Modifiers::CONTROL | Modifiers::SHIFT, Code::KeyL)
-                            {
-                                toggle_widget_lock_v2(app);
-                                log_project_event(
-                                    app,
-                                    "info",
-                                    "shortcut.toggle_widget_lock",
-                                    "Global shortcut requested widget lock toggle.",
-                                    Some(serde_json::json!({
-                                        "shortcut": "Ctrl+Shift+L"
-                                    })),
-                                );
-                            }
-                        })
-                        .build(),
-                )?;
+                app.manage(shortcuts::ShortcutRegistry::default());
+                let shortcut_plugin = tauri_plugin_global_shortcut::Builder::new()
+                    .with_handler(|app, shortcut, event| {
+                        if event.state != ShortcutState::Pressed {
+                            return;
+                        }
+                        let action = app
+                            .try_state::<shortcuts::ShortcutRegistry>()
+                            .and_then(|registry| registry.action_for(shortcut));
+                        if let Some(action) = action {
+                            run_shortcut_action(app, action, &shortcut.to_string());
+                        }
+                    })
+                    .build();
+                match app.handle().plugin(shortcut_plugin) {
+                    Ok(()) => {
+                        apply_global_shortcuts(app.handle(), &initial_config);
+                    }
+                    Err(error) => log_project_event(
+                        app.handle(),
+                        "error",
+                        "shortcut.plugin.failure",
+                        "Global shortcuts are unavailable.",
+                        Some(serde_json::json!({ "error": error.to_string() })),
+                    ),
+                }
             }
 
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             ensure_widget_window,
+            close_widget_window,
+            list_widget_instances,
+            widget_ready,
+            start_widget_drag,
             focus_main_window,
             resize_widget_window_atomic,
+            list_monitors,
+            move_widget_to_monitor,
             sync_widget_locked_state,
+            set_widget_opacity,
+            set_widget_click_through,
+            set_widget_desktop_layer,
+            peek_widget,
             sync_widget_visibility_state,
             load_app_data,
             save_app_data,
             load_app_config,
-            save_app_config
+            update_app_config,
+            list_global_shortcuts,
+            set_global_shortcut,
+            reload_global_shortcuts,
+            open_quick_capture,
+            submit_quick_capture,
+            query_logs,
+            start_log_tail,
+            stop_log_tail,
+            list_crash_reports,
+            read_crash_report,
+            clear_crash_reports,
+            export_diagnostics
         ])
-        .run(tauri::generate_context!());
+        .build(tauri::generate_context!())
+        .map(|app| {
+            app.run(|app_handle, event| {
+                if let tauri::RunEvent::Exit = event {
+                    if let Some(logging_state) = app_handle.try_state::<LoggingState>() {
+                        logging_state.flush();
+                    }
+                }
+            })
+        });
 
     if let Err(error) = result {
         if is_ignorable_shutdown_error(&error) {
@@ -961,9 +1425,9 @@ pub fn run() {
 #[cfg(test)]
 mod tests {
     use super::{
-        append_jsonl, create_log_entry, ensure_directory_exists, read_app_config_file, read_app_data_file,
-        write_app_config_file, write_app_data_file,
+        ensure_directory_exists, read_app_config_file, read_app_data_file, write_app_config_file, write_app_data_file,
     };
+    use crate::error::ErrorCode;
     use serde_json::json;
     use std::{fs, path::PathBuf};
 
@@ -1060,54 +1524,7 @@ mod tests {
         fs::write(&data_file_path, "{invalid json").expect("write invalid file");
 
         let error = read_app_data_file(&data_file_path).expect_err("should fail parse");
-        assert!(error.contains("failed to parse app data file"));
-    }
-
-    #[test]
-    fn append_jsonl_creates_log_file_and_writes_single_line() {
-        let temp_dir = tempfile::tempdir().expect("create temp dir");
-        let log_file_path = make_temp_path(&temp_dir, "logs/events.jsonl");
-        let line = json!({ "event": "startup", "ok": true });
-
-        append_jsonl(&log_file_path, &line).expect("append should succeed");
-        let content = fs::read_to_string(&log_file_path).expect("log file should exist");
-        let parsed: serde_json::Value = serde_json::from_str(content.trim_end()).expect("line should be valid json");
-
-        assert_eq!(parsed["event"], "startup");
-        assert_eq!(parsed["ok"], true);
-    }
-
-    #[test]
-    fn append_jsonl_appends_multiple_lines() {
-        let temp_dir = tempfile::tempdir().expect("create temp dir");
-        let log_file_path = make_temp_path(&temp_dir, "logs/events.jsonl");
-
-        append_jsonl(&log_file_path, &json!({ "index": 1 })).expect("first append should succeed");
-        append_jsonl(&log_file_path, &json!({ "index": 2 })).expect("second append should succeed");
-
-        let content = fs::read_to_string(&log_file_path).expect("log file should exist");
-        let lines: Vec<&str> = content.lines().collect();
-        assert_eq!(lines.len(), 2);
-
-        let first: serde_json::Value = serde_json::from_str(lines[0]).expect("first line should be valid");
-        let second: serde_json::Value = serde_json::from_str(lines[1]).expect("second line should be valid");
-        assert_eq!(first["index"], 1);
-        assert_eq!(second["index"], 2);
-    }
-
-    #[test]
-    fn create_log_entry_contains_required_fields() {
-        let entry = create_log_entry(
-            "info",
-            "data.save.success",
-            "Saved data.json successfully.",
-            Some(json!({ "path": "/tmp/data.json" })),
-        );
-
-        assert_eq!(entry["level"], "info");
-        assert_eq!(entry["event"], "data.save.success");
-        assert_eq!(entry["message"], "Saved data.json successfully.");
-        assert_eq!(entry["data"]["path"], "/tmp/data.json");
-        assert!(entry["timestamp"].is_number());
+        assert_eq!(error.code(), ErrorCode::Parse);
+        assert!(error.message().contains("failed to parse app data file"));
     }
 }
@@ -0,0 +1,1473 @@
+use std::{
+    collections::{BTreeSet, VecDeque},
+    fs,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc, Mutex, RwLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use chrono::{DateTime, Local, NaiveDate};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager, Runtime};
+
+use crate::{
+    ensure_directory_exists, ensure_project_directories,
+    error::{AppError, AppResult},
+};
+
+pub(crate) const LOG_FILE_NAME: &str = "events.jsonl";
+const LOG_FILE_STEM: &str = "events";
+const LOG_FILE_EXTENSION: &str = "jsonl";
+const LOG_COMPRESSED_EXTENSION: &str = "gz";
+const LOG_CONFIG_KEY: &str = "logging";
+const LOG_LEVEL_ENV_VAR: &str = "DAILY_LOG_LEVEL";
+const DEFAULT_LOG_MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+const DEFAULT_LOG_RETAIN_FILES: usize = 14;
+const DEFAULT_LOG_RETAIN_DAYS: u32 = 30;
+const DEFAULT_LOG_QUERY_LIMIT: usize = 200;
+const MAX_LOG_QUERY_LIMIT: usize = 2000;
+const REVERSE_READ_CHUNK_BYTES: u64 = 64 * 1024;
+const LOG_WRITER_THREAD_NAME: &str = "daily-log-writer";
+const LOG_WRITER_MAX_BATCH: usize = 256;
+const LOG_FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+const RECENT_LOG_CAPACITY: usize = 100;
+pub(crate) const EVENT_LOG_ENTRY_APPENDED: &str = "log-entry-appended";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LogRotationPolicy {
+    pub max_file_bytes: u64,
+    pub retain_files: usize,
+    pub retain_days: Option<u32>,
+    pub compress: bool,
+}
+
+impl Default for LogRotationPolicy {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: DEFAULT_LOG_MAX_FILE_BYTES,
+            retain_files: DEFAULT_LOG_RETAIN_FILES,
+            retain_days: Some(DEFAULT_LOG_RETAIN_DAYS),
+            compress: false,
+        }
+    }
+}
+
+impl LogRotationPolicy {
+    /// Reads the `logging` section of config.json, falling back to defaults for missing or invalid keys.
+    pub(crate) fn from_config(config: Option<&serde_json::Value>) -> Self {
+        let mut policy = Self::default();
+        let Some(logging) = config.and_then(|config| config.get(LOG_CONFIG_KEY)) else {
+            return policy;
+        };
+
+        if let Some(max_file_bytes) = logging.get("maxFileBytes").and_then(serde_json::Value::as_u64) {
+            policy.max_file_bytes = max_file_bytes.max(1);
+        }
+        if let Some(retain_files) = logging.get("retainFiles").and_then(serde_json::Value::as_u64) {
+            policy.retain_files = usize::try_from(retain_files).unwrap_or(usize::MAX);
+        }
+        match logging.get("retainDays") {
+            Some(serde_json::Value::Null) => policy.retain_days = None,
+            Some(value) => {
+                if let Some(retain_days) = value.as_u64() {
+                    policy.retain_days = Some(u32::try_from(retain_days).unwrap_or(u32::MAX));
+                }
+            }
+            None => {}
+        }
+        if let Some(compress) = logging.get("compressRotated").and_then(serde_json::Value::as_bool) {
+            policy.compress = compress;
+        }
+
+        policy
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Off,
+}
+
+impl LogLevel {
+    pub(crate) fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "trace" => Some(Self::Trace),
+            "debug" => Some(Self::Debug),
+            "info" => Some(Self::Info),
+            "warn" | "warning" => Some(Self::Warn),
+            "error" => Some(Self::Error),
+            "off" | "none" => Some(Self::Off),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LogFilter {
+    pub min_level: LogLevel,
+    /// Per event-prefix minimum levels; the longest matching prefix wins over `min_level`.
+    pub prefixes: Vec<(String, LogLevel)>,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            min_level: LogLevel::Info,
+            prefixes: Vec::new(),
+        }
+    }
+}
+
+impl LogFilter {
+    /// Reads `logging.level` and `logging.filters` from config.json; `DAILY_LOG_LEVEL` overrides the level.
+    pub(crate) fn from_config(config: Option<&serde_json::Value>, env_level: Option<&str>) -> Self {
+        let mut filter = Self::default();
+        let logging = config.and_then(|config| config.get(LOG_CONFIG_KEY));
+
+        if let Some(level) = logging
+            .and_then(|logging| logging.get("level"))
+            .and_then(serde_json::Value::as_str)
+            .and_then(LogLevel::parse)
+        {
+            filter.min_level = level;
+        }
+        if let Some(level) = env_level.and_then(LogLevel::parse) {
+            filter.min_level = level;
+        }
+
+        if let Some(filters) = logging
+            .and_then(|logging| logging.get("filters"))
+            .and_then(serde_json::Value::as_object)
+        {
+            filter.prefixes = filters
+                .iter()
+                .filter_map(|(prefix, level)| {
+                    let level = LogLevel::parse(level.as_str()?)?;
+                    let prefix = prefix.trim_end_matches('*').trim_end_matches('.');
+                    Some((prefix.to_string(), level))
+                })
+                .collect();
+        }
+
+        filter
+    }
+
+    pub(crate) fn allows(&self, level: &str, event: &str) -> bool {
+        let level = LogLevel::parse(level).unwrap_or(LogLevel::Info);
+        let threshold = self
+            .prefixes
+            .iter()
+            .filter(|(prefix, _)| event_matches_prefix(event, prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, threshold)| *threshold)
+            .unwrap_or(self.min_level);
+
+        threshold != LogLevel::Off && level >= threshold
+    }
+}
+
+fn event_matches_prefix(event: &str, prefix: &str) -> bool {
+    prefix.is_empty()
+        || event
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+#[derive(Debug, Clone, Default)]
+struct LogSettings {
+    rotation: LogRotationPolicy,
+    filter: LogFilter,
+}
+
+impl LogSettings {
+    fn from_config(config: Option<&serde_json::Value>) -> Self {
+        let env_level = std::env::var(LOG_LEVEL_ENV_VAR).ok();
+        Self {
+            rotation: LogRotationPolicy::from_config(config),
+            filter: LogFilter::from_config(config, env_level.as_deref()),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct LoggingState {
+    settings: Arc<RwLock<LogSettings>>,
+    tail_subscribers: Mutex<BTreeSet<String>>,
+    writer: Mutex<Option<mpsc::Sender<LogCommand>>>,
+    recent: Mutex<VecDeque<serde_json::Value>>,
+}
+
+impl LoggingState {
+    pub(crate) fn from_config(config: Option<&serde_json::Value>) -> Self {
+        Self {
+            settings: Arc::new(RwLock::new(LogSettings::from_config(config))),
+            tail_subscribers: Mutex::default(),
+            writer: Mutex::default(),
+            recent: Mutex::default(),
+        }
+    }
+
+    fn remember(&self, entry: &serde_json::Value) {
+        if let Ok(mut recent) = self.recent.lock() {
+            if recent.len() == RECENT_LOG_CAPACITY {
+                recent.pop_front();
+            }
+            recent.push_back(entry.clone());
+        }
+    }
+
+    /// Returns the most recent entries kept in memory, oldest first. Returns nothing instead of waiting when another
+    /// thread holds the buffer, so the panic hook cannot deadlock on it.
+    pub(crate) fn recent_entries(&self) -> Vec<serde_json::Value> {
+        self.recent
+            .try_lock()
+            .map(|recent| recent.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Spawns the background writer that owns `events.jsonl`; until then entries are written synchronously.
+    pub(crate) fn start_writer(&self, logs_dir: &Path) -> AppResult<()> {
+        let (sender, receiver) = mpsc::channel::<LogCommand>();
+        let mut writer = LogWriter::new(logs_dir.join(LOG_FILE_NAME), Arc::clone(&self.settings));
+        std::thread::Builder::new()
+            .name(LOG_WRITER_THREAD_NAME.to_string())
+            .spawn(move || writer.run(receiver))
+            .map_err(|error| AppError::internal(format!("failed to spawn log writer thread: {error}")))?;
+
+        if let Ok(mut current) = self.writer.lock() {
+            *current = Some(sender);
+        }
+        Ok(())
+    }
+
+    fn enqueue(&self, entry: &serde_json::Value) -> bool {
+        let Ok(writer) = self.writer.lock() else {
+            return false;
+        };
+
+        writer
+            .as_ref()
+            .is_some_and(|sender| sender.send(LogCommand::Entry(entry.clone())).is_ok())
+    }
+
+    /// Blocks until every queued entry has reached the file, or the flush timeout elapses.
+    pub(crate) fn flush(&self) {
+        Self::flush_through(self.writer.lock().ok().and_then(|writer| writer.clone()));
+    }
+
+    /// Like `flush`, but skips the flush instead of waiting when another thread holds the writer handle.
+    pub(crate) fn try_flush(&self) {
+        Self::flush_through(self.writer.try_lock().ok().and_then(|writer| writer.clone()));
+    }
+
+    fn flush_through(sender: Option<mpsc::Sender<LogCommand>>) {
+        if std::thread::current().name() == Some(LOG_WRITER_THREAD_NAME) {
+            return;
+        }
+
+        let Some(sender) = sender else {
+            return;
+        };
+        let (ack_sender, ack_receiver) = mpsc::channel();
+        if sender.send(LogCommand::Flush(ack_sender)).is_ok() {
+            let _ = ack_receiver.recv_timeout(LOG_FLUSH_TIMEOUT);
+        }
+    }
+
+    pub(crate) fn subscribe_tail(&self, window_label: &str) {
+        if let Ok(mut subscribers) = self.tail_subscribers.lock() {
+            subscribers.insert(window_label.to_string());
+        }
+    }
+
+    pub(crate) fn unsubscribe_tail(&self, window_label: &str) {
+        if let Ok(mut subscribers) = self.tail_subscribers.lock() {
+            subscribers.remove(window_label);
+        }
+    }
+
+    fn tail_subscribers(&self) -> Vec<String> {
+        self.tail_subscribers
+            .lock()
+            .map(|subscribers| subscribers.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Re-reads rotation and filter settings so config.json edits apply without a restart.
+    pub(crate) fn apply_config(&self, config: Option<&serde_json::Value>) {
+        if let Ok(mut settings) = self.settings.write() {
+            *settings = LogSettings::from_config(config);
+        }
+    }
+
+    fn allows(&self, level: &str, event: &str) -> bool {
+        self.settings
+            .read()
+            .map(|settings| settings.filter.allows(level, event))
+            .unwrap_or(true)
+    }
+
+    fn rotation_policy(&self) -> LogRotationPolicy {
+        self.settings
+            .read()
+            .map(|settings| settings.rotation.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct RotatedLogFile {
+    date: NaiveDate,
+    sequence: u32,
+    path: PathBuf,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct LogQuery {
+    /// Minimum level to include.
+    pub level: Option<String>,
+    pub event_prefix: Option<String>,
+    /// Inclusive lower bound in unix milliseconds.
+    pub from: Option<u64>,
+    /// Inclusive upper bound in unix milliseconds.
+    pub to: Option<u64>,
+    /// Continue with the entries older than this position; `None` starts from the newest entry.
+    pub cursor: Option<LogCursor>,
+    pub limit: Option<usize>,
+}
+
+/// Where a page stopped: the start of its last entry. Entries appended later land after it, so they never shift the
+/// pages that follow.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LogCursor {
+    /// Rotated log the entry was read from; `None` for the live log.
+    pub file: Option<String>,
+    /// Newest rotated log at the time. A newer one means the live log has been rotated into it since.
+    pub newest_rotated: Option<String>,
+    /// Byte offset of the entry in the uncompressed file.
+    pub offset: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LogQueryPage {
+    /// Matching entries, newest first.
+    pub entries: Vec<serde_json::Value>,
+    pub next_cursor: Option<LogCursor>,
+}
+
+impl LogQuery {
+    fn matches(&self, entry: &serde_json::Value) -> bool {
+        if let Some(min_level) = self.level.as_deref().and_then(LogLevel::parse) {
+            let level = entry
+                .get("level")
+                .and_then(serde_json::Value::as_str)
+                .and_then(LogLevel::parse)
+                .unwrap_or(LogLevel::Info);
+            if level < min_level {
+                return false;
+            }
+        }
+
+        if let Some(prefix) = self.event_prefix.as_deref() {
+            let event = entry.get("event").and_then(serde_json::Value::as_str).unwrap_or("");
+            if !event_matches_prefix(event, prefix.trim_end_matches('*').trim_end_matches('.')) {
+                return false;
+            }
+        }
+
+        let timestamp = entry_timestamp(entry);
+        self.from.is_none_or(|from| timestamp >= from) && self.to.is_none_or(|to| timestamp <= to)
+    }
+}
+
+/// Yields the lines of a file that end before `end`, from last to first, each with the offset it starts at, without
+/// loading the file whole.
+struct ReverseLineReader {
+    file: File,
+    position: u64,
+    pending: Vec<u8>,
+}
+
+impl ReverseLineReader {
+    fn open(path: &Path, end: Option<u64>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        let position = end.map_or(len, |end| end.min(len));
+        Ok(Self {
+            file,
+            position,
+            pending: Vec::new(),
+        })
+    }
+}
+
+impl Iterator for ReverseLineReader {
+    type Item = io::Result<(u64, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(index) = self.pending.iter().rposition(|byte| *byte == b'\n') {
+                let line = self.pending.split_off(index + 1);
+                self.pending.truncate(index);
+                if line.is_empty() {
+                    continue;
+                }
+                let start = self.position + index as u64 + 1;
+                return Some(Ok((start, String::from_utf8_lossy(&line).into_owned())));
+            }
+
+            if self.position == 0 {
+                if self.pending.is_empty() {
+                    return None;
+                }
+                let line = std::mem::take(&mut self.pending);
+                return Some(Ok((0, String::from_utf8_lossy(&line).into_owned())));
+            }
+
+            let read_size = REVERSE_READ_CHUNK_BYTES.min(self.position);
+            self.position -= read_size;
+            let mut chunk = vec![0; read_size as usize];
+            if let Err(error) = self
+                .file
+                .seek(SeekFrom::Start(self.position))
+                .and_then(|_| self.file.read_exact(&mut chunk))
+            {
+                self.position = 0;
+                self.pending.clear();
+                return Some(Err(error));
+            }
+            chunk.extend_from_slice(&self.pending);
+            self.pending = chunk;
+        }
+    }
+}
+
+enum LogCommand {
+    Entry(serde_json::Value),
+    Flush(mpsc::Sender<()>),
+}
+
+/// Background writer that keeps the live log open and appends queued entries in batches.
+struct LogWriter {
+    path: PathBuf,
+    settings: Arc<RwLock<LogSettings>>,
+    file: Option<BufWriter<File>>,
+    file_date: NaiveDate,
+    file_len: u64,
+}
+
+impl LogWriter {
+    fn new(path: PathBuf, settings: Arc<RwLock<LogSettings>>) -> Self {
+        Self {
+            path,
+            settings,
+            file: None,
+            file_date: Local::now().date_naive(),
+            file_len: 0,
+        }
+    }
+
+    fn run(&mut self, receiver: mpsc::Receiver<LogCommand>) {
+        let mut batch = Vec::with_capacity(LOG_WRITER_MAX_BATCH);
+        while let Ok(command) = receiver.recv() {
+            let mut pending_acks = Vec::new();
+            let mut next = Some(command);
+            while let Some(command) = next.take() {
+                match command {
+                    LogCommand::Entry(entry) => batch.push(entry),
+                    LogCommand::Flush(ack) => pending_acks.push(ack),
+                }
+                if batch.len() < LOG_WRITER_MAX_BATCH {
+                    next = receiver.try_recv().ok();
+                }
+            }
+
+            if let Err(error) = self.write_batch(&batch) {
+                eprintln!("[log] failed to write {} entries: {error}", batch.len());
+                self.file = None;
+            }
+            batch.clear();
+            for ack in pending_acks {
+                let _ = ack.send(());
+            }
+        }
+
+        self.close();
+    }
+
+    fn open(&mut self) -> AppResult<&mut BufWriter<File>> {
+        if let Some(ref mut file) = self.file {
+            return Ok(file);
+        }
+
+        if let Some(parent_dir) = self.path.parent() {
+            ensure_directory_exists(parent_dir)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|error| AppError::io(format!("failed to open log file `{}`: {error}", self.path.display())))?;
+        let metadata = file
+            .metadata()
+            .map_err(|error| AppError::io(format!("failed to inspect log file `{}`: {error}", self.path.display())))?;
+
+        self.file_len = metadata.len();
+        self.file_date = metadata
+            .modified()
+            .ok()
+            .filter(|_| metadata.len() > 0)
+            .map(|modified| DateTime::<Local>::from(modified).date_naive())
+            .unwrap_or_else(|| Local::now().date_naive());
+        Ok(self.file.insert(BufWriter::new(file)))
+    }
+
+    fn close(&mut self) {
+        if let Some(mut file) = self.file.take() {
+            let _ = file.flush();
+        }
+    }
+
+    fn rotate_if_needed(&mut self, now: DateTime<Local>) {
+        let policy = self
+            .settings
+            .read()
+            .map(|settings| settings.rotation.clone())
+            .unwrap_or_default();
+        if self.file.is_some() && self.file_date >= now.date_naive() && self.file_len < policy.max_file_bytes {
+            return;
+        }
+
+        self.close();
+        if let Err(error) = rotate_log_file_if_needed(&self.path, &policy, now) {
+            eprintln!("[log] rotation failed: {error}");
+        }
+    }
+
+    fn write_batch(&mut self, entries: &[serde_json::Value]) -> AppResult<()> {
+        if entries.is_empty() {
+            return self.file.as_mut().map_or(Ok(()), |file| {
+                file.flush().map_err(|error| AppError::io(format!("failed to flush log file: {error}")))
+            });
+        }
+
+        self.rotate_if_needed(Local::now());
+        let path = self.path.clone();
+        let file = self.open()?;
+        let mut written = 0;
+        for entry in entries {
+            let serialized =
+                serde_json::to_string(entry)
+                    .map_err(|error| AppError::internal(format!("failed to serialize log line: {error}")))?;
+            writeln!(file, "{serialized}")
+                .map_err(|error| AppError::io(format!("failed to append log line `{}`: {error}", path.display())))?;
+            written += serialized.len() as u64 + 1;
+        }
+        file.flush()
+            .map_err(|error| AppError::io(format!("failed to flush log file `{}`: {error}", path.display())))?;
+        self.file_len += written;
+
+        Ok(())
+    }
+}
+
+fn resolve_project_log_file_path<R: Runtime>(app: &tauri::AppHandle<R>) -> AppResult<PathBuf> {
+    let directories = ensure_project_directories(app)?;
+    Ok(directories.logs.join(LOG_FILE_NAME))
+}
+
+pub(crate) fn now_unix_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0)
+}
+
+pub(crate) fn create_log_entry(
+    level: &str,
+    event: &str,
+    message: &str,
+    data: Option<serde_json::Value>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "timestamp": now_unix_millis(),
+        "level": level,
+        "event": event,
+        "message": message,
+        "data": data
+    })
+}
+
+pub(crate) fn append_jsonl(path: &Path, line: &serde_json::Value) -> AppResult<()> {
+    if let Some(parent_dir) = path.parent() {
+        ensure_directory_exists(parent_dir)?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|error| AppError::io(format!("failed to open log file `{}`: {error}", path.display())))?;
+
+    let serialized = serde_json::to_string(line)
+        .map_err(|error| AppError::internal(format!("failed to serialize log line: {error}")))?;
+    writeln!(file, "{serialized}")
+        .map_err(|error| AppError::io(format!("failed to append log line `{}`: {error}", path.display())))?;
+
+    Ok(())
+}
+
+fn rotated_log_file_name(date: NaiveDate, sequence: u32) -> String {
+    if sequence == 0 {
+        format!("{LOG_FILE_STEM}-{date}.{LOG_FILE_EXTENSION}")
+    } else {
+        format!("{LOG_FILE_STEM}-{date}.{sequence}.{LOG_FILE_EXTENSION}")
+    }
+}
+
+/// Parses `events-YYYY-MM-DD[.N].jsonl[.gz]`; the live `events.jsonl` is not a rotated file.
+fn parse_rotated_log_file_name(file_name: &str) -> Option<(NaiveDate, u32)> {
+    let rest = file_name.strip_prefix(&format!("{LOG_FILE_STEM}-"))?;
+    let rest = rest
+        .strip_suffix(&format!(".{LOG_COMPRESSED_EXTENSION}"))
+        .unwrap_or(rest);
+    let rest = rest.strip_suffix(&format!(".{LOG_FILE_EXTENSION}"))?;
+    let (date_part, sequence) = match rest.split_once('.') {
+        Some((date_part, sequence)) => (date_part, sequence.parse::<u32>().ok()?),
+        None => (rest, 0),
+    };
+    let date = NaiveDate::parse_from_str(date_part, "%Y-%m-%d").ok()?;
+
+    Some((date, sequence))
+}
+
+fn list_rotated_log_files(logs_dir: &Path) -> AppResult<Vec<RotatedLogFile>> {
+    let entries = match fs::read_dir(logs_dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => {
+            return Err(AppError::io(format!(
+                "failed to read log directory `{}`: {error}",
+                logs_dir.display()
+            )))
+        }
+    };
+
+    let mut rotated = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            let (date, sequence) = parse_rotated_log_file_name(file_name.to_str()?)?;
+            Some(RotatedLogFile {
+                date,
+                sequence,
+                path: entry.path(),
+            })
+        })
+        .collect::<Vec<_>>();
+    rotated.sort();
+
+    Ok(rotated)
+}
+
+fn next_rotated_log_file_path(logs_dir: &Path, date: NaiveDate) -> PathBuf {
+    let mut sequence = 0;
+    loop {
+        let candidate = logs_dir.join(rotated_log_file_name(date, sequence));
+        let compressed = candidate.with_extension(format!("{LOG_FILE_EXTENSION}.{LOG_COMPRESSED_EXTENSION}"));
+        if !candidate.exists() && !compressed.exists() {
+            return candidate;
+        }
+        sequence += 1;
+    }
+}
+
+fn gzip_log_file(path: &Path) -> AppResult<PathBuf> {
+    let compressed_path = path.with_extension(format!("{LOG_FILE_EXTENSION}.{LOG_COMPRESSED_EXTENSION}"));
+    let mut source =
+        File::open(path)
+            .map_err(|error| AppError::io(format!("failed to open rotated log `{}`: {error}", path.display())))?;
+    let target = File::create(&compressed_path).map_err(|error| {
+        AppError::io(format!(
+            "failed to create compressed log `{}`: {error}",
+            compressed_path.display()
+        ))
+    })?;
+
+    let mut encoder = GzEncoder::new(target, Compression::default());
+    io::copy(&mut source, &mut encoder)
+        .and_then(|_| encoder.finish())
+        .map_err(|error| AppError::io(format!("failed to compress rotated log `{}`: {error}", path.display())))?;
+    fs::remove_file(path)
+        .map_err(|error| AppError::io(format!("failed to remove uncompressed log `{}`: {error}", path.display())))?;
+
+    Ok(compressed_path)
+}
+
+/// Rotates the live log when it was last written on an earlier day or has reached the size limit.
+/// Returns the path of the rotated file, if any.
+pub(crate) fn rotate_log_file_if_needed(
+    path: &Path,
+    policy: &LogRotationPolicy,
+    now: DateTime<Local>,
+) -> AppResult<Option<PathBuf>> {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(AppError::io(format!("failed to inspect log file `{}`: {error}", path.display()))),
+    };
+
+    let file_date = metadata
+        .modified()
+        .map(|modified| DateTime::<Local>::from(modified).date_naive())
+        .unwrap_or_else(|_| now.date_naive());
+    let is_stale = file_date < now.date_naive();
+    let is_oversized = metadata.len() >= policy.max_file_bytes;
+    if metadata.len() == 0 || (!is_stale && !is_oversized) {
+        return Ok(None);
+    }
+
+    let Some(logs_dir) = path.parent() else {
+        return Ok(None);
+    };
+    let rotated_path = next_rotated_log_file_path(logs_dir, file_date);
+    fs::rename(path, &rotated_path).map_err(|error| {
+        AppError::io(format!(
+            "failed to rotate log file `{}` to `{}`: {error}",
+            path.display(),
+            rotated_path.display()
+        ))
+    })?;
+
+    let rotated_path = if policy.compress {
+        gzip_log_file(&rotated_path)?
+    } else {
+        rotated_path
+    };
+    enforce_log_retention(logs_dir, policy, now)?;
+
+    Ok(Some(rotated_path))
+}
+
+/// Deletes rotated logs beyond the configured file count or older than the configured age.
+pub(crate) fn enforce_log_retention(
+    logs_dir: &Path,
+    policy: &LogRotationPolicy,
+    now: DateTime<Local>,
+) -> AppResult<()> {
+    let oldest_kept_date = policy
+        .retain_days
+        .and_then(|retain_days| now.date_naive().checked_sub_days(chrono::Days::new(u64::from(retain_days))));
+
+    let rotated = list_rotated_log_files(logs_dir)?;
+    for (index, file) in rotated.iter().rev().enumerate() {
+        let beyond_count = index >= policy.retain_files;
+        let beyond_age = oldest_kept_date.is_some_and(|oldest| file.date < oldest);
+        if !beyond_count && !beyond_age {
+            continue;
+        }
+
+        fs::remove_file(&file.path)
+            .map_err(|error| AppError::io(format!("failed to remove expired log `{}`: {error}", file.path.display())))?;
+    }
+
+    Ok(())
+}
+
+fn entry_timestamp(entry: &serde_json::Value) -> u64 {
+    entry.get("timestamp").and_then(serde_json::Value::as_u64).unwrap_or(0)
+}
+
+/// Lists the live log followed by rotated logs, newest first.
+pub(crate) fn list_log_files_newest_first(logs_dir: &Path) -> AppResult<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let live = logs_dir.join(LOG_FILE_NAME);
+    if live.exists() {
+        files.push(live);
+    }
+    files.extend(list_rotated_log_files(logs_dir)?.into_iter().rev().map(|file| file.path));
+
+    Ok(files)
+}
+
+/// One file a query reads, newest entries first, from `end` (or the end of the file) backwards.
+struct LogScan {
+    path: PathBuf,
+    /// `None` for the live log.
+    rotated: Option<RotatedLogFile>,
+    end: Option<u64>,
+}
+
+fn file_name_of(path: &Path) -> Option<String> {
+    path.file_name().map(|name| name.to_string_lossy().into_owned())
+}
+
+/// The files a query reads, newest first, starting where `cursor` stopped.
+fn log_scans_from(logs_dir: &Path, cursor: Option<&LogCursor>) -> AppResult<(Vec<LogScan>, Option<String>)> {
+    let rotated = list_rotated_log_files(logs_dir)?;
+    let newest_rotated = rotated.last().and_then(|file| file_name_of(&file.path));
+    let key = |name: Option<&String>| -> AppResult<Option<(NaiveDate, u32)>> {
+        name.map(|name| {
+            parse_rotated_log_file_name(name).ok_or_else(|| {
+                AppError::validation("log cursor does not name a rotated log").with_context("file", name.as_str())
+            })
+        })
+        .transpose()
+    };
+    let older_than = |limit: Option<(NaiveDate, u32)>| {
+        rotated
+            .iter()
+            .rev()
+            .filter(move |file| limit.is_none_or(|limit| (file.date, file.sequence) < limit))
+            .map(|file| LogScan {
+                path: file.path.clone(),
+                rotated: Some(file.clone()),
+                end: None,
+            })
+    };
+
+    let live_scan = |end: Option<u64>| {
+        let path = logs_dir.join(LOG_FILE_NAME);
+        path.exists().then_some(LogScan {
+            path,
+            rotated: None,
+            end,
+        })
+    };
+
+    let mut scans = Vec::new();
+    let (start, offset) = match cursor {
+        None => {
+            scans.extend(live_scan(None));
+            scans.extend(older_than(None));
+            return Ok((scans, newest_rotated));
+        }
+        Some(LogCursor { file: Some(file), offset, .. }) => (key(Some(file))?, *offset),
+        Some(LogCursor { file: None, newest_rotated: seen, offset }) => {
+            // The live log the cursor points into became the oldest rotated file newer than the one seen back then.
+            let seen = key(seen.as_ref())?;
+            match rotated.iter().find(|file| seen.is_none_or(|seen| (file.date, file.sequence) > seen)) {
+                Some(file) => (Some((file.date, file.sequence)), *offset),
+                None => {
+                    scans.extend(live_scan(Some(*offset)));
+                    scans.extend(older_than(None));
+                    return Ok((scans, newest_rotated));
+                }
+            }
+        }
+    };
+
+    // A rotated file removed by retention since the last page is skipped; older ones still follow.
+    if let Some(file) = rotated.iter().find(|file| Some((file.date, file.sequence)) == start) {
+        scans.push(LogScan {
+            path: file.path.clone(),
+            rotated: Some(file.clone()),
+            end: Some(offset),
+        });
+    }
+    scans.extend(older_than(start));
+    Ok((scans, newest_rotated))
+}
+
+/// Matching entries of one file that start before `scan.end`, newest first, each with its offset. A compressed file
+/// can only be read forwards, so it is streamed line by line keeping just the newest `keep` matches.
+fn matching_log_entries<'a>(
+    scan: &LogScan,
+    query: &'a LogQuery,
+    keep: usize,
+) -> AppResult<Box<dyn Iterator<Item = (u64, serde_json::Value)> + 'a>> {
+    let path = &scan.path;
+    let parse = move |line: &str| {
+        serde_json::from_str::<serde_json::Value>(line)
+            .ok()
+            .filter(|entry| query.matches(entry))
+    };
+    let is_compressed = path
+        .extension()
+        .is_some_and(|extension| extension == LOG_COMPRESSED_EXTENSION);
+    if !is_compressed {
+        let reader = ReverseLineReader::open(path, scan.end)
+            .map_err(|error| AppError::io(format!("failed to open log file `{}`: {error}", path.display())))?;
+        return Ok(Box::new(
+            reader
+                .map_while(Result::ok)
+                .filter_map(move |(start, line)| parse(&line).map(|entry| (start, entry))),
+        ));
+    }
+
+    let file = File::open(path)
+        .map_err(|error| AppError::io(format!("failed to open log file `{}`: {error}", path.display())))?;
+    let mut reader = BufReader::new(GzDecoder::new(file));
+    let mut newest = VecDeque::with_capacity(keep);
+    let mut line = Vec::new();
+    let mut offset = 0;
+    while scan.end.is_none_or(|end| offset < end) {
+        line.clear();
+        let read = reader
+            .read_until(b'\n', &mut line)
+            .map_err(|error| AppError::parse(format!("failed to decompress log file `{}`: {error}", path.display())))?;
+        if read == 0 {
+            break;
+        }
+        if let Some(entry) = parse(String::from_utf8_lossy(&line).trim_end()) {
+            if newest.len() == keep {
+                newest.pop_front();
+            }
+            newest.push_back((offset, entry));
+        }
+        offset += read as u64;
+    }
+
+    Ok(Box::new(newest.into_iter().rev()))
+}
+
+/// Scans the live and rotated logs from the newest entry backwards and returns one page of matches.
+pub(crate) fn query_logs(logs_dir: &Path, query: &LogQuery) -> AppResult<LogQueryPage> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_LOG_QUERY_LIMIT)
+        .clamp(1, MAX_LOG_QUERY_LIMIT);
+    let from_date = query
+        .from
+        .and_then(|from| DateTime::from_timestamp_millis(i64::try_from(from).ok()?))
+        .map(|from| from.with_timezone(&Local).date_naive());
+    let (scans, newest_rotated) = log_scans_from(logs_dir, query.cursor.as_ref())?;
+    let mut entries = Vec::new();
+    let mut last_cursor = None;
+
+    // Timestamps inside a file are not trusted to be ordered (the clock can step back, lines can lack one), so
+    // every line is checked. Only a rotated file last written before `from`'s day is known to hold nothing newer.
+    for scan in scans {
+        let last_written = scan.rotated.as_ref().map(|file| file.date);
+        if last_written.zip(from_date).is_some_and(|(last_written, from_date)| last_written < from_date) {
+            break;
+        }
+        // One match beyond the page tells whether another page follows.
+        for (offset, entry) in matching_log_entries(&scan, query, limit + 1 - entries.len())? {
+            if entries.len() == limit {
+                return Ok(LogQueryPage {
+                    entries,
+                    next_cursor: last_cursor,
+                });
+            }
+            entries.push(entry);
+            last_cursor = Some(LogCursor {
+                file: scan.rotated.as_ref().and_then(|file| file_name_of(&file.path)),
+                newest_rotated: newest_rotated.clone(),
+                offset,
+            });
+        }
+    }
+
+    Ok(LogQueryPage {
+        entries,
+        next_cursor: None,
+    })
+}
+
+fn append_project_log_entry_sync<R: Runtime>(
+    app: &tauri::AppHandle<R>,
+    logging_state: Option<&LoggingState>,
+    entry: &serde_json::Value,
+) {
+    let Ok(log_file_path) = resolve_project_log_file_path(app) else {
+        return;
+    };
+
+    let rotation_policy = logging_state
+        .map(|state| state.rotation_policy())
+        .unwrap_or_default();
+    if let Err(error) = rotate_log_file_if_needed(&log_file_path, &rotation_policy, Local::now()) {
+        eprintln!("[log] rotation failed: {error}");
+    }
+
+    let _ = append_jsonl(&log_file_path, entry);
+}
+
+pub(crate) fn log_project_event<R: Runtime>(
+    app: &tauri::AppHandle<R>,
+    level: &str,
+    event: &str,
+    message: &str,
+    data: Option<serde_json::Value>,
+) {
+    let logging_state = app.try_state::<LoggingState>();
+    if logging_state
+        .as_ref()
+        .is_some_and(|state| !state.allows(level, event))
+    {
+        return;
+    }
+
+    let entry = create_log_entry(level, event, message, data);
+    if let Some(state) = logging_state.as_ref() {
+        state.remember(&entry);
+    }
+    let queued = logging_state
+        .as_ref()
+        .is_some_and(|state| state.enqueue(&entry));
+    if !queued {
+        append_project_log_entry_sync(app, logging_state.as_deref(), &entry);
+    }
+
+    for window_label in logging_state
+        .map(|state| state.tail_subscribers())
+        .unwrap_or_default()
+    {
+        let _ = app.emit_to(window_label.as_str(), EVENT_LOG_ENTRY_APPENDED, &entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        append_jsonl, create_log_entry, enforce_log_retention, parse_rotated_log_file_name, query_logs,
+        rotate_log_file_if_needed, LogFilter, LogLevel, LogQuery, LogRotationPolicy, LoggingState, ReverseLineReader,
+        RECENT_LOG_CAPACITY,
+    };
+    use chrono::{Local, NaiveDate, TimeZone};
+    use serde_json::json;
+    use std::{fs, io::Read, path::PathBuf};
+
+    fn make_temp_path(temp_dir: &tempfile::TempDir, path: &str) -> PathBuf {
+        temp_dir.path().join(path)
+    }
+
+    fn sorted_file_names(temp_dir: &tempfile::TempDir) -> Vec<String> {
+        let mut names = fs::read_dir(temp_dir.path())
+            .expect("read temp dir")
+            .map(|entry| entry.expect("dir entry").file_name().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn append_jsonl_creates_log_file_and_writes_single_line() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let log_file_path = make_temp_path(&temp_dir, "logs/events.jsonl");
+        let line = json!({ "event": "startup", "ok": true });
+
+        append_jsonl(&log_file_path, &line).expect("append should succeed");
+        let content = fs::read_to_string(&log_file_path).expect("log file should exist");
+        let parsed: serde_json::Value = serde_json::from_str(content.trim_end()).expect("line should be valid json");
+
+        assert_eq!(parsed["event"], "startup");
+        assert_eq!(parsed["ok"], true);
+    }
+
+    #[test]
+    fn append_jsonl_appends_multiple_lines() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let log_file_path = make_temp_path(&temp_dir, "logs/events.jsonl");
+
+        append_jsonl(&log_file_path, &json!({ "index": 1 })).expect("first append should succeed");
+        append_jsonl(&log_file_path, &json!({ "index": 2 })).expect("second append should succeed");
+
+        let content = fs::read_to_string(&log_file_path).expect("log file should exist");
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let first: serde_json::Value = serde_json::from_str(lines[0]).expect("first line should be valid");
+        let second: serde_json::Value = serde_json::from_str(lines[1]).expect("second line should be valid");
+        assert_eq!(first["index"], 1);
+        assert_eq!(second["index"], 2);
+    }
+
+    #[test]
+    fn create_log_entry_contains_required_fields() {
+        let entry = create_log_entry(
+            "info",
+            "data.save.success",
+            "Saved data.json successfully.",
+            Some(json!({ "path": "/tmp/data.json" })),
+        );
+
+        assert_eq!(entry["level"], "info");
+        assert_eq!(entry["event"], "data.save.success");
+        assert_eq!(entry["message"], "Saved data.json successfully.");
+        assert_eq!(entry["data"]["path"], "/tmp/data.json");
+        assert!(entry["timestamp"].is_number());
+    }
+
+    #[test]
+    fn rotation_policy_reads_logging_section_from_config() {
+        let policy = LogRotationPolicy::from_config(Some(&json!({
+            "logging": {
+                "maxFileBytes": 1024,
+                "retainFiles": 3,
+                "retainDays": null,
+                "compressRotated": true
+            }
+        })));
+
+        assert_eq!(
+            policy,
+            LogRotationPolicy {
+                max_file_bytes: 1024,
+                retain_files: 3,
+                retain_days: None,
+                compress: true,
+            }
+        );
+        assert_eq!(LogRotationPolicy::from_config(None), LogRotationPolicy::default());
+    }
+
+    #[test]
+    fn parse_rotated_log_file_name_accepts_dated_and_sequenced_names() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 17).expect("valid date");
+
+        assert_eq!(parse_rotated_log_file_name("events-2026-10-17.jsonl"), Some((date, 0)));
+        assert_eq!(parse_rotated_log_file_name("events-2026-10-17.2.jsonl.gz"), Some((date, 2)));
+        assert_eq!(parse_rotated_log_file_name("events.jsonl"), None);
+        assert_eq!(parse_rotated_log_file_name("crash-1.json"), None);
+    }
+
+    #[test]
+    fn rotate_renames_oversized_log_with_date_suffix() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let log_file_path = make_temp_path(&temp_dir, "events.jsonl");
+        let policy = LogRotationPolicy {
+            max_file_bytes: 8,
+            ..LogRotationPolicy::default()
+        };
+        append_jsonl(&log_file_path, &json!({ "index": 1 })).expect("append should succeed");
+
+        let now = Local::now();
+        let rotated = rotate_log_file_if_needed(&log_file_path, &policy, now)
+            .expect("rotation should succeed")
+            .expect("file should be rotated");
+        let second = {
+            append_jsonl(&log_file_path, &json!({ "index": 2 })).expect("append should succeed");
+            rotate_log_file_if_needed(&log_file_path, &policy, now)
+                .expect("rotation should succeed")
+                .expect("file should be rotated")
+        };
+
+        let today = now.date_naive();
+        assert_eq!(rotated, make_temp_path(&temp_dir, &format!("events-{today}.jsonl")));
+        assert_eq!(second, make_temp_path(&temp_dir, &format!("events-{today}.1.jsonl")));
+        assert!(!log_file_path.exists());
+    }
+
+    #[test]
+    fn rotate_keeps_small_log_written_today() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let log_file_path = make_temp_path(&temp_dir, "events.jsonl");
+        append_jsonl(&log_file_path, &json!({ "index": 1 })).expect("append should succeed");
+
+        let rotated = rotate_log_file_if_needed(&log_file_path, &LogRotationPolicy::default(), Local::now())
+            .expect("rotation should succeed");
+
+        assert!(rotated.is_none());
+        assert!(log_file_path.exists());
+    }
+
+    #[test]
+    fn rotate_compresses_rotated_log_when_enabled() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let log_file_path = make_temp_path(&temp_dir, "events.jsonl");
+        let policy = LogRotationPolicy {
+            max_file_bytes: 1,
+            compress: true,
+            ..LogRotationPolicy::default()
+        };
+        append_jsonl(&log_file_path, &json!({ "index": 1 })).expect("append should succeed");
+
+        let rotated = rotate_log_file_if_needed(&log_file_path, &policy, Local::now())
+            .expect("rotation should succeed")
+            .expect("file should be rotated");
+
+        assert!(rotated.to_string_lossy().ends_with(".jsonl.gz"));
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(fs::File::open(&rotated).expect("open compressed log"))
+            .read_to_string(&mut decoded)
+            .expect("decompress log");
+        let parsed: serde_json::Value = serde_json::from_str(decoded.trim_end()).expect("line should be valid json");
+        assert_eq!(parsed["index"], 1);
+    }
+
+    #[test]
+    fn retention_removes_files_beyond_count_and_age() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        for name in [
+            "events-2026-09-01.jsonl",
+            "events-2026-10-14.jsonl.gz",
+            "events-2026-10-15.jsonl",
+            "events-2026-10-16.jsonl",
+            "events-2026-10-16.1.jsonl",
+            "events.jsonl",
+        ] {
+            fs::write(make_temp_path(&temp_dir, name), "{}\n").expect("write log file");
+        }
+        let policy = LogRotationPolicy {
+            retain_files: 3,
+            retain_days: Some(30),
+            ..LogRotationPolicy::default()
+        };
+        let now = Local
+            .with_ymd_and_hms(2026, 10, 17, 12, 0, 0)
+            .single()
+            .expect("valid local time");
+
+        enforce_log_retention(temp_dir.path(), &policy, now).expect("retention should succeed");
+
+        assert_eq!(
+            sorted_file_names(&temp_dir),
+            vec![
+                "events-2026-10-15.jsonl",
+                "events-2026-10-16.1.jsonl",
+                "events-2026-10-16.jsonl",
+                "events.jsonl",
+            ]
+        );
+    }
+
+    #[test]
+    fn log_filter_applies_min_level_and_env_override() {
+        let config = json!({ "logging": { "level": "warn" } });
+
+        let from_config = LogFilter::from_config(Some(&config), None);
+        assert!(!from_config.allows("info", "data.save.success"));
+        assert!(from_config.allows("error", "data.save.failure"));
+
+        let from_env = LogFilter::from_config(Some(&config), Some("debug"));
+        assert_eq!(from_env.min_level, LogLevel::Debug);
+        assert!(from_env.allows("info", "data.save.success"));
+        assert!(!from_env.allows("trace", "data.save.success"));
+    }
+
+    #[test]
+    fn log_filter_prefers_longest_matching_event_prefix() {
+        let filter = LogFilter::from_config(
+            Some(&json!({
+                "logging": {
+                    "level": "info",
+                    "filters": {
+                        "tray.exit.step.*": "off",
+                        "tray.exit": "warn",
+                        "data": "trace"
+                    }
+                }
+            })),
+            None,
+        );
+
+        assert!(!filter.allows("error", "tray.exit.step.window.failure"));
+        assert!(!filter.allows("info", "tray.exit.requested"));
+        assert!(filter.allows("error", "tray.exit.sequence.aborted"));
+        assert!(filter.allows("trace", "data.load.success"));
+        assert!(filter.allows("info", "database.opened"));
+        assert!(!filter.allows("debug", "database.opened"));
+    }
+
+    #[test]
+    fn reverse_line_reader_yields_lines_from_last_to_first() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let log_file_path = make_temp_path(&temp_dir, "events.jsonl");
+        let long_line = "x".repeat(100_000);
+        fs::write(&log_file_path, format!("first\n\n{long_line}\nlast\n")).expect("write log file");
+
+        let read_before = |end| {
+            ReverseLineReader::open(&log_file_path, end)
+                .expect("open reader")
+                .collect::<Result<Vec<_>, _>>()
+                .expect("read lines")
+        };
+
+        let last_start = 7 + long_line.len() as u64 + 1;
+        assert_eq!(
+            read_before(None),
+            vec![(last_start, "last".to_string()), (7, long_line.clone()), (0, "first".to_string())]
+        );
+        assert_eq!(read_before(Some(last_start)), vec![(7, long_line), (0, "first".to_string())]);
+    }
+
+    #[test]
+    fn query_logs_filters_and_paginates_across_rotated_files() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let rotated_path = make_temp_path(&temp_dir, "events-2026-10-16.jsonl");
+        let live_path = make_temp_path(&temp_dir, "events.jsonl");
+        for (path, timestamp, level, event) in [
+            (&rotated_path, 1, "info", "data.load.success"),
+            (&rotated_path, 2, "error", "data.save.failure"),
+            (&live_path, 3, "info", "tray.open_main"),
+            (&live_path, 4, "info", "data.save.success"),
+            (&live_path, 5, "info", "data.save.success"),
+        ] {
+            append_jsonl(path, &json!({ "timestamp": timestamp, "level": level, "event": event }))
+                .expect("append should succeed");
+        }
+
+        let first_page = query_logs(
+            temp_dir.path(),
+            &LogQuery {
+                event_prefix: Some("data.*".to_string()),
+                limit: Some(2),
+                ..LogQuery::default()
+            },
+        )
+        .expect("query should succeed");
+        let timestamps = |entries: &[serde_json::Value]| {
+            entries.iter().map(|entry| entry["timestamp"].as_u64().unwrap_or(0)).collect::<Vec<_>>()
+        };
+        assert_eq!(timestamps(&first_page.entries), vec![5, 4]);
+        assert!(first_page.next_cursor.is_some());
+
+        let second_page = query_logs(
+            temp_dir.path(),
+            &LogQuery {
+                event_prefix: Some("data".to_string()),
+                cursor: first_page.next_cursor,
+                limit: Some(2),
+                ..LogQuery::default()
+            },
+        )
+        .expect("query should succeed");
+        assert_eq!(timestamps(&second_page.entries), vec![2, 1]);
+        assert_eq!(second_page.next_cursor, None);
+
+        let errors_in_range = query_logs(
+            temp_dir.path(),
+            &LogQuery {
+                level: Some("warn".to_string()),
+                from: Some(2),
+                to: Some(4),
+                ..LogQuery::default()
+            },
+        )
+        .expect("query should succeed");
+        assert_eq!(timestamps(&errors_in_range.entries), vec![2]);
+    }
+
+    #[test]
+    fn query_log_pages_hold_while_the_log_grows_and_rotates() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let live_path = make_temp_path(&temp_dir, "events.jsonl");
+        let append = |timestamp: u64| {
+            append_jsonl(&live_path, &json!({ "timestamp": timestamp, "event": "data.save.success" }))
+                .expect("append should succeed");
+        };
+        (1..=5).for_each(append);
+        let page_after = |cursor| {
+            query_logs(
+                temp_dir.path(),
+                &LogQuery {
+                    cursor,
+                    limit: Some(2),
+                    ..LogQuery::default()
+                },
+            )
+            .expect("query should succeed")
+        };
+        let timestamps = |entries: &[serde_json::Value]| {
+            entries.iter().map(|entry| entry["timestamp"].as_u64().unwrap_or(0)).collect::<Vec<_>>()
+        };
+
+        let first_page = page_after(None);
+        assert_eq!(timestamps(&first_page.entries), vec![5, 4]);
+
+        append(6);
+        let second_page = page_after(first_page.next_cursor);
+        assert_eq!(timestamps(&second_page.entries), vec![3, 2]);
+
+        let policy = LogRotationPolicy {
+            max_file_bytes: 1,
+            compress: true,
+            ..LogRotationPolicy::default()
+        };
+        let rotated = rotate_log_file_if_needed(&live_path, &policy, Local::now())
+            .expect("rotation should succeed")
+            .expect("live log should rotate");
+        assert!(rotated.to_string_lossy().ends_with(".gz"));
+        append(7);
+        let third_page = page_after(second_page.next_cursor);
+        assert_eq!(timestamps(&third_page.entries), vec![1]);
+        assert_eq!(third_page.next_cursor, None);
+    }
+
+    #[test]
+    fn query_logs_survives_clock_steps_and_skips_files_older_than_from() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let from = Local::now().timestamp_millis() as u64;
+        let stale_path = make_temp_path(&temp_dir, "events-2020-01-01.jsonl");
+        append_jsonl(&stale_path, &json!({ "timestamp": from + 1, "event": "stale" })).expect("append");
+        let live_path = make_temp_path(&temp_dir, "events.jsonl");
+        for (timestamp, event) in [(from + 100, "before.step"), (from - 50, "stepped.back"), (from + 200, "after")] {
+            append_jsonl(&live_path, &json!({ "timestamp": timestamp, "event": event })).expect("append");
+        }
+
+        let page = query_logs(
+            temp_dir.path(),
+            &LogQuery {
+                from: Some(from),
+                ..LogQuery::default()
+            },
+        )
+        .expect("query should succeed");
+        let events = page.entries.iter().map(|entry| entry["event"].clone()).collect::<Vec<_>>();
+        assert_eq!(events, vec![json!("after"), json!("before.step")]);
+    }
+
+    #[test]
+    fn background_writer_appends_queued_entries_on_flush() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let logging_state = LoggingState::from_config(None);
+        logging_state.start_writer(temp_dir.path()).expect("writer should start");
+
+        for index in 0..3 {
+            assert!(logging_state.enqueue(&json!({ "index": index })));
+        }
+        logging_state.flush();
+
+        let content = fs::read_to_string(make_temp_path(&temp_dir, "events.jsonl")).expect("log file should exist");
+        let indexes = content
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("line should be valid")["index"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(indexes, vec![json!(0), json!(1), json!(2)]);
+    }
+
+    #[test]
+    fn background_writer_rotates_when_size_limit_is_reached() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let logging_state = LoggingState::from_config(Some(&json!({ "logging": { "maxFileBytes": 8 } })));
+        logging_state.start_writer(temp_dir.path()).expect("writer should start");
+
+        logging_state.enqueue(&json!({ "index": 1 }));
+        logging_state.flush();
+        logging_state.enqueue(&json!({ "index": 2 }));
+        logging_state.flush();
+
+        let today = Local::now().date_naive();
+        let rotated = fs::read_to_string(make_temp_path(&temp_dir, &format!("events-{today}.jsonl")))
+            .expect("rotated log should exist");
+        let live = fs::read_to_string(make_temp_path(&temp_dir, "events.jsonl")).expect("live log should exist");
+        assert!(rotated.contains("\"index\":1"));
+        assert!(live.contains("\"index\":2"));
+    }
+
+    #[test]
+    fn recent_entries_keep_only_the_latest_capacity() {
+        let logging_state = LoggingState::default();
+        for index in 0..RECENT_LOG_CAPACITY + 5 {
+            logging_state.remember(&json!({ "index": index }));
+        }
+
+        let recent = logging_state.recent_entries();
+        assert_eq!(recent.len(), RECENT_LOG_CAPACITY);
+        assert_eq!(recent[0]["index"], 5);
+        assert_eq!(recent[RECENT_LOG_CAPACITY - 1]["index"], RECENT_LOG_CAPACITY + 4);
+    }
+}
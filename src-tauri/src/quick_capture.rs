@@ -0,0 +1,259 @@
+use chrono::{Days, NaiveDate, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::{AppError, AppResult, ErrorCode};
+
+pub(crate) const QUICK_CAPTURE_LABEL: &str = "quick-capture";
+pub(crate) const EVENT_QUICK_CAPTURE_SAVED: &str = "quick-capture-saved";
+pub(crate) const EVENT_QUICK_CAPTURE_KIND: &str = "quick-capture-kind";
+const DATA_SCHEMA_VERSION: u64 = 2;
+
+/// What a capture line turns into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CaptureKind {
+    Task,
+    Spark,
+}
+
+impl CaptureKind {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Task => "task",
+            Self::Spark => "spark",
+        }
+    }
+}
+
+/// A capture line split into its title and markers: `#tag`, `@date` and `!global`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CaptureDraft {
+    pub title: String,
+    pub tags: Vec<String>,
+    pub date: Option<NaiveDate>,
+    pub global: Option<String>,
+}
+
+/// Broadcast after a capture is written to data.json so open windows can merge the new item.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedItem {
+    pub kind: CaptureKind,
+    pub item: Value,
+}
+
+/// Parses `#tag @2026-10-20 !global-name title`. Markers may appear anywhere; the remaining words form the title.
+/// `@today` and `@tomorrow` are accepted next to ISO dates.
+pub(crate) fn parse_capture(input: &str, today: NaiveDate) -> AppResult<CaptureDraft> {
+    let mut draft = CaptureDraft::default();
+    let mut words = Vec::new();
+
+    for token in input.split_whitespace() {
+        if let Some(tag) = token.strip_prefix('#').filter(|tag| !tag.is_empty()) {
+            if !draft.tags.iter().any(|existing| existing == tag) {
+                draft.tags.push(tag.to_string());
+            }
+        } else if let Some(date) = token.strip_prefix('@').filter(|date| !date.is_empty()) {
+            if draft.date.is_some() {
+                return Err(AppError::validation("a capture can only have one `@date`").with_context("token", token));
+            }
+            draft.date = Some(parse_capture_date(date, today).ok_or_else(|| {
+                AppError::validation(format!("`{token}` is not a date; use @YYYY-MM-DD, @today or @tomorrow"))
+                    .with_context("token", token)
+            })?);
+        } else if let Some(global) = token.strip_prefix('!').filter(|global| !global.is_empty()) {
+            if draft.global.is_some() {
+                return Err(AppError::validation("a capture can only link one `!global`").with_context("token", token));
+            }
+            draft.global = Some(global.to_string());
+        } else {
+            words.push(token);
+        }
+    }
+
+    draft.title = words.join(" ");
+    if draft.title.is_empty() {
+        return Err(AppError::validation("the capture needs a title besides its markers"));
+    }
+    Ok(draft)
+}
+
+fn parse_capture_date(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    match value.to_ascii_lowercase().as_str() {
+        "today" => Some(today),
+        "tomorrow" => today.checked_add_days(Days::new(1)),
+        _ => NaiveDate::parse_from_str(value, "%Y-%m-%d").ok(),
+    }
+}
+
+/// Compares global titles loosely so `!weekly-review` finds "Weekly Review".
+fn normalize_global_name(name: &str) -> String {
+    name.chars()
+        .filter(|character| !character.is_whitespace() && !matches!(character, '-' | '_'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Resolves `!name` to a global id: an exact (normalized) title match wins, otherwise a unique prefix match.
+fn resolve_global_id(globals: &[Value], name: &str) -> AppResult<String> {
+    let wanted = normalize_global_name(name);
+    let candidates = globals
+        .iter()
+        .filter_map(|global| Some((global.get("id")?.as_str()?, global.get("title")?.as_str()?)))
+        .map(|(id, title)| (id, title, normalize_global_name(title)))
+        .collect::<Vec<_>>();
+
+    if let Some((id, _, _)) = candidates.iter().find(|(_, _, normalized)| *normalized == wanted) {
+        return Ok(id.to_string());
+    }
+    let prefixed = candidates
+        .iter()
+        .filter(|(_, _, normalized)| normalized.starts_with(&wanted))
+        .collect::<Vec<_>>();
+    match prefixed.as_slice() {
+        [(id, _, _)] => Ok(id.to_string()),
+        [] => Err(AppError::not_found(format!("no global matches `!{name}`")).with_context("global", name)),
+        matches => Err(AppError::new(
+            ErrorCode::Conflict,
+            format!("`!{name}` matches several globals; type more of the name"),
+        )
+        .with_context("global", name)
+        .with_context("matches", matches.iter().map(|(_, title, _)| title.to_string()).collect::<Vec<_>>())),
+    }
+}
+
+fn empty_app_data() -> Value {
+    serde_json::json!({
+        "schemaVersion": DATA_SCHEMA_VERSION,
+        "tasks": [],
+        "globals": [],
+        "taskLogs": [],
+        "sparks": [],
+    })
+}
+
+/// Builds the task or spark described by `draft` and prepends it to `data`, the way the main window adds items.
+/// Markers that do not apply to `kind` are rejected rather than dropped, so nothing typed is silently lost.
+pub(crate) fn apply_capture(
+    data: Option<Value>,
+    kind: CaptureKind,
+    draft: &CaptureDraft,
+    today: NaiveDate,
+) -> AppResult<(Value, Value)> {
+    let mut data = data.unwrap_or_else(empty_app_data);
+    if data.get("schemaVersion").and_then(Value::as_u64) != Some(DATA_SCHEMA_VERSION) {
+        return Err(AppError::validation("data.json has an unsupported schema; open the main window to migrate it"));
+    }
+
+    let now = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+    let mut item = Map::new();
+    item.insert("id".into(), Value::String(uuid::Uuid::new_v4().to_string()));
+    item.insert("title".into(), Value::String(draft.title.clone()));
+
+    let collection = match kind {
+        CaptureKind::Task => {
+            if draft.global.is_some() {
+                return Err(AppError::validation("tasks link to a global through a spark; drop the `!global`"));
+            }
+            let date = draft.date.unwrap_or(today);
+            item.insert("executionDate".into(), Value::String(date.format("%Y-%m-%d").to_string()));
+            item.insert("status".into(), Value::String("active".into()));
+            if !draft.tags.is_empty() {
+                item.insert("tags".into(), serde_json::json!(draft.tags));
+            }
+            "tasks"
+        }
+        CaptureKind::Spark => {
+            if !draft.tags.is_empty() || draft.date.is_some() {
+                return Err(AppError::validation("sparks have no tags or dates; capture a task instead"));
+            }
+            if let Some(global) = &draft.global {
+                let globals = data.get("globals").and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+                item.insert("globalIds".into(), serde_json::json!([resolve_global_id(globals, global)?]));
+            }
+            "sparks"
+        }
+    };
+    item.insert("createdAt".into(), Value::String(now.clone()));
+    item.insert("updatedAt".into(), Value::String(now));
+
+    let item = Value::Object(item);
+    let Some(object) = data.as_object_mut() else {
+        return Err(AppError::validation("data.json is not an object"));
+    };
+    let entries = object.entry(collection).or_insert_with(|| Value::Array(Vec::new()));
+    let Some(entries) = entries.as_array_mut() else {
+        return Err(AppError::validation(format!("`{collection}` in data.json is not a list")));
+    };
+    entries.insert(0, item.clone());
+    Ok((data, item))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").expect("test date should parse")
+    }
+
+    #[test]
+    fn parses_markers_anywhere_in_the_line() {
+        let today = date("2026-10-18");
+        let draft = parse_capture("call #home  the plumber @tomorrow #urgent #home", today).expect("should parse");
+
+        assert_eq!(
+            draft,
+            CaptureDraft {
+                title: "call the plumber".to_string(),
+                tags: vec!["home".to_string(), "urgent".to_string()],
+                date: Some(date("2026-10-19")),
+                global: None,
+            }
+        );
+        let linked = parse_capture("!side-project idea", today).expect("should parse");
+        assert_eq!(linked.global.as_deref(), Some("side-project"));
+        assert!(parse_capture("#only @today", today).is_err());
+        assert!(parse_capture("title @2026-13-01", today).is_err());
+        assert!(parse_capture("title !one !two", today).is_err());
+    }
+
+    #[test]
+    fn captures_are_prepended_with_a_resolved_global() {
+        let today = date("2026-10-18");
+        let data = serde_json::json!({
+            "schemaVersion": 2,
+            "tasks": [{ "id": "t1" }],
+            "globals": [
+                { "id": "g1", "title": "Side Project" },
+                { "id": "g2", "title": "Side Quest" },
+                { "id": "g3", "title": "Weekly review" },
+            ],
+            "taskLogs": [],
+            "sparks": [],
+        });
+
+        let task = parse_capture("#home fix sink", today).expect("should parse");
+        let (next, item) = apply_capture(Some(data.clone()), CaptureKind::Task, &task, today).expect("task applies");
+        assert_eq!(next["tasks"][0], item);
+        assert_eq!(next["tasks"][1]["id"], "t1");
+        assert_eq!(item["executionDate"], "2026-10-18");
+        assert_eq!(item["tags"], serde_json::json!(["home"]));
+
+        let spark = parse_capture("!weekly-review retro format", today).expect("should parse");
+        let (next, item) = apply_capture(Some(data.clone()), CaptureKind::Spark, &spark, today).expect("spark applies");
+        assert_eq!(next["sparks"][0], item);
+        assert_eq!(item["globalIds"], serde_json::json!(["g3"]));
+
+        let ambiguous = parse_capture("!side idea", today).expect("should parse");
+        let error = apply_capture(Some(data.clone()), CaptureKind::Spark, &ambiguous, today).expect_err("ambiguous");
+        assert_eq!(error.code(), ErrorCode::Conflict);
+        let tagged_spark = parse_capture("#home idea", today).expect("should parse");
+        assert!(apply_capture(Some(data), CaptureKind::Spark, &tagged_spark, today).is_err());
+
+        let (fresh, _) = apply_capture(None, CaptureKind::Task, &task, today).expect("empty data should apply");
+        assert_eq!(fresh["schemaVersion"], 2);
+        assert_eq!(fresh["tasks"].as_array().map(Vec::len), Some(1));
+    }
+}
@@ -0,0 +1,209 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+/// Actions that can be bound to a global shortcut in the `shortcuts` map of config.json.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ShortcutAction {
+    ToggleWidgetLock,
+    ToggleWidgetVisibility,
+    OpenMainWindow,
+    QuickAddTask,
+    QuickCaptureSpark,
+    PeekWidgets,
+}
+
+impl ShortcutAction {
+    pub(crate) fn parse(action: &str) -> Option<Self> {
+        serde_json::from_value(serde_json::Value::String(action.to_string())).ok()
+    }
+}
+
+/// Outcome of binding one configured shortcut.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum ShortcutBindingState {
+    Registered,
+    UnknownAction,
+    #[serde(rename_all = "camelCase")]
+    InvalidAccelerator {
+        message: String,
+    },
+    /// Another configured action already uses the same key combination.
+    #[serde(rename_all = "camelCase")]
+    Conflict {
+        with_action: String,
+    },
+    /// The combination is valid but the OS refused it, usually because another application holds it.
+    #[serde(rename_all = "camelCase")]
+    Unavailable {
+        message: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutStatus {
+    pub action: String,
+    pub accelerator: String,
+    #[serde(flatten)]
+    pub state: ShortcutBindingState,
+}
+
+/// Shortcuts currently registered with the OS, by hotkey id, and the status of the last registration pass.
+#[derive(Default)]
+pub(crate) struct ShortcutRegistry {
+    bindings: Mutex<HashMap<u32, ShortcutAction>>,
+    statuses: Mutex<Vec<ShortcutStatus>>,
+}
+
+impl ShortcutRegistry {
+    pub(crate) fn action_for(&self, shortcut: &Shortcut) -> Option<ShortcutAction> {
+        self.bindings
+            .lock()
+            .ok()
+            .and_then(|bindings| bindings.get(&shortcut.id()).copied())
+    }
+
+    pub(crate) fn statuses(&self) -> Vec<ShortcutStatus> {
+        self.statuses.lock().map(|statuses| statuses.clone()).unwrap_or_default()
+    }
+}
+
+type PlannedBinding = (usize, ShortcutAction, Shortcut);
+
+/// Resolves the configured map into bindable shortcuts. Entries that cannot be bound get their final status here;
+/// the rest are returned with their index in the status list so registration can fill it in.
+fn plan_bindings(shortcuts: &BTreeMap<String, String>) -> (Vec<PlannedBinding>, Vec<ShortcutStatus>) {
+    let mut bindable = Vec::new();
+    let mut statuses = Vec::new();
+    let mut claimed: HashMap<u32, String> = HashMap::new();
+
+    for (action, accelerator) in shortcuts {
+        let state = match (ShortcutAction::parse(action), accelerator.parse::<Shortcut>()) {
+            (None, _) => ShortcutBindingState::UnknownAction,
+            (Some(_), Err(error)) => ShortcutBindingState::InvalidAccelerator {
+                message: error.to_string(),
+            },
+            (Some(_), Ok(shortcut)) if claimed.contains_key(&shortcut.id()) => ShortcutBindingState::Conflict {
+                with_action: claimed[&shortcut.id()].clone(),
+            },
+            (Some(parsed), Ok(shortcut)) => {
+                claimed.insert(shortcut.id(), action.clone());
+                bindable.push((statuses.len(), parsed, shortcut));
+                ShortcutBindingState::Registered
+            }
+        };
+        statuses.push(ShortcutStatus {
+            action: action.clone(),
+            accelerator: accelerator.clone(),
+            state,
+        });
+    }
+
+    (bindable, statuses)
+}
+
+/// Replaces every registered global shortcut with the configured ones. Each shortcut is registered on its own, so
+/// one bad or taken accelerator never keeps the others from working.
+pub(crate) fn register_shortcuts<R: Runtime>(
+    app: &AppHandle<R>,
+    shortcuts: &BTreeMap<String, String>,
+) -> Vec<ShortcutStatus> {
+    let (bindable, mut statuses) = plan_bindings(shortcuts);
+    let global_shortcut = app.global_shortcut();
+    let _ = global_shortcut.unregister_all();
+
+    let mut bindings = HashMap::new();
+    for (index, action, shortcut) in bindable {
+        match global_shortcut.register(shortcut) {
+            Ok(()) => {
+                bindings.insert(shortcut.id(), action);
+            }
+            Err(error) => {
+                statuses[index].state = ShortcutBindingState::Unavailable {
+                    message: error.to_string(),
+                };
+            }
+        }
+    }
+
+    if let Some(registry) = app.try_state::<ShortcutRegistry>() {
+        if let Ok(mut current) = registry.bindings.lock() {
+            *current = bindings;
+        }
+        if let Ok(mut current) = registry.statuses.lock() {
+            current.clone_from(&statuses);
+        }
+    }
+    statuses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shortcut_map(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(action, accelerator)| (action.to_string(), accelerator.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn plan_reports_unknown_invalid_and_conflicting_shortcuts() {
+        let (bindable, statuses) = plan_bindings(&shortcut_map(&[
+            ("openMainWindow", "ctrl+shift+m"),
+            ("peekWidgets", "not a shortcut"),
+            ("toggleWidgetLock", "ctrl+shift+l"),
+            ("toggleWidgetVisibility", "Shift+Control+L"),
+            ("launchRockets", "ctrl+shift+r"),
+        ]));
+
+        let states = statuses
+            .iter()
+            .map(|status| (status.action.as_str(), &status.state))
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(states["openMainWindow"], &ShortcutBindingState::Registered);
+        assert!(matches!(states["peekWidgets"], ShortcutBindingState::InvalidAccelerator { .. }));
+        assert_eq!(states["launchRockets"], &ShortcutBindingState::UnknownAction);
+        assert_eq!(states["toggleWidgetLock"], &ShortcutBindingState::Registered);
+        assert_eq!(
+            states["toggleWidgetVisibility"],
+            &ShortcutBindingState::Conflict {
+                with_action: "toggleWidgetLock".to_string()
+            }
+        );
+        assert_eq!(
+            bindable.iter().map(|(_, action, _)| *action).collect::<Vec<_>>(),
+            vec![ShortcutAction::OpenMainWindow, ShortcutAction::ToggleWidgetLock]
+        );
+    }
+
+    #[test]
+    fn statuses_serialize_with_a_flat_status_tag() {
+        let status = ShortcutStatus {
+            action: "toggleWidgetVisibility".to_string(),
+            accelerator: "ctrl+shift+l".to_string(),
+            state: ShortcutBindingState::Conflict {
+                with_action: "toggleWidgetLock".to_string(),
+            },
+        };
+
+        assert_eq!(
+            serde_json::to_value(status).expect("status should serialize"),
+            serde_json::json!({
+                "action": "toggleWidgetVisibility",
+                "accelerator": "ctrl+shift+l",
+                "status": "conflict",
+                "withAction": "toggleWidgetLock",
+            })
+        );
+    }
+}
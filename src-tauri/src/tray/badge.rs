@@ -0,0 +1,198 @@
+use std::sync::Mutex;
+
+use tauri::{image::Image, AppHandle, Manager, Runtime};
+
+use crate::{
+    app_data::TaskCounts,
+    config::Language,
+    i18n::{format_text, Message},
+};
+
+const TRAY_TOOLTIP_TITLE: &str = "daily";
+/// Badge diameter relative to the shorter icon side.
+const BADGE_DIAMETER_RATIO: f64 = 0.6;
+const BADGE_NORMAL_COLOR: [u8; 4] = [37, 99, 235, 255];
+const BADGE_ALERT_COLOR: [u8; 4] = [220, 38, 38, 255];
+const BADGE_TEXT_COLOR: [u8; 4] = [255, 255, 255, 255];
+const GLYPH_WIDTH: u32 = 3;
+const GLYPH_HEIGHT: u32 = 5;
+
+/// 3x5 bitmaps for the badge text, one row per entry with the leftmost pixel in the highest bit.
+const DIGIT_GLYPHS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+const PLUS_GLYPH: [u8; 5] = [0b000, 0b010, 0b111, 0b010, 0b000];
+
+/// Counts the tray icon was last rendered with, so unrelated refreshes do not re-upload the icon.
+#[derive(Default)]
+pub(crate) struct TrayBadgeState {
+    rendered: Mutex<Option<TaskCounts>>,
+}
+
+/// "daily — 3 active today · 1 exception · 2 overdue"; exceptions and overdue tasks are only listed when present.
+pub(crate) fn tray_tooltip(counts: TaskCounts, language: Language) -> String {
+    let mut parts = vec![format_text(
+        language,
+        Message::TrayTooltipActiveToday,
+        &[("count", &counts.active_today)],
+    )];
+    if counts.exceptions > 0 {
+        let message = if counts.exceptions == 1 {
+            Message::TrayTooltipException
+        } else {
+            Message::TrayTooltipExceptions
+        };
+        parts.push(format_text(language, message, &[("count", &counts.exceptions)]));
+    }
+    if counts.overdue > 0 {
+        parts.push(format_text(language, Message::TrayTooltipOverdue, &[("count", &counts.overdue)]));
+    }
+    format!("{TRAY_TOOLTIP_TITLE} — {}", parts.join(" · "))
+}
+
+/// Number in the badge: everything that should be done by now. Overdue or failing tasks turn the badge red.
+fn badge_content(counts: TaskCounts) -> Option<(usize, bool)> {
+    let due = counts.active_today + counts.overdue;
+    let alert = counts.overdue > 0 || counts.exceptions > 0;
+    (due > 0 || alert).then_some((due, alert))
+}
+
+fn badge_glyphs(count: usize) -> Vec<[u8; 5]> {
+    if count > 9 {
+        vec![DIGIT_GLYPHS[9], PLUS_GLYPH]
+    } else {
+        vec![DIGIT_GLYPHS[count]]
+    }
+}
+
+fn put_pixel(rgba: &mut [u8], width: u32, x: u32, y: u32, color: [u8; 4]) {
+    let offset = ((y * width + x) * 4) as usize;
+    if let Some(pixel) = rgba.get_mut(offset..offset + 4) {
+        pixel.copy_from_slice(&color);
+    }
+}
+
+/// Draws a filled badge with `count` in the top-right corner of an RGBA icon. Returns `None` for a malformed
+/// buffer so the caller can keep the plain icon.
+pub(crate) fn render_badge(rgba: &[u8], width: u32, height: u32, count: usize, alert: bool) -> Option<Vec<u8>> {
+    if width == 0 || height == 0 || rgba.len() != (width * height * 4) as usize {
+        return None;
+    }
+
+    let mut output = rgba.to_vec();
+    let diameter = (f64::from(width.min(height)) * BADGE_DIAMETER_RATIO).round().max(GLYPH_HEIGHT as f64 + 2.0);
+    let radius = diameter / 2.0;
+    let (center_x, center_y) = (f64::from(width) - radius, radius);
+    let fill = if alert { BADGE_ALERT_COLOR } else { BADGE_NORMAL_COLOR };
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy) = (f64::from(x) + 0.5 - center_x, f64::from(y) + 0.5 - center_y);
+            if dx * dx + dy * dy <= radius * radius {
+                put_pixel(&mut output, width, x, y, fill);
+            }
+        }
+    }
+
+    let glyphs = badge_glyphs(count);
+    let columns = glyphs.len() as u32 * (GLYPH_WIDTH + 1) - 1;
+    let scale = ((diameter * 0.6) / f64::from(GLYPH_HEIGHT.max(columns))).floor().max(1.0) as u32;
+    let origin_x = (center_x - f64::from(columns * scale) / 2.0).round().max(0.0) as u32;
+    let origin_y = (center_y - f64::from(GLYPH_HEIGHT * scale) / 2.0).round().max(0.0) as u32;
+    for (index, glyph) in glyphs.iter().enumerate() {
+        let glyph_x = origin_x + index as u32 * (GLYPH_WIDTH + 1) * scale;
+        for (row, bits) in glyph.iter().enumerate() {
+            for column in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - column)) == 0 {
+                    continue;
+                }
+                for step_y in 0..scale {
+                    for step_x in 0..scale {
+                        let x = glyph_x + column * scale + step_x;
+                        let y = origin_y + row as u32 * scale + step_y;
+                        if x < width && y < height {
+                            put_pixel(&mut output, width, x, y, BADGE_TEXT_COLOR);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Some(output)
+}
+
+/// Updates the tray tooltip and redraws the badge on the default icon when the counts changed.
+pub(crate) fn apply_tray_badge<R: Runtime>(
+    app: &AppHandle<R>,
+    tray: &tauri::tray::TrayIcon<R>,
+    counts: TaskCounts,
+    language: Language,
+) {
+    let _ = tray.set_tooltip(Some(tray_tooltip(counts, language)));
+
+    let Some(state) = app.try_state::<TrayBadgeState>() else {
+        return;
+    };
+    let Ok(mut rendered) = state.rendered.lock() else {
+        return;
+    };
+    if *rendered == Some(counts) {
+        return;
+    }
+    let Some(default_icon) = app.default_window_icon() else {
+        return;
+    };
+
+    let (width, height) = (default_icon.width(), default_icon.height());
+    let badged = badge_content(counts)
+        .and_then(|(count, alert)| render_badge(default_icon.rgba(), width, height, count, alert));
+    let icon = match &badged {
+        Some(rgba) => Image::new(rgba, width, height),
+        None => Image::new(default_icon.rgba(), width, height),
+    };
+    if tray.set_icon(Some(icon)).is_ok() {
+        *rendered = Some(counts);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tooltip_lists_only_present_problems() {
+        assert_eq!(tray_tooltip(TaskCounts::default(), Language::En), "daily — 0 active today");
+        let counts = TaskCounts {
+            active_today: 3,
+            exceptions: 1,
+            overdue: 2,
+        };
+        assert_eq!(tray_tooltip(counts, Language::En), "daily — 3 active today · 1 exception · 2 overdue");
+        assert_eq!(tray_tooltip(counts, Language::ZhCn), "daily — 今日进行中 3 项 · 异常 1 项 · 逾期 2 项");
+    }
+
+    #[test]
+    fn badge_is_drawn_in_the_top_right_corner() {
+        let (width, height) = (32, 32);
+        let icon = vec![0u8; (width * height * 4) as usize];
+        let badged = render_badge(&icon, width, height, 12, true).expect("buffer is well formed");
+        let pixel = |x: u32, y: u32| {
+            let offset = ((y * width + x) * 4) as usize;
+            [badged[offset], badged[offset + 1], badged[offset + 2], badged[offset + 3]]
+        };
+
+        assert_eq!(pixel(0, height - 1), [0, 0, 0, 0]);
+        assert_eq!(pixel(width - 4, 6), BADGE_ALERT_COLOR);
+        assert!((0..width).any(|x| (0..height).any(|y| pixel(x, y) == BADGE_TEXT_COLOR)));
+        assert_eq!(badge_content(TaskCounts::default()), None);
+        assert!(render_badge(&icon[1..], width, height, 1, false).is_none());
+    }
+}
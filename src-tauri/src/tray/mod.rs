@@ -0,0 +1,274 @@
+mod badge;
+mod model;
+
+use tauri::{
+    menu::{CheckMenuItem, IsMenuItem, Menu, MenuItem, MenuItemKind, PredefinedMenuItem, Submenu},
+    tray::TrayIconBuilder,
+    AppHandle, Emitter, Runtime,
+};
+
+use crate::{
+    app_data,
+    error::{AppError, AppResult},
+    i18n,
+    logging::log_project_event,
+    quick_capture::CaptureKind,
+    widget_interaction::WIDGET_DEFAULT_PEEK,
+    window,
+};
+
+pub(crate) use badge::TrayBadgeState;
+use model::{TrayCommand, TrayEntryKind, TrayMenuNode, TrayMenuState};
+
+pub(crate) const TRAY_ID: &str = "daily-tray";
+const EVENT_TASK_STATUS_UPDATED: &str = "task-status-updated";
+const EVENT_TRAY_NAVIGATE: &str = "tray-navigate";
+
+fn tray_menu_state<R: Runtime>(app: &AppHandle<R>) -> TrayMenuState {
+    let config = crate::current_config(app);
+    let summary = app_data::app_data_snapshot(app)
+        .map(|data| app_data::summarize_for_tray(&data, chrono::Utc::now().date_naive()))
+        .unwrap_or_default();
+    TrayMenuState {
+        language: i18n::resolve_language(config.language),
+        widgets_locked: crate::all_widgets_locked(&config),
+        widgets_visible: crate::any_widget_visible(&config),
+        peek_duration: WIDGET_DEFAULT_PEEK,
+        summary,
+    }
+}
+
+fn tray_task_counts<R: Runtime>(app: &AppHandle<R>) -> app_data::TaskCounts {
+    app_data::app_data_snapshot(app)
+        .map(|data| app_data::count_tasks(&data, chrono::Utc::now().date_naive()))
+        .unwrap_or_default()
+}
+
+fn build_menu_node<R: Runtime>(app: &AppHandle<R>, node: &TrayMenuNode) -> tauri::Result<MenuItemKind<R>> {
+    let item = match node {
+        TrayMenuNode::Action { action, text, enabled } => {
+            MenuItemKind::MenuItem(MenuItem::with_id(app, action.id, text, *enabled, None::<&str>)?)
+        }
+        TrayMenuNode::Entry {
+            id,
+            text,
+            checkable: true,
+        } => MenuItemKind::Check(CheckMenuItem::with_id(app, id, text, true, false, None::<&str>)?),
+        TrayMenuNode::Entry { id, text, .. } => {
+            MenuItemKind::MenuItem(MenuItem::with_id(app, id, text, true, None::<&str>)?)
+        }
+        TrayMenuNode::Placeholder(text) => MenuItemKind::MenuItem(MenuItem::new(app, text, false, None::<&str>)?),
+        TrayMenuNode::Submenu { text, children } => {
+            let items = build_menu_nodes(app, children)?;
+            let item_refs = items.iter().map(|item| item as &dyn IsMenuItem<R>).collect::<Vec<_>>();
+            MenuItemKind::Submenu(Submenu::with_items(app, text, true, &item_refs)?)
+        }
+        TrayMenuNode::Separator => MenuItemKind::Predefined(PredefinedMenuItem::separator(app)?),
+    };
+    Ok(item)
+}
+
+fn build_menu_nodes<R: Runtime>(app: &AppHandle<R>, nodes: &[TrayMenuNode]) -> tauri::Result<Vec<MenuItemKind<R>>> {
+    nodes.iter().map(|node| build_menu_node(app, node)).collect()
+}
+
+/// Renders the declarative menu model into a native menu.
+fn build_tray_menu<R: Runtime>(app: &AppHandle<R>, state: &TrayMenuState) -> tauri::Result<Menu<R>> {
+    let items = build_menu_nodes(app, &model::tray_menu(state))?;
+    let item_refs = items.iter().map(|item| item as &dyn IsMenuItem<R>).collect::<Vec<_>>();
+    Menu::with_items(app, &item_refs)
+}
+
+/// Creates the tray icon with the current menu, tooltip and badge.
+pub(crate) fn create_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    let state = tray_menu_state(app);
+    let tray_menu = build_tray_menu(app, &state)?;
+    let mut tray_builder = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&tray_menu)
+        .show_menu_on_left_click(false)
+        .tooltip("daily");
+    if let Some(default_icon) = app.default_window_icon().cloned() {
+        tray_builder = tray_builder.icon(default_icon);
+    }
+
+    let tray = tray_builder.build(app)?;
+    badge::apply_tray_badge(app, &tray, tray_task_counts(app), state.language);
+    Ok(())
+}
+
+/// Rebuilds the menu and badge from the current config and data snapshot.
+pub(crate) fn refresh_tray_menu<R: Runtime>(app: &AppHandle<R>) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+
+    let state = tray_menu_state(app);
+    if let Ok(tray_menu) = build_tray_menu(app, &state) {
+        let _ = tray.set_menu(Some(tray_menu));
+    }
+    badge::apply_tray_badge(app, &tray, tray_task_counts(app), state.language);
+}
+
+/// Entry point for every tray menu click.
+pub(crate) fn handle_tray_menu_event(app: &AppHandle, id: &str) {
+    match model::parse_menu_id(id) {
+        Some(TrayCommand::Action(action)) => (action.run)(app),
+        Some(TrayCommand::Entry(kind, item_id)) => run_tray_entry(app, id, kind, item_id),
+        None => {}
+    }
+}
+
+fn open_quick_capture(app: &AppHandle) {
+    if let Err(error) = window::open_quick_capture_window(app, CaptureKind::Spark) {
+        log_project_event(
+            app,
+            "warn",
+            "tray.new_spark.failure",
+            "Tray menu failed to open the quick capture window.",
+            Some(serde_json::json!({ "error": error })),
+        );
+    }
+}
+
+fn open_main_window(app: &AppHandle) {
+    if let Err(error) = window::focus_main_window(app) {
+        log_project_event(
+            app,
+            "warn",
+            "tray.open_main.failure",
+            "Tray menu failed to open the main window.",
+            Some(serde_json::json!({ "error": error })),
+        );
+    }
+    log_project_event(app, "info", "tray.open_main", "Tray menu requested opening main window.", None);
+}
+
+fn toggle_widget_visibility(app: &AppHandle) {
+    crate::toggle_widget_visibility(app);
+    log_project_event(
+        app,
+        "info",
+        "tray.toggle_widget_visibility",
+        "Tray menu requested toggling widget visibility.",
+        None,
+    );
+}
+
+fn toggle_widget_lock(app: &AppHandle) {
+    crate::toggle_widget_lock(app);
+    log_project_event(
+        app,
+        "info",
+        "tray.toggle_widget_lock",
+        "Tray menu requested toggling widget lock.",
+        None,
+    );
+}
+
+fn peek_widgets(app: &AppHandle) {
+    let labels = crate::current_config(app).widget_labels();
+    if let Err(error) = crate::peek_widgets(app, &labels, WIDGET_DEFAULT_PEEK) {
+        log_project_event(
+            app,
+            "warn",
+            "tray.peek_widgets.failure",
+            "Tray menu failed to peek widgets.",
+            Some(serde_json::json!({ "error": error })),
+        );
+    }
+}
+
+fn run_tray_entry(app: &AppHandle, id: &str, kind: TrayEntryKind, item_id: &str) {
+    let result = match kind {
+        TrayEntryKind::Task => complete_task_from_tray(app, item_id),
+        TrayEntryKind::Global => navigate_main_window(app, "global", item_id),
+        TrayEntryKind::Spark => navigate_main_window(app, "spark", item_id),
+    };
+
+    if let Err(error) = result {
+        log_project_event(
+            app,
+            "warn",
+            "tray.data_item.failure",
+            "Tray menu item could not be applied.",
+            Some(serde_json::json!({ "item": id, "error": error })),
+        );
+        // A failed check must not leave the item looking done.
+        refresh_tray_menu(app);
+    }
+}
+
+/// Payload of `tray-navigate`: the main window opens `section` and selects `id` when it can.
+#[derive(Clone, serde::Serialize)]
+struct TrayNavigatePayload<'a> {
+    section: &'static str,
+    id: &'a str,
+}
+
+fn navigate_main_window(app: &AppHandle, section: &'static str, id: &str) -> AppResult<()> {
+    window::focus_main_window(app)?;
+    let _ = app.emit_to("main", EVENT_TRAY_NAVIGATE, TrayNavigatePayload { section, id });
+    Ok(())
+}
+
+/// Completes a task straight in data.json, then tells open windows the same way a window toggling it would.
+fn complete_task_from_tray(app: &AppHandle, task_id: &str) -> AppResult<()> {
+    let app_data_path = crate::resolve_app_data_file_path(app)?;
+    let Some(mut data) = crate::read_app_data_file(&app_data_path)? else {
+        return Err(AppError::not_found("data.json does not exist yet"));
+    };
+    let updated_at = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    if app_data::complete_task(&mut data, task_id, &updated_at)? {
+        crate::write_app_data_file(&app_data_path, &data)?;
+        let _ = app.emit(
+            EVENT_TASK_STATUS_UPDATED,
+            serde_json::json!({ "taskId": task_id, "status": "completed", "updatedAt": updated_at }),
+        );
+        log_project_event(
+            app,
+            "info",
+            "tray.task.completed",
+            "Task completed from the tray menu.",
+            Some(serde_json::json!({ "task": task_id })),
+        );
+    }
+    app_data::remember_app_data(app, &data);
+    refresh_tray_menu(app);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Language;
+
+    #[test]
+    fn menu_model_renders_with_the_mock_runtime() {
+        let app = tauri::test::mock_app();
+        let state = model::tests::sample_state(Language::ZhCn);
+        let menu = build_tray_menu(app.handle(), &state).expect("menu should build");
+        let items = menu.items().expect("menu items should be readable");
+
+        assert_eq!(items.len(), model::tray_menu(&state).len());
+        let MenuItemKind::Submenu(today) = &items[0] else {
+            panic!("today's tasks should render as a submenu");
+        };
+        assert_eq!(today.text().expect("submenu text"), "今日任务（1）");
+        let today_items = today.items().expect("submenu items should be readable");
+        let MenuItemKind::Check(task) = &today_items[0] else {
+            panic!("tasks should render as check items");
+        };
+        assert_eq!(task.id().as_ref(), "tray-task:t1");
+
+        let peek = menu
+            .get("tray-peek-widgets")
+            .and_then(|item| item.as_menuitem().cloned())
+            .expect("peek item should exist");
+        assert!(!peek.is_enabled().expect("enabled state"));
+        let exit = menu
+            .get("tray-exit")
+            .and_then(|item| item.as_menuitem().cloned())
+            .expect("exit item should exist");
+        assert_eq!(exit.text().expect("item text"), "退出应用");
+    }
+}
@@ -0,0 +1,351 @@
+use std::{fmt, time::Duration};
+
+use tauri::AppHandle;
+
+use crate::{
+    app_data::{TrayDataSummary, TrayEntry},
+    config::Language,
+    i18n::{format_text, text, Message},
+};
+
+const TASK_ID_PREFIX: &str = "tray-task:";
+const GLOBAL_ID_PREFIX: &str = "tray-global:";
+const SPARK_ID_PREFIX: &str = "tray-spark:";
+const TITLE_MAX_CHARS: usize = 40;
+
+/// A fixed tray command. A new action is one row in `ALL` plus its catalog message in `i18n`.
+#[derive(Clone, Copy)]
+pub(crate) struct TrayAction {
+    pub id: &'static str,
+    /// Capture actions sit with the data submenus, the rest below the separator.
+    capture: bool,
+    message: fn(&TrayMenuState) -> Message,
+    enabled_when: fn(&TrayMenuState) -> bool,
+    pub run: fn(&AppHandle),
+}
+
+impl TrayAction {
+    /// Every action in menu order.
+    pub(crate) const ALL: [Self; 6] = [
+        Self {
+            id: "tray-new-spark",
+            capture: true,
+            message: |_| Message::TrayNewSpark,
+            enabled_when: |_| true,
+            run: super::open_quick_capture,
+        },
+        Self {
+            id: "tray-open-main",
+            capture: false,
+            message: |_| Message::TrayOpenMainWindow,
+            enabled_when: |_| true,
+            run: super::open_main_window,
+        },
+        Self {
+            id: "tray-toggle-widget-visibility",
+            capture: false,
+            message: |state| {
+                if state.widgets_visible {
+                    Message::TrayHideWidget
+                } else {
+                    Message::TrayShowWidget
+                }
+            },
+            enabled_when: |_| true,
+            run: super::toggle_widget_visibility,
+        },
+        Self {
+            id: "tray-toggle-widget-lock",
+            capture: false,
+            message: |state| {
+                if state.widgets_locked {
+                    Message::TrayUnlockWidget
+                } else {
+                    Message::TrayLockWidget
+                }
+            },
+            enabled_when: |_| true,
+            run: super::toggle_widget_lock,
+        },
+        Self {
+            id: "tray-peek-widgets",
+            capture: false,
+            message: |_| Message::TrayPeekWidgets,
+            // Peeking only makes sense while the widgets ignore the mouse.
+            enabled_when: |state| state.widgets_locked,
+            run: super::peek_widgets,
+        },
+        Self {
+            id: "tray-exit",
+            capture: false,
+            message: |_| Message::TrayExit,
+            enabled_when: |_| true,
+            run: crate::request_app_exit,
+        },
+    ];
+
+    fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.id == id)
+    }
+
+    fn label(self, state: &TrayMenuState) -> String {
+        format_text(state.language, (self.message)(state), &[("seconds", &state.peek_duration.as_secs())])
+    }
+
+    fn enabled(self, state: &TrayMenuState) -> bool {
+        (self.enabled_when)(state)
+    }
+}
+
+/// Actions are told apart by their menu id.
+impl PartialEq for TrayAction {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for TrayAction {}
+
+impl fmt::Debug for TrayAction {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_tuple("TrayAction").field(&self.id).finish()
+    }
+}
+
+/// The data submenus; entry ids are the kind's prefix followed by the task, global or spark id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TrayEntryKind {
+    Task,
+    Global,
+    Spark,
+}
+
+impl TrayEntryKind {
+    const ALL: [Self; 3] = [Self::Task, Self::Global, Self::Spark];
+
+    fn id_prefix(self) -> &'static str {
+        match self {
+            Self::Task => TASK_ID_PREFIX,
+            Self::Global => GLOBAL_ID_PREFIX,
+            Self::Spark => SPARK_ID_PREFIX,
+        }
+    }
+
+    fn submenu_label(self, language: Language, count: usize) -> String {
+        let message = match self {
+            Self::Task => Message::TrayToday,
+            Self::Global => Message::TrayOpenGlobal,
+            Self::Spark => Message::TrayRecentSparks,
+        };
+        format_text(language, message, &[("count", &count)])
+    }
+
+    fn empty_label(self, language: Language) -> &'static str {
+        let message = match self {
+            Self::Task => Message::TrayNothingToday,
+            Self::Global => Message::TrayNoGlobals,
+            Self::Spark => Message::TrayNoSparks,
+        };
+        text(language, message)
+    }
+
+    fn entries(self, summary: &TrayDataSummary) -> &[TrayEntry] {
+        match self {
+            Self::Task => &summary.today_tasks,
+            Self::Global => &summary.active_globals,
+            Self::Spark => &summary.recent_sparks,
+        }
+    }
+}
+
+/// Everything the tray menu is derived from.
+#[derive(Debug, Clone)]
+pub(crate) struct TrayMenuState {
+    pub language: Language,
+    pub widgets_locked: bool,
+    pub widgets_visible: bool,
+    pub peek_duration: Duration,
+    pub summary: TrayDataSummary,
+}
+
+/// One node of the tray menu, independent of the runtime that renders it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TrayMenuNode {
+    Action {
+        action: TrayAction,
+        text: String,
+        enabled: bool,
+    },
+    /// A task, global or spark. Tasks render as check items; checking one completes the task.
+    Entry {
+        id: String,
+        text: String,
+        checkable: bool,
+    },
+    /// Disabled text standing in for an empty submenu.
+    Placeholder(String),
+    Submenu {
+        text: String,
+        children: Vec<TrayMenuNode>,
+    },
+    Separator,
+}
+
+/// What a clicked menu id asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TrayCommand<'a> {
+    Action(TrayAction),
+    Entry(TrayEntryKind, &'a str),
+}
+
+pub(crate) fn parse_menu_id(id: &str) -> Option<TrayCommand<'_>> {
+    if let Some(action) = TrayAction::from_id(id) {
+        return Some(TrayCommand::Action(action));
+    }
+    TrayEntryKind::ALL.into_iter().find_map(|kind| {
+        id.strip_prefix(kind.id_prefix())
+            .filter(|item_id| !item_id.is_empty())
+            .map(|item_id| TrayCommand::Entry(kind, item_id))
+    })
+}
+
+fn entry_title(title: &str) -> String {
+    if title.chars().count() <= TITLE_MAX_CHARS {
+        return title.to_string();
+    }
+    let truncated = title.chars().take(TITLE_MAX_CHARS - 1).collect::<String>();
+    format!("{truncated}…")
+}
+
+fn entry_submenu(kind: TrayEntryKind, state: &TrayMenuState) -> TrayMenuNode {
+    let entries = kind.entries(&state.summary);
+    let mut children = entries
+        .iter()
+        .map(|entry| TrayMenuNode::Entry {
+            id: format!("{}{}", kind.id_prefix(), entry.id),
+            text: entry_title(&entry.title),
+            checkable: kind == TrayEntryKind::Task,
+        })
+        .collect::<Vec<_>>();
+    if children.is_empty() {
+        children.push(TrayMenuNode::Placeholder(kind.empty_label(state.language).to_string()));
+    }
+    TrayMenuNode::Submenu {
+        text: kind.submenu_label(state.language, entries.len()),
+        children,
+    }
+}
+
+fn action_node(action: TrayAction, state: &TrayMenuState) -> TrayMenuNode {
+    TrayMenuNode::Action {
+        action,
+        text: action.label(state),
+        enabled: action.enabled(state),
+    }
+}
+
+/// Today's tasks and the capture actions, the other data submenus, then the app and widget controls.
+pub(crate) fn tray_menu(state: &TrayMenuState) -> Vec<TrayMenuNode> {
+    let actions = |capture: bool| {
+        TrayAction::ALL
+            .into_iter()
+            .filter(move |action| action.capture == capture)
+            .map(|action| action_node(action, state))
+    };
+
+    let mut nodes = vec![entry_submenu(TrayEntryKind::Task, state)];
+    nodes.extend(actions(true));
+    nodes.push(entry_submenu(TrayEntryKind::Global, state));
+    nodes.push(entry_submenu(TrayEntryKind::Spark, state));
+    nodes.push(TrayMenuNode::Separator);
+    nodes.extend(actions(false));
+    nodes
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn sample_state(language: Language) -> TrayMenuState {
+        TrayMenuState {
+            language,
+            widgets_locked: false,
+            widgets_visible: true,
+            peek_duration: Duration::from_secs(8),
+            summary: TrayDataSummary {
+                today_tasks: vec![TrayEntry {
+                    id: "t1".to_string(),
+                    title: "x".repeat(50),
+                }],
+                active_globals: Vec::new(),
+                recent_sparks: vec![TrayEntry {
+                    id: "s1".to_string(),
+                    title: "Idea".to_string(),
+                }],
+            },
+        }
+    }
+
+    fn action_texts(nodes: &[TrayMenuNode]) -> Vec<(&'static str, String, bool)> {
+        nodes
+            .iter()
+            .filter_map(|node| match node {
+                TrayMenuNode::Action { action, text, enabled } => Some((action.id, text.clone(), *enabled)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn menu_lists_every_action_once_with_state_dependent_labels() {
+        let nodes = tray_menu(&sample_state(Language::En));
+        let actions = action_texts(&nodes);
+
+        assert_eq!(
+            actions.iter().map(|(id, _, _)| *id).collect::<Vec<_>>(),
+            TrayAction::ALL.map(|action| action.id)
+        );
+        assert!(actions.contains(&("tray-toggle-widget-visibility", "Hide Widget".to_string(), true)));
+        assert!(actions.contains(&("tray-toggle-widget-lock", "Lock Widget".to_string(), true)));
+        assert!(actions.contains(&("tray-peek-widgets", "Peek Widgets (8s)".to_string(), false)));
+
+        let TrayMenuNode::Submenu { text, children } = &nodes[0] else {
+            panic!("today's tasks should come first");
+        };
+        assert_eq!(text, "Today (1)");
+        assert_eq!(
+            children[0],
+            TrayMenuNode::Entry {
+                id: "tray-task:t1".to_string(),
+                text: format!("{}…", "x".repeat(39)),
+                checkable: true,
+            }
+        );
+        assert!(nodes.contains(&TrayMenuNode::Submenu {
+            text: "Open Global".to_string(),
+            children: vec![TrayMenuNode::Placeholder("No active globals".to_string())],
+        }));
+    }
+
+    #[test]
+    fn labels_follow_the_configured_language() {
+        let mut state = sample_state(Language::ZhCn);
+        state.widgets_locked = true;
+        let actions = action_texts(&tray_menu(&state));
+
+        assert!(actions.contains(&("tray-toggle-widget-lock", "解锁 Widget".to_string(), true)));
+        assert!(actions.contains(&("tray-peek-widgets", "临时解锁 Widget（8 秒）".to_string(), true)));
+        assert!(actions.contains(&("tray-exit", "退出应用".to_string(), true)));
+    }
+
+    #[test]
+    fn menu_ids_round_trip_to_commands() {
+        for action in TrayAction::ALL {
+            assert_eq!(parse_menu_id(action.id), Some(TrayCommand::Action(action)));
+        }
+        assert_eq!(parse_menu_id("tray-task:t1"), Some(TrayCommand::Entry(TrayEntryKind::Task, "t1")));
+        assert_eq!(parse_menu_id("tray-global:g1"), Some(TrayCommand::Entry(TrayEntryKind::Global, "g1")));
+        assert_eq!(parse_menu_id("tray-spark:"), None);
+        assert_eq!(parse_menu_id("unknown"), None);
+    }
+}
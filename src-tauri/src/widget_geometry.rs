@@ -0,0 +1,558 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tauri::{Manager, Monitor, Runtime, WebviewWindow};
+
+use crate::{
+    config::{WidgetAlignMode, WidgetDock, WidgetInstanceConfig, WidgetPosition, WidgetSize, WidgetVerticalAnchor},
+    error::AppResult,
+    logging::log_project_event,
+    update_widget_config,
+    window::{resolve_widget_window_logical_size, snap_widget_to_edges},
+};
+
+const GEOMETRY_PERSIST_DEBOUNCE: Duration = Duration::from_millis(400);
+/// A drag started from the page that has not settled within this long no longer counts as one.
+const WIDGET_DRAG_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Logical work area of one monitor.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MonitorArea {
+    pub name: Option<String>,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl MonitorArea {
+    pub(crate) fn from_monitor(monitor: &Monitor) -> Self {
+        let scale_factor = monitor.scale_factor();
+        let position = monitor.work_area().position.to_logical::<f64>(scale_factor);
+        let size = monitor.work_area().size.to_logical::<f64>(scale_factor);
+        Self {
+            name: monitor.name().cloned(),
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        }
+    }
+
+    fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+
+    fn distance_to(&self, x: f64, y: f64) -> f64 {
+        let dx = (self.x - x).max(x - (self.x + self.width)).max(0.0);
+        let dy = (self.y - y).max(y - (self.y + self.height)).max(0.0);
+        dx.hypot(dy)
+    }
+
+    /// Width and height of the overlap between this work area and `bounds`.
+    fn overlap(&self, bounds: &WidgetBounds) -> (f64, f64) {
+        let width = (self.x + self.width).min(bounds.x + bounds.width) - self.x.max(bounds.x);
+        let height = (self.y + self.height).min(bounds.y + bounds.height) - self.y.max(bounds.y);
+        (width.max(0.0), height.max(0.0))
+    }
+}
+
+/// How much of the widget must stay on some monitor for it to count as reachable by the user.
+const MIN_VISIBLE_LOGICAL_PX: f64 = 48.0;
+
+/// A drag that ends this close to a work-area edge snaps the widget flush against it.
+const SNAP_THRESHOLD_LOGICAL_PX: f64 = 16.0;
+
+/// Logical outer bounds of the widget window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct WidgetBounds {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Lists monitors with the primary one first, so it is the fallback when nothing else matches.
+pub(crate) fn collect_monitor_areas<R: Runtime>(app: &tauri::AppHandle<R>) -> Vec<MonitorArea> {
+    let primary = app.primary_monitor().ok().flatten().map(|monitor| MonitorArea::from_monitor(&monitor));
+    let mut areas = primary.iter().cloned().collect::<Vec<_>>();
+    for monitor in app.available_monitors().unwrap_or_default() {
+        let area = MonitorArea::from_monitor(&monitor);
+        if primary.as_ref() != Some(&area) {
+            areas.push(area);
+        }
+    }
+
+    areas
+}
+
+/// Returns the saved monitor (by name, else the one containing the saved position) and whether it was found.
+/// When neither exists any more, the monitor nearest to the saved position is used instead.
+fn select_monitor<'a>(
+    widget: &WidgetInstanceConfig,
+    monitors: &'a [MonitorArea],
+) -> Option<(&'a MonitorArea, bool)> {
+    let by_name = widget
+        .monitor
+        .as_deref()
+        .and_then(|name| monitors.iter().find(|monitor| monitor.name.as_deref() == Some(name)));
+    let by_position = widget.position.and_then(|position| {
+        monitors
+            .iter()
+            .find(|monitor| monitor.contains(f64::from(position.x), f64::from(position.y)))
+    });
+    if let Some(monitor) = by_name.or(by_position) {
+        return Some((monitor, true));
+    }
+
+    let nearest = widget.position.and_then(|position| {
+        monitors.iter().min_by(|left, right| {
+            let (x, y) = (f64::from(position.x), f64::from(position.y));
+            left.distance_to(x, y).total_cmp(&right.distance_to(x, y))
+        })
+    });
+    nearest.or_else(|| monitors.first()).map(|monitor| (monitor, false))
+}
+
+/// Places a window of `width`×`height` in the top-right corner of the monitor's work area.
+pub(crate) fn anchor_top_right(monitor: &MonitorArea, width: f64, height: f64) -> WidgetBounds {
+    let width = width.min(monitor.width);
+    let height = height.min(monitor.height);
+    WidgetBounds {
+        x: (monitor.x + monitor.width - width).round(),
+        y: monitor.y.round(),
+        width: width.round(),
+        height: height.round(),
+    }
+}
+
+/// Resizes `current` to `width`×`height`, keeping the edges named by `align` and `vertical` fixed.
+pub(crate) fn resize_anchored(
+    current: WidgetBounds,
+    width: f64,
+    height: f64,
+    align: WidgetAlignMode,
+    vertical: WidgetVerticalAnchor,
+) -> WidgetBounds {
+    let x = match align {
+        WidgetAlignMode::Left => current.x,
+        WidgetAlignMode::Right => current.x + current.width - width,
+    };
+    let y = match vertical {
+        WidgetVerticalAnchor::Top => current.y,
+        WidgetVerticalAnchor::Bottom => current.y + current.height - height,
+    };
+    WidgetBounds {
+        x: x.round(),
+        y: y.round(),
+        width,
+        height,
+    }
+}
+
+/// Picks the monitor showing the largest part of the widget.
+pub(crate) fn monitor_for_bounds<'a>(bounds: &WidgetBounds, monitors: &'a [MonitorArea]) -> Option<&'a MonitorArea> {
+    monitors
+        .iter()
+        .map(|monitor| {
+            let (width, height) = monitor.overlap(bounds);
+            (monitor, width * height)
+        })
+        .filter(|(_, area)| *area > 0.0)
+        .max_by(|(_, left), (_, right)| left.total_cmp(right))
+        .map(|(monitor, _)| monitor)
+}
+
+/// Finds the work-area edges within the snap threshold, preferring the closer one on each axis.
+/// Returns `None` when the widget is not near any edge.
+pub(crate) fn detect_dock(bounds: &WidgetBounds, monitor: &MonitorArea) -> Option<WidgetDock> {
+    let left = (bounds.x - monitor.x).abs();
+    let right = (monitor.x + monitor.width - (bounds.x + bounds.width)).abs();
+    let top = (bounds.y - monitor.y).abs();
+    let bottom = (monitor.y + monitor.height - (bounds.y + bounds.height)).abs();
+
+    let horizontal = match (left <= SNAP_THRESHOLD_LOGICAL_PX, right <= SNAP_THRESHOLD_LOGICAL_PX) {
+        (true, true) if right < left => Some(WidgetAlignMode::Right),
+        (true, _) => Some(WidgetAlignMode::Left),
+        (false, true) => Some(WidgetAlignMode::Right),
+        (false, false) => None,
+    };
+    let vertical = match (top <= SNAP_THRESHOLD_LOGICAL_PX, bottom <= SNAP_THRESHOLD_LOGICAL_PX) {
+        (true, true) if bottom < top => Some(WidgetVerticalAnchor::Bottom),
+        (true, _) => Some(WidgetVerticalAnchor::Top),
+        (false, true) => Some(WidgetVerticalAnchor::Bottom),
+        (false, false) => None,
+    };
+
+    (horizontal.is_some() || vertical.is_some()).then_some(WidgetDock { horizontal, vertical })
+}
+
+/// Moves the widget flush against the docked edges of the monitor's work area, keeping its size.
+pub(crate) fn apply_dock(bounds: WidgetBounds, dock: WidgetDock, monitor: &MonitorArea) -> WidgetBounds {
+    let width = bounds.width.min(monitor.width);
+    let height = bounds.height.min(monitor.height);
+    let x = match dock.horizontal {
+        Some(WidgetAlignMode::Left) => monitor.x,
+        Some(WidgetAlignMode::Right) => monitor.x + monitor.width - width,
+        None => bounds.x,
+    };
+    let y = match dock.vertical {
+        Some(WidgetVerticalAnchor::Top) => monitor.y,
+        Some(WidgetVerticalAnchor::Bottom) => monitor.y + monitor.height - height,
+        None => bounds.y,
+    };
+    WidgetBounds {
+        x: x.round(),
+        y: y.round(),
+        width: width.round(),
+        height: height.round(),
+    }
+}
+
+fn clamp_to_monitor(monitor: &MonitorArea, bounds: WidgetBounds) -> WidgetBounds {
+    let width = bounds.width.min(monitor.width);
+    let height = bounds.height.min(monitor.height);
+    WidgetBounds {
+        x: bounds.x.clamp(monitor.x, monitor.x + monitor.width - width).round(),
+        y: bounds.y.clamp(monitor.y, monitor.y + monitor.height - height).round(),
+        width: width.round(),
+        height: height.round(),
+    }
+}
+
+/// Restores the saved bounds, shrunk and moved as needed to fit the saved monitor's work area.
+/// If that monitor is gone, or nothing was saved yet, the widget is anchored top-right on the nearest monitor.
+pub(crate) fn resolve_restore_bounds(widget: &WidgetInstanceConfig, monitors: &[MonitorArea]) -> Option<WidgetBounds> {
+    let (monitor, is_saved_monitor) = select_monitor(widget, monitors)?;
+    let (default_width, default_height) = resolve_widget_window_logical_size(widget.scale_percent);
+    let size = widget.size.unwrap_or(WidgetSize {
+        width: default_width.unsigned_abs(),
+        height: default_height.unsigned_abs(),
+    });
+    let (width, height) = (f64::from(size.width), f64::from(size.height));
+
+    match widget.position {
+        Some(position) if is_saved_monitor => Some(clamp_to_monitor(
+            monitor,
+            WidgetBounds {
+                x: f64::from(position.x),
+                y: f64::from(position.y),
+                width,
+                height,
+            },
+        )),
+        _ => Some(anchor_top_right(monitor, width, height)),
+    }
+}
+
+/// Returns new bounds when the widget is no longer reachable on any monitor, keeping its size.
+pub(crate) fn relocate_if_hidden(bounds: WidgetBounds, monitors: &[MonitorArea]) -> Option<WidgetBounds> {
+    let is_reachable = monitors.iter().any(|monitor| {
+        let (width, height) = monitor.overlap(&bounds);
+        width >= MIN_VISIBLE_LOGICAL_PX.min(bounds.width) && height >= MIN_VISIBLE_LOGICAL_PX.min(bounds.height)
+    });
+    if is_reachable {
+        return None;
+    }
+
+    let nearest = monitors.iter().min_by(|left, right| {
+        left.distance_to(bounds.x, bounds.y)
+            .total_cmp(&right.distance_to(bounds.x, bounds.y))
+    })?;
+    Some(anchor_top_right(nearest, bounds.width, bounds.height))
+}
+
+struct CapturedGeometry {
+    position: WidgetPosition,
+    size: WidgetSize,
+    monitor: Option<String>,
+}
+
+/// Reads the window on the calling thread; must run before the config lock is taken because
+/// the getters round-trip through the main thread.
+fn capture_widget_geometry<R: Runtime>(window: &WebviewWindow<R>) -> AppResult<CapturedGeometry> {
+    let scale_factor = window.scale_factor()?;
+    let position = window.outer_position()?.to_logical::<f64>(scale_factor);
+    let size = window.outer_size()?.to_logical::<f64>(scale_factor);
+    let monitor = window.current_monitor()?.and_then(|monitor| monitor.name().cloned());
+
+    Ok(CapturedGeometry {
+        position: WidgetPosition {
+            x: position.x.round() as i32,
+            y: position.y.round() as i32,
+        },
+        size: WidgetSize {
+            width: size.width.round() as u32,
+            height: size.height.round() as u32,
+        },
+        monitor,
+    })
+}
+
+/// Saves the settled position, size and monitor of `label`. Only a drag the user started snaps the widget to
+/// nearby edges and re-docks it; programmatic moves and resizes keep the saved dock.
+fn persist_widget_geometry(app: &tauri::AppHandle, label: &str, dragged: bool) {
+    let Some(widget_window) = app.get_webview_window(label) else {
+        return;
+    };
+
+    let snapped_dock = if dragged {
+        snap_widget_to_edges(app, label).map(Some)
+    } else {
+        Ok(None)
+    };
+    let result = snapped_dock.and_then(|snapped_dock| {
+        let geometry = capture_widget_geometry(&widget_window)?;
+        update_widget_config(app, label, move |widget| {
+            widget.position = Some(geometry.position);
+            widget.size = Some(geometry.size);
+            if let Some(monitor) = geometry.monitor {
+                widget.monitor = Some(monitor);
+            }
+            if let Some(dock) = snapped_dock {
+                widget.dock = dock;
+            }
+        })
+    });
+    if let Err(error) = result {
+        log_project_event(
+            app,
+            "warn",
+            "widget.geometry.persist_failure",
+            "Failed to persist widget geometry.",
+            Some(serde_json::json!({ "widget": label, "error": error })),
+        );
+    }
+}
+
+/// Window changes of one widget that have not settled yet.
+struct PendingGeometry {
+    changed_at: Instant,
+    moved: bool,
+}
+
+/// Tracks the last Moved/Resized event per widget so a drag is written to config.json once it settles.
+#[derive(Default)]
+pub(crate) struct WidgetGeometryState {
+    pending: Mutex<HashMap<String, PendingGeometry>>,
+    /// When each widget last started a drag from its page.
+    drags: Mutex<HashMap<String, Instant>>,
+}
+
+/// Marks `label` as being dragged by the user, so the move snaps once it settles.
+pub(crate) fn arm_widget_drag<R: Runtime>(app: &tauri::AppHandle<R>, label: &str) {
+    if let Some(state) = app.try_state::<WidgetGeometryState>() {
+        if let Ok(mut drags) = state.drags.lock() {
+            drags.insert(label.to_string(), Instant::now());
+        }
+    }
+}
+
+fn take_widget_drag(app: &tauri::AppHandle, label: &str) -> bool {
+    app.try_state::<WidgetGeometryState>()
+        .and_then(|state| state.drags.lock().ok()?.remove(label))
+        .is_some_and(|started_at| started_at.elapsed() < WIDGET_DRAG_TIMEOUT)
+}
+
+pub(crate) fn schedule_widget_geometry_persist(app: &tauri::AppHandle, label: &str, moved: bool) {
+    let Some(state) = app.try_state::<WidgetGeometryState>() else {
+        return;
+    };
+    let Ok(mut pending) = state.pending.lock() else {
+        return;
+    };
+    let previous = pending.insert(
+        label.to_string(),
+        PendingGeometry {
+            changed_at: Instant::now(),
+            moved,
+        },
+    );
+    if let Some(previous) = previous {
+        if let Some(current) = pending.get_mut(label) {
+            current.moved |= previous.moved;
+        }
+        return;
+    }
+    drop(pending);
+
+    let app_handle = app.clone();
+    let thread_label = label.to_string();
+    let spawn_result = std::thread::Builder::new()
+        .name(format!("daily-{label}-geometry"))
+        .spawn(move || {
+            let moved = loop {
+                std::thread::sleep(GEOMETRY_PERSIST_DEBOUNCE);
+                let Some(state) = app_handle.try_state::<WidgetGeometryState>() else {
+                    return;
+                };
+                let Ok(mut pending) = state.pending.lock() else {
+                    return;
+                };
+                if pending
+                    .get(&thread_label)
+                    .is_some_and(|change| change.changed_at.elapsed() < GEOMETRY_PERSIST_DEBOUNCE)
+                {
+                    continue;
+                }
+                break pending.remove(&thread_label).is_some_and(|change| change.moved);
+            };
+            // A resize never snaps, and leaves a drag that has not moved yet armed.
+            let dragged = moved && take_widget_drag(&app_handle, &thread_label);
+            persist_widget_geometry(&app_handle, &thread_label, dragged);
+        });
+    if spawn_result.is_err() {
+        if let Ok(mut pending) = state.pending.lock() {
+            pending.remove(label);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        apply_dock, detect_dock, relocate_if_hidden, resize_anchored, resolve_restore_bounds, MonitorArea,
+        WidgetBounds,
+    };
+    use crate::config::{
+        WidgetAlignMode, WidgetDock, WidgetInstanceConfig, WidgetPosition, WidgetSize, WidgetVerticalAnchor,
+    };
+
+    fn monitor(name: &str, x: f64, width: f64) -> MonitorArea {
+        MonitorArea {
+            name: Some(name.to_string()),
+            x,
+            y: 0.0,
+            width,
+            height: 1040.0,
+        }
+    }
+
+    #[test]
+    fn restore_keeps_saved_bounds_on_existing_monitor() {
+        let widget = WidgetInstanceConfig {
+            position: Some(WidgetPosition { x: 2200, y: 100 }),
+            size: Some(WidgetSize { width: 360, height: 760 }),
+            monitor: Some("DP-2".to_string()),
+            ..WidgetInstanceConfig::default()
+        };
+        let monitors = [monitor("DP-1", 0.0, 1920.0), monitor("DP-2", 1920.0, 1920.0)];
+
+        assert_eq!(
+            resolve_restore_bounds(&widget, &monitors),
+            Some(WidgetBounds { x: 2200.0, y: 100.0, width: 360.0, height: 760.0 })
+        );
+    }
+
+    #[test]
+    fn restore_clamps_saved_position_inside_its_monitor() {
+        let widget = WidgetInstanceConfig {
+            position: Some(WidgetPosition { x: 1800, y: 900 }),
+            size: Some(WidgetSize { width: 360, height: 760 }),
+            ..WidgetInstanceConfig::default()
+        };
+        let monitors = [monitor("DP-1", 0.0, 1920.0)];
+
+        assert_eq!(
+            resolve_restore_bounds(&widget, &monitors),
+            Some(WidgetBounds { x: 1560.0, y: 280.0, width: 360.0, height: 760.0 })
+        );
+    }
+
+    #[test]
+    fn restore_anchors_top_right_on_nearest_monitor_when_saved_monitor_is_gone() {
+        let widget = WidgetInstanceConfig {
+            position: Some(WidgetPosition { x: 5200, y: 300 }),
+            size: Some(WidgetSize { width: 360, height: 760 }),
+            monitor: Some("DP-3".to_string()),
+            ..WidgetInstanceConfig::default()
+        };
+        let monitors = [monitor("DP-1", 0.0, 1920.0), monitor("DP-2", 1920.0, 2560.0)];
+
+        assert_eq!(
+            resolve_restore_bounds(&widget, &monitors),
+            Some(WidgetBounds { x: 4120.0, y: 0.0, width: 360.0, height: 760.0 })
+        );
+    }
+
+    #[test]
+    fn relocate_moves_only_unreachable_widgets() {
+        let monitors = [monitor("DP-1", 0.0, 1920.0)];
+        let visible = WidgetBounds { x: 1800.0, y: 100.0, width: 360.0, height: 760.0 };
+        let hidden = WidgetBounds { x: 2400.0, y: 100.0, width: 360.0, height: 760.0 };
+
+        assert_eq!(relocate_if_hidden(visible, &monitors), None);
+        assert_eq!(
+            relocate_if_hidden(hidden, &monitors),
+            Some(WidgetBounds { x: 1560.0, y: 0.0, width: 360.0, height: 760.0 })
+        );
+    }
+
+    #[test]
+    fn restore_defaults_to_top_right_without_saved_position() {
+        let widget = WidgetInstanceConfig::default();
+        let monitors = [monitor("DP-1", 0.0, 1920.0)];
+
+        assert_eq!(
+            resolve_restore_bounds(&widget, &monitors),
+            Some(WidgetBounds { x: 1560.0, y: 0.0, width: 360.0, height: 760.0 })
+        );
+        assert_eq!(resolve_restore_bounds(&widget, &[]), None);
+    }
+
+    #[test]
+    fn resize_keeps_the_anchored_edges_fixed() {
+        let current = WidgetBounds { x: 100.0, y: 200.0, width: 360.0, height: 760.0 };
+
+        assert_eq!(
+            resize_anchored(current, 720.0, 1520.0, WidgetAlignMode::Left, WidgetVerticalAnchor::Top),
+            WidgetBounds { x: 100.0, y: 200.0, width: 720.0, height: 1520.0 }
+        );
+        assert_eq!(
+            resize_anchored(current, 720.0, 1520.0, WidgetAlignMode::Right, WidgetVerticalAnchor::Top),
+            WidgetBounds { x: -260.0, y: 200.0, width: 720.0, height: 1520.0 }
+        );
+        assert_eq!(
+            resize_anchored(current, 252.0, 532.0, WidgetAlignMode::Right, WidgetVerticalAnchor::Bottom),
+            WidgetBounds { x: 208.0, y: 428.0, width: 252.0, height: 532.0 }
+        );
+    }
+
+    #[test]
+    fn drops_near_an_edge_snap_flush_and_record_the_dock() {
+        let area = monitor("DP-1", 0.0, 1920.0);
+        let near_top_right = WidgetBounds { x: 1550.0, y: 12.0, width: 360.0, height: 760.0 };
+        let floating = WidgetBounds { x: 800.0, y: 100.0, width: 360.0, height: 760.0 };
+
+        let dock = detect_dock(&near_top_right, &area).expect("should dock");
+        assert_eq!(
+            dock,
+            WidgetDock {
+                horizontal: Some(WidgetAlignMode::Right),
+                vertical: Some(WidgetVerticalAnchor::Top),
+            }
+        );
+        assert_eq!(
+            apply_dock(near_top_right, dock, &area),
+            WidgetBounds { x: 1560.0, y: 0.0, width: 360.0, height: 760.0 }
+        );
+        assert_eq!(detect_dock(&floating, &area), None);
+    }
+
+    #[test]
+    fn dock_follows_a_work_area_resize() {
+        let dock = WidgetDock { horizontal: Some(WidgetAlignMode::Left), vertical: Some(WidgetVerticalAnchor::Bottom) };
+        let bounds = WidgetBounds { x: 0.0, y: 280.0, width: 360.0, height: 760.0 };
+        let smaller = MonitorArea { height: 860.0, ..monitor("DP-1", 0.0, 1600.0) };
+
+        assert_eq!(
+            apply_dock(bounds, dock, &smaller),
+            WidgetBounds { x: 0.0, y: 100.0, width: 360.0, height: 760.0 }
+        );
+    }
+}
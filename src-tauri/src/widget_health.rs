@@ -0,0 +1,289 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tauri::{webview::PageLoadEvent, AppHandle, Emitter, Manager};
+
+use crate::{
+    config::ConfigState,
+    i18n::{self, Message},
+    logging::log_project_event,
+    window::ensure_widget_window,
+};
+
+pub(crate) const EVENT_WIDGET_HEALTH: &str = "widget-health";
+const WIDGET_READY_TIMEOUT: Duration = Duration::from_secs(10);
+const WIDGET_RECOVERY_BASE_DELAY: Duration = Duration::from_secs(1);
+const WIDGET_RECOVERY_MAX_DELAY: Duration = Duration::from_secs(30);
+const WIDGET_RECOVERY_MAX_ATTEMPTS: u32 = 5;
+
+#[derive(Debug, Clone, Copy)]
+struct WidgetPageLoad {
+    started_at: Instant,
+    finished: bool,
+    ready: bool,
+}
+
+/// Page-load progress per widget window. A widget is healthy once its frontend calls `widget_ready`; a page that
+/// stays unready past `WIDGET_READY_TIMEOUT` is recreated with exponential backoff.
+#[derive(Default)]
+pub(crate) struct WidgetHealthState {
+    loads: Mutex<HashMap<String, WidgetPageLoad>>,
+    recovery_attempts: Mutex<HashMap<String, u32>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WidgetHealthStatus {
+    Recovering,
+    Recovered,
+    GaveUp,
+}
+
+/// Sent to the main window whenever a widget is being, or could not be, recovered.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WidgetHealthEvent {
+    pub label: String,
+    pub status: WidgetHealthStatus,
+    pub attempt: u32,
+    /// Ready-to-show notification text in the configured language.
+    pub message: String,
+}
+
+pub(crate) fn record_widget_page_load(app: &AppHandle, label: &str, event: PageLoadEvent) {
+    let Some(state) = app.try_state::<WidgetHealthState>() else {
+        return;
+    };
+    let Ok(mut loads) = state.loads.lock() else {
+        return;
+    };
+    match event {
+        PageLoadEvent::Started => {
+            let started_at = Instant::now();
+            loads.insert(
+                label.to_string(),
+                WidgetPageLoad {
+                    started_at,
+                    finished: false,
+                    ready: false,
+                },
+            );
+            drop(loads);
+            spawn_ready_watchdog(app, label, started_at);
+        }
+        PageLoadEvent::Finished => {
+            if let Some(load) = loads.get_mut(label) {
+                load.finished = true;
+            }
+        }
+    }
+}
+
+/// Handles the frontend handshake; clears the backoff so a later failure starts from the base delay again.
+pub(crate) fn mark_widget_ready(app: &AppHandle, label: &str) {
+    let Some(state) = app.try_state::<WidgetHealthState>() else {
+        return;
+    };
+    if let Ok(mut loads) = state.loads.lock() {
+        let load = loads.entry(label.to_string()).or_insert(WidgetPageLoad {
+            started_at: Instant::now(),
+            finished: true,
+            ready: false,
+        });
+        load.ready = true;
+    }
+    let recovered_attempt = state
+        .recovery_attempts
+        .lock()
+        .ok()
+        .and_then(|mut attempts| attempts.remove(label));
+    if let Some(attempt) = recovered_attempt {
+        log_project_event(
+            app,
+            "info",
+            "widget.health.recovered",
+            "Widget recovered after being recreated.",
+            Some(serde_json::json!({ "widget": label, "attempt": attempt })),
+        );
+        notify_main_window(app, label, WidgetHealthStatus::Recovered, attempt);
+    }
+}
+
+pub(crate) fn forget_widget_page_load(app: &AppHandle, label: &str) {
+    let Some(state) = app.try_state::<WidgetHealthState>() else {
+        return;
+    };
+    if let Ok(mut loads) = state.loads.lock() {
+        loads.remove(label);
+    };
+}
+
+/// Whether an existing widget window can be reused. A window with no recorded load is given the benefit of the
+/// doubt: it was created before tracking started or has not begun navigating.
+pub(crate) fn is_widget_healthy(app: &AppHandle, label: &str) -> bool {
+    let load = app
+        .try_state::<WidgetHealthState>()
+        .and_then(|state| state.loads.lock().ok().and_then(|loads| loads.get(label).copied()));
+    is_page_load_healthy(load, Instant::now())
+}
+
+fn is_page_load_healthy(load: Option<WidgetPageLoad>, now: Instant) -> bool {
+    match load {
+        Some(load) => load.ready || now.saturating_duration_since(load.started_at) < WIDGET_READY_TIMEOUT,
+        None => true,
+    }
+}
+
+fn recovery_delay(attempt: u32) -> Duration {
+    WIDGET_RECOVERY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(WIDGET_RECOVERY_MAX_DELAY)
+}
+
+fn spawn_ready_watchdog(app: &AppHandle, label: &str, started_at: Instant) {
+    let app_handle = app.clone();
+    let thread_label = label.to_string();
+    let spawn_result = std::thread::Builder::new()
+        .name(format!("daily-{label}-watchdog"))
+        .spawn(move || {
+            std::thread::sleep(WIDGET_READY_TIMEOUT);
+            let Some(state) = app_handle.try_state::<WidgetHealthState>() else {
+                return;
+            };
+            let load = state
+                .loads
+                .lock()
+                .ok()
+                .and_then(|loads| loads.get(&thread_label).copied());
+            // A reload or a destroyed window hands responsibility to the newer load, if any.
+            let Some(load) = load.filter(|load| load.started_at == started_at) else {
+                return;
+            };
+            if load.ready {
+                return;
+            }
+            recover_widget(&app_handle, &thread_label, load);
+        });
+    if let Err(error) = spawn_result {
+        eprintln!("[{label}] failed to spawn ready watchdog: {error}");
+    }
+}
+
+fn recover_widget(app: &AppHandle, label: &str, load: WidgetPageLoad) {
+    let Some(state) = app.try_state::<WidgetHealthState>() else {
+        return;
+    };
+    let attempt = match state.recovery_attempts.lock() {
+        Ok(mut attempts) => {
+            let attempt = attempts.entry(label.to_string()).or_insert(0);
+            *attempt += 1;
+            *attempt
+        }
+        Err(_) => return,
+    };
+
+    if attempt > WIDGET_RECOVERY_MAX_ATTEMPTS {
+        log_project_event(
+            app,
+            "error",
+            "widget.health.gave_up",
+            "Widget never became ready; automatic recovery stopped.",
+            Some(serde_json::json!({ "widget": label, "attempts": WIDGET_RECOVERY_MAX_ATTEMPTS })),
+        );
+        notify_main_window(app, label, WidgetHealthStatus::GaveUp, WIDGET_RECOVERY_MAX_ATTEMPTS);
+        if let Ok(mut attempts) = state.recovery_attempts.lock() {
+            attempts.remove(label);
+        }
+        return;
+    }
+
+    let delay = recovery_delay(attempt);
+    log_project_event(
+        app,
+        "warn",
+        "widget.health.unresponsive",
+        "Widget did not report ready in time; recreating it.",
+        Some(serde_json::json!({
+            "widget": label,
+            "attempt": attempt,
+            "pageFinished": load.finished,
+            "delayMs": delay.as_millis() as u64,
+        })),
+    );
+    notify_main_window(app, label, WidgetHealthStatus::Recovering, attempt);
+    std::thread::sleep(delay);
+
+    let Some(widget) = app
+        .try_state::<ConfigState>()
+        .and_then(|config| config.snapshot().widget(label))
+        .filter(|widget| widget.visible)
+    else {
+        return;
+    };
+    if app.get_webview_window(label).is_none() {
+        return;
+    }
+    if let Err(error) = ensure_widget_window(app, label, &widget) {
+        log_project_event(
+            app,
+            "error",
+            "widget.health.recreate_failure",
+            "Failed to recreate an unresponsive widget.",
+            Some(serde_json::json!({ "widget": label, "attempt": attempt, "error": error })),
+        );
+    }
+}
+
+fn notify_main_window(app: &AppHandle, label: &str, status: WidgetHealthStatus, attempt: u32) {
+    let message = match status {
+        WidgetHealthStatus::Recovering => Message::WidgetRecovering,
+        WidgetHealthStatus::Recovered => Message::WidgetRecovered,
+        WidgetHealthStatus::GaveUp => Message::WidgetGaveUp,
+    };
+    let payload = WidgetHealthEvent {
+        label: label.to_string(),
+        status,
+        attempt,
+        message: i18n::format_text(
+            i18n::current_language(),
+            message,
+            &[("label", &label), ("attempt", &attempt)],
+        ),
+    };
+    let _ = app.emit_to("main", EVENT_WIDGET_HEALTH, payload);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_load_is_healthy_until_the_ready_deadline() {
+        let started_at = Instant::now();
+        let load = WidgetPageLoad {
+            started_at,
+            finished: true,
+            ready: false,
+        };
+
+        assert!(is_page_load_healthy(None, started_at));
+        assert!(is_page_load_healthy(Some(load), started_at + Duration::from_secs(1)));
+        assert!(!is_page_load_healthy(Some(load), started_at + WIDGET_READY_TIMEOUT));
+        assert!(is_page_load_healthy(
+            Some(WidgetPageLoad { ready: true, ..load }),
+            started_at + WIDGET_READY_TIMEOUT * 10
+        ));
+    }
+
+    #[test]
+    fn recovery_delay_doubles_up_to_the_cap() {
+        assert_eq!(recovery_delay(1), Duration::from_secs(1));
+        assert_eq!(recovery_delay(2), Duration::from_secs(2));
+        assert_eq!(recovery_delay(4), Duration::from_secs(8));
+        assert_eq!(recovery_delay(10), WIDGET_RECOVERY_MAX_DELAY);
+    }
+}
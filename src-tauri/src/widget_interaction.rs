@@ -0,0 +1,196 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Runtime, Window};
+
+use crate::{
+    config::{ConfigState, WidgetInstanceConfig},
+    error::{AppError, AppResult},
+    logging::log_project_event,
+};
+
+pub(crate) const EVENT_WIDGET_PEEK: &str = "widget-peek-state";
+pub(crate) const WIDGET_DEFAULT_PEEK: Duration = Duration::from_secs(10);
+pub(crate) const WIDGET_MAX_PEEK: Duration = Duration::from_secs(300);
+
+/// How a widget window handles pointer and keyboard input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct WidgetInteraction {
+    pub ignore_cursor_events: bool,
+    pub focusable: bool,
+}
+
+impl WidgetInteraction {
+    /// Locked widgets refuse focus and, unless `click_through` is off, pass clicks to the windows below.
+    /// A running peek lifts both until it expires.
+    pub(crate) fn resolve(widget: &WidgetInstanceConfig, peeking: bool) -> Self {
+        let locked = widget.locked && !peeking;
+        Self {
+            ignore_cursor_events: locked && widget.click_through,
+            focusable: !locked,
+        }
+    }
+}
+
+/// Deadline of the running peek per widget label.
+#[derive(Default)]
+pub(crate) struct WidgetPeekState {
+    until: Mutex<HashMap<String, Instant>>,
+}
+
+/// Broadcast when a peek starts or ends so the widget page can enable or disable its controls.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WidgetPeekEvent {
+    pub label: String,
+    pub active: bool,
+    pub remaining_ms: u64,
+}
+
+pub(crate) fn is_widget_peeking<R: Runtime>(app: &AppHandle<R>, label: &str) -> bool {
+    let Some(state) = app.try_state::<WidgetPeekState>() else {
+        return false;
+    };
+    let peeking = state
+        .until
+        .lock()
+        .is_ok_and(|until| until.get(label).is_some_and(|deadline| *deadline > Instant::now()));
+    peeking
+}
+
+pub(crate) fn apply_window_interaction<R: Runtime>(
+    window: &Window<R>,
+    interaction: WidgetInteraction,
+) -> AppResult<()> {
+    window.set_ignore_cursor_events(interaction.ignore_cursor_events)?;
+    window.set_focusable(interaction.focusable)?;
+    Ok(())
+}
+
+/// Applies the saved lock and click-through state of `label`, plus any running peek, to its window when open.
+pub(crate) fn apply_widget_interaction<R: Runtime>(app: &AppHandle<R>, label: &str) -> AppResult<()> {
+    let Some(widget_window) = app.get_webview_window(label) else {
+        return Ok(());
+    };
+    let Some(widget) = app
+        .try_state::<ConfigState>()
+        .and_then(|state| state.snapshot().widget(label))
+    else {
+        return Ok(());
+    };
+
+    let interaction = WidgetInteraction::resolve(&widget, is_widget_peeking(app, label));
+    apply_window_interaction(&widget_window.as_ref().window(), interaction)
+}
+
+/// Makes a locked widget interactive for `duration` and relocks it from a backend thread, so the widget comes
+/// back to click-through even when its page is unresponsive. Returns `false` when the widget is not locked.
+pub(crate) fn start_widget_peek<R: Runtime>(app: &AppHandle<R>, label: &str, duration: Duration) -> AppResult<bool> {
+    let (Some(config_state), Some(peek_state)) = (app.try_state::<ConfigState>(), app.try_state::<WidgetPeekState>())
+    else {
+        return Ok(false);
+    };
+    if !config_state.snapshot().widget(label).is_some_and(|widget| widget.locked) {
+        return Ok(false);
+    }
+
+    let deadline = Instant::now() + duration;
+    if let Ok(mut until) = peek_state.until.lock() {
+        until.insert(label.to_string(), deadline);
+    }
+    apply_widget_interaction(app, label)?;
+    emit_peek_state(app, label, true, duration);
+
+    let app_handle = app.clone();
+    let thread_label = label.to_string();
+    let spawn_result = std::thread::Builder::new()
+        .name(format!("daily-{label}-peek"))
+        .spawn(move || {
+            std::thread::sleep(duration);
+            let Some(state) = app_handle.try_state::<WidgetPeekState>() else {
+                return;
+            };
+            // A newer peek or an explicit lock change owns the widget now.
+            let expired = state.until.lock().is_ok_and(|mut until| {
+                let current = until.get(&thread_label) == Some(&deadline);
+                if current {
+                    until.remove(&thread_label);
+                }
+                current
+            });
+            if !expired {
+                return;
+            }
+
+            let result = apply_widget_interaction(&app_handle, &thread_label);
+            emit_peek_state(&app_handle, &thread_label, false, Duration::ZERO);
+            log_project_event(
+                &app_handle,
+                if result.is_ok() { "info" } else { "warn" },
+                "widget.peek.ended",
+                "Widget peek expired; lock restored.",
+                Some(serde_json::json!({ "widget": thread_label, "error": result.err() })),
+            );
+        });
+    if let Err(error) = spawn_result {
+        end_widget_peek(app, label);
+        apply_widget_interaction(app, label)?;
+        return Err(AppError::internal(format!("failed to spawn peek timer: {error}")));
+    }
+
+    Ok(true)
+}
+
+/// Cancels a running peek without touching the window; callers reapply the interaction afterwards.
+pub(crate) fn end_widget_peek<R: Runtime>(app: &AppHandle<R>, label: &str) {
+    let Some(state) = app.try_state::<WidgetPeekState>() else {
+        return;
+    };
+    let was_peeking = state.until.lock().is_ok_and(|mut until| until.remove(label).is_some());
+    if was_peeking {
+        emit_peek_state(app, label, false, Duration::ZERO);
+    }
+}
+
+fn emit_peek_state<R: Runtime>(app: &AppHandle<R>, label: &str, active: bool, remaining: Duration) {
+    let payload = WidgetPeekEvent {
+        label: label.to_string(),
+        active,
+        remaining_ms: remaining.as_millis() as u64,
+    };
+    let _ = app.emit(EVENT_WIDGET_PEEK, payload);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locked_widgets_pass_clicks_through_unless_peeking_or_disabled() {
+        let locked = WidgetInstanceConfig {
+            locked: true,
+            ..WidgetInstanceConfig::default()
+        };
+
+        assert_eq!(
+            WidgetInteraction::resolve(&locked, false),
+            WidgetInteraction { ignore_cursor_events: true, focusable: false }
+        );
+        assert_eq!(
+            WidgetInteraction::resolve(&locked, true),
+            WidgetInteraction { ignore_cursor_events: false, focusable: true }
+        );
+        assert_eq!(
+            WidgetInteraction::resolve(&WidgetInstanceConfig { click_through: false, ..locked }, false),
+            WidgetInteraction { ignore_cursor_events: false, focusable: false }
+        );
+        assert_eq!(
+            WidgetInteraction::resolve(&WidgetInstanceConfig::default(), false),
+            WidgetInteraction { ignore_cursor_events: false, focusable: true }
+        );
+    }
+}
@@ -1,16 +1,45 @@
-use tauri::{
-    webview::PageLoadEvent, AppHandle, Manager, WebviewUrl, WebviewWindowBuilder,
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{webview::PageLoadEvent, AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+
+use crate::{
+    config::{
+        ConfigState, WidgetAlignMode, WidgetDock, WidgetInstanceConfig, WidgetVerticalAnchor, PRIMARY_WIDGET_LABEL,
+        WIDGET_MAX_SCALE_PERCENT, WIDGET_MIN_SCALE_PERCENT,
+    },
+    desktop_layer::apply_desktop_layer,
+    error::{AppError, AppResult},
+    logging::log_project_event,
+    widget_geometry::{
+        anchor_top_right, apply_dock, collect_monitor_areas, detect_dock, monitor_for_bounds, relocate_if_hidden,
+        resize_anchored, resolve_restore_bounds, MonitorArea, WidgetBounds,
+    },
+    quick_capture::{CaptureKind, EVENT_QUICK_CAPTURE_KIND, QUICK_CAPTURE_LABEL},
+    widget_health::{forget_widget_page_load, is_widget_healthy},
+    widget_interaction::{apply_window_interaction, is_widget_peeking, WidgetInteraction},
 };
 
 const WIDGET_BASE_WINDOW_WIDTH: f64 = 360.0;
 const WIDGET_BASE_WINDOW_HEIGHT: f64 = 760.0;
-const WIDGET_MIN_SCALE_PERCENT: u16 = 70;
-const WIDGET_MAX_SCALE_PERCENT: u16 = 300;
+const MONITOR_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+const QUICK_CAPTURE_WINDOW_WIDTH: f64 = 560.0;
+const QUICK_CAPTURE_WINDOW_HEIGHT: f64 = 148.0;
+
+/// How `ensure_widget_window` satisfied a request; logged so flashes and reloads can be traced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WidgetWindowOutcome {
+    Created,
+    Reused,
+    Recreated,
+}
 
-fn resolve_widget_url(app: &AppHandle) -> WebviewUrl {
+/// URL of the main window's page with `query` instead of its own, so auxiliary windows work in dev and bundles.
+fn resolve_mode_url(app: &AppHandle, query: &str) -> WebviewUrl {
     if let Some(main_window) = app.get_webview_window("main") {
         if let Ok(mut current_url) = main_window.url() {
-            current_url.set_query(Some("mode=widget"));
+            current_url.set_query(Some(query));
 
             if current_url.scheme() == "http" || current_url.scheme() == "https" {
                 return WebviewUrl::External(current_url);
@@ -27,7 +56,7 @@ fn normalize_widget_scale_percent(scale_percent: u16) -> u16 {
     scale_percent.clamp(WIDGET_MIN_SCALE_PERCENT, WIDGET_MAX_SCALE_PERCENT)
 }
 
-fn resolve_widget_window_logical_size(scale_percent: u16) -> (i32, i32) {
+pub(crate) fn resolve_widget_window_logical_size(scale_percent: u16) -> (i32, i32) {
     let normalized_scale = f64::from(normalize_widget_scale_percent(scale_percent)) / 100.0;
     (
         (WIDGET_BASE_WINDOW_WIDTH * normalized_scale).round() as i32,
@@ -43,7 +72,7 @@ fn set_widget_bounds_windows(
     y: i32,
     width: i32,
     height: i32,
-) -> Result<(), String> {
+) -> AppResult<()> {
     use windows_sys::Win32::UI::WindowsAndMessaging::{
         SetWindowPos, SWP_NOACTIVATE, SWP_NOOWNERZORDER, SWP_NOZORDER,
     };
@@ -53,11 +82,11 @@ fn set_widget_bounds_windows(
     let next_physical_size = tauri::LogicalSize::new(f64::from(width), f64::from(height))
         .to_physical::<u32>(scale_factor);
     let next_physical_width = i32::try_from(next_physical_size.width)
-        .map_err(|_| "widget width overflow while converting to physical pixels.".to_string())?;
+        .map_err(|_| AppError::internal("widget width overflow while converting to physical pixels."))?;
     let next_physical_height = i32::try_from(next_physical_size.height)
-        .map_err(|_| "widget height overflow while converting to physical pixels.".to_string())?;
+        .map_err(|_| AppError::internal("widget height overflow while converting to physical pixels."))?;
 
-    let hwnd = widget_window.hwnd().map_err(|error| error.to_string())?;
+    let hwnd = widget_window.hwnd()?;
     let succeeded = unsafe {
         SetWindowPos(
             hwnd.0 as *mut std::ffi::c_void,
@@ -71,7 +100,7 @@ fn set_widget_bounds_windows(
     };
 
     if succeeded == 0 {
-        return Err("SetWindowPos failed while resizing widget window.".to_string());
+        return Err(AppError::internal("SetWindowPos failed while resizing widget window."));
     }
 
     Ok(())
@@ -85,42 +114,46 @@ fn set_widget_bounds_windows(
     y: i32,
     width: i32,
     height: i32,
-) -> Result<(), String> {
+) -> AppResult<()> {
     widget_window
-        .set_size(tauri::LogicalSize::new(f64::from(width), f64::from(height)))
-        .map_err(|error| error.to_string())?;
+        .set_size(tauri::LogicalSize::new(f64::from(width), f64::from(height)))?;
     widget_window
-        .set_position(tauri::LogicalPosition::new(f64::from(x), f64::from(y)))
-        .map_err(|error| error.to_string())?;
+        .set_position(tauri::LogicalPosition::new(f64::from(x), f64::from(y)))?;
 
     Ok(())
 }
 
-pub fn resize_widget_window_atomic(app: &AppHandle, scale_percent: u16) -> Result<(), String> {
+/// Resizes the widget for `scale_percent`, keeping the `align` edge and the `vertical` edge where they are.
+pub fn resize_widget_window_atomic(
+    app: &AppHandle,
+    label: &str,
+    scale_percent: u16,
+    align: WidgetAlignMode,
+    vertical: WidgetVerticalAnchor,
+) -> AppResult<()> {
     let app_handle = app.clone();
+    let label = label.to_string();
     let normalized_scale_percent = normalize_widget_scale_percent(scale_percent);
-    let (sender, receiver) = std::sync::mpsc::channel::<Result<(), String>>();
+    let (sender, receiver) = std::sync::mpsc::channel::<AppResult<()>>();
 
     app.run_on_main_thread(move || {
-        let result = (|| -> Result<(), String> {
-            let Some(widget_window) = app_handle.get_webview_window("widget") else {
+        let result = (|| -> AppResult<()> {
+            let Some(widget_window) = app_handle.get_webview_window(&label) else {
                 return Ok(());
             };
 
-            let scale_factor = widget_window.scale_factor().map_err(|error| error.to_string())?;
-            let current_outer_position = widget_window
-                .outer_position()
-                .map_err(|error| error.to_string())?;
-            let current_outer_size = widget_window
-                .outer_size()
-                .map_err(|error| error.to_string())?;
-            let current_logical_position = current_outer_position.to_logical::<f64>(scale_factor);
-            let current_logical_size = current_outer_size.to_logical::<f64>(scale_factor);
-            let anchored_right = current_logical_position.x + current_logical_size.width;
+            let (current, scale_factor) = read_widget_logical_bounds(&widget_window)?;
             let (next_logical_width, next_logical_height) =
                 resolve_widget_window_logical_size(normalized_scale_percent);
-            let next_logical_x = (anchored_right - f64::from(next_logical_width)).round() as i32;
-            let next_logical_y = current_logical_position.y.round() as i32;
+            let next = resize_anchored(
+                current,
+                f64::from(next_logical_width),
+                f64::from(next_logical_height),
+                align,
+                vertical,
+            );
+            let next_logical_x = next.x as i32;
+            let next_logical_y = next.y as i32;
 
             set_widget_bounds_windows(
                 &widget_window,
@@ -134,71 +167,374 @@ pub fn resize_widget_window_atomic(app: &AppHandle, scale_percent: u16) -> Resul
         })();
 
         let _ = sender.send(result);
-    })
-    .map_err(|error| error.to_string())?;
+    })?;
 
     receiver
         .recv()
-        .map_err(|error| format!("failed to receive widget resize result: {error}"))?
+        .map_err(|error| AppError::internal(format!("failed to receive widget resize result: {error}")))?
 }
 
-pub fn ensure_widget_window(app: &AppHandle) -> Result<(), String> {
+/// Shows the widget window, creating it when missing. A live window is reused as-is so scroll and UI state survive;
+/// it is only rebuilt when its frontend never reported ready (see `widget_health`).
+pub fn ensure_widget_window(
+    app: &AppHandle,
+    label: &str,
+    widget: &WidgetInstanceConfig,
+) -> AppResult<WidgetWindowOutcome> {
     let app_handle = app.clone();
-    let (sender, receiver) = std::sync::mpsc::channel::<Result<(), String>>();
+    let thread_label = label.to_string();
+    let widget = widget.clone();
+    let (sender, receiver) = std::sync::mpsc::channel::<AppResult<WidgetWindowOutcome>>();
 
     app.run_on_main_thread(move || {
-        let result = (|| -> Result<(), String> {
-            if let Some(existing) = app_handle.get_webview_window("widget") {
-                println!("[widget] existing window found, destroying before recreate");
-                let _ = existing.destroy();
+        let label = thread_label;
+        let result = (|| -> AppResult<WidgetWindowOutcome> {
+            let mut outcome = WidgetWindowOutcome::Created;
+            if let Some(existing) = app_handle.get_webview_window(&label) {
+                if is_widget_healthy(&app_handle, &label) {
+                    println!("[{label}] existing window found, reusing");
+                    let interaction = WidgetInteraction::resolve(&widget, is_widget_peeking(&app_handle, &label));
+                    apply_window_interaction(&existing.as_ref().window(), interaction)?;
+                    existing.show()?;
+                    existing.unminimize()?;
+                    if !widget.locked {
+                        existing.set_focus()?;
+                    }
+                    return Ok(WidgetWindowOutcome::Reused);
+                }
+
+                println!("[{label}] existing window never became ready, destroying before recreate");
+                existing.destroy()?;
+                forget_widget_page_load(&app_handle, &label);
+                outcome = WidgetWindowOutcome::Recreated;
             }
 
-            let widget_url = resolve_widget_url(&app_handle);
-            println!("[widget] resolved url: {widget_url}");
-            println!("[widget] creating window...");
+            let widget_url = resolve_mode_url(&app_handle, "mode=widget");
+            println!("[{label}] resolved url: {widget_url}");
+            println!("[{label}] creating window...");
 
-            let widget_window = WebviewWindowBuilder::new(&app_handle, "widget", widget_url)
-                .title("Daily Widget")
-                .inner_size(360.0, 760.0)
+            let title = if label == PRIMARY_WIDGET_LABEL {
+                "Daily Widget".to_string()
+            } else {
+                format!("Daily Widget ({label})")
+            };
+            let mut builder = WebviewWindowBuilder::new(&app_handle, &label, widget_url)
+                .title(title)
                 .initialization_script("window.__DAILY_MODE__ = 'widget';")
-                .transparent(false)
-                .decorations(true)
-                .resizable(true)
-                .always_on_top(false)
-                .focused(true)
-                .skip_taskbar(false)
-                .on_page_load(|_window, payload| match payload.event() {
+                .transparent(true)
+                .decorations(false)
+                .shadow(false)
+                .resizable(false)
+                .always_on_top(!widget.desktop_layer)
+                .always_on_bottom(widget.desktop_layer)
+                .visible_on_all_workspaces(widget.desktop_layer)
+                .focused(!widget.locked && !widget.desktop_layer)
+                .skip_taskbar(true)
+                // Desktop-layer hints only take effect before the window is first mapped.
+                .visible(!widget.desktop_layer);
+            let restore_bounds = resolve_restore_bounds(&widget, &collect_monitor_areas(&app_handle));
+            match restore_bounds {
+                Some(bounds) => {
+                    println!("[{label}] restoring bounds: {bounds:?}");
+                    builder = builder
+                        .inner_size(bounds.width, bounds.height)
+                        .position(bounds.x, bounds.y);
+                }
+                None => {
+                    let (width, height) = resolve_widget_window_logical_size(widget.scale_percent);
+                    builder = builder.inner_size(f64::from(width), f64::from(height));
+                }
+            }
+
+            let widget_window = builder
+                .on_page_load(|window, payload| match payload.event() {
                     PageLoadEvent::Started => {
-                        println!("[widget] load started: {}", payload.url());
+                        println!("[{}] load started: {}", window.label(), payload.url());
                     }
                     PageLoadEvent::Finished => {
-                        println!("[widget] load finished: {}", payload.url());
+                        println!("[{}] load finished: {}", window.label(), payload.url());
                     }
                 })
-                .build()
-                .map_err(|error| error.to_string())?;
+                .build()?;
 
-            println!("[widget] window created");
+            println!("[{label}] window created");
+            if widget.desktop_layer {
+                let backend = apply_desktop_layer(&widget_window, restore_bounds)?;
+                log_project_event(
+                    &app_handle,
+                    "info",
+                    "widget.desktop_layer.applied",
+                    "Widget pinned to the desktop layer.",
+                    Some(serde_json::json!({ "widget": label, "backend": backend })),
+                );
+            }
+            let interaction = WidgetInteraction::resolve(&widget, is_widget_peeking(&app_handle, &label));
+            apply_window_interaction(&widget_window.as_ref().window(), interaction)?;
             let _ = widget_window.show();
-            Ok(())
+            Ok(outcome)
+        })();
+
+        let _ = sender.send(result);
+    })?;
+
+    let outcome = receiver
+        .recv()
+        .map_err(|error| AppError::internal(format!("failed to receive widget creation result: {error}")))??;
+    log_project_event(
+        app,
+        "info",
+        "widget.window.ensured",
+        "Widget window ensured.",
+        Some(serde_json::json!({ "widget": label, "outcome": outcome })),
+    );
+    Ok(outcome)
+}
+
+fn read_widget_logical_bounds(widget_window: &tauri::WebviewWindow) -> AppResult<(WidgetBounds, f64)> {
+    let scale_factor = widget_window.scale_factor()?;
+    let position = widget_window.outer_position()?.to_logical::<f64>(scale_factor);
+    let size = widget_window.outer_size()?.to_logical::<f64>(scale_factor);
+    Ok((
+        WidgetBounds {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        },
+        scale_factor,
+    ))
+}
+
+fn apply_widget_bounds(widget_window: &tauri::WebviewWindow, scale_factor: f64, bounds: WidgetBounds) -> AppResult<()> {
+    set_widget_bounds_windows(
+        widget_window,
+        scale_factor,
+        bounds.x as i32,
+        bounds.y as i32,
+        bounds.width as i32,
+        bounds.height as i32,
+    )
+}
+
+/// Moves the widget back onto a monitor when none of the connected monitors shows enough of it,
+/// then re-applies `dock` so a docked widget stays flush after a resolution change.
+/// Returns whether the widget was moved.
+pub fn revalidate_widget_placement(app: &AppHandle, label: &str, dock: Option<WidgetDock>) -> AppResult<bool> {
+    let app_handle = app.clone();
+    let label = label.to_string();
+    let (sender, receiver) = std::sync::mpsc::channel::<AppResult<bool>>();
+
+    app.run_on_main_thread(move || {
+        let result = (|| -> AppResult<bool> {
+            let Some(widget_window) = app_handle.get_webview_window(&label) else {
+                return Ok(false);
+            };
+
+            let (bounds, scale_factor) = read_widget_logical_bounds(&widget_window)?;
+            let monitors = collect_monitor_areas(&app_handle);
+            let mut next_bounds = relocate_if_hidden(bounds, &monitors).unwrap_or(bounds);
+            if let Some(dock) = dock {
+                if let Some(monitor) = monitor_for_bounds(&next_bounds, &monitors) {
+                    next_bounds = apply_dock(next_bounds, dock, monitor);
+                }
+            }
+            if next_bounds == bounds {
+                return Ok(false);
+            }
+            apply_widget_bounds(&widget_window, scale_factor, next_bounds)?;
+            Ok(true)
+        })();
+
+        let _ = sender.send(result);
+    })?;
+
+    receiver
+        .recv()
+        .map_err(|error| AppError::internal(format!("failed to receive widget placement result: {error}")))?
+}
+
+/// Snaps the widget flush against any work-area edge it was dropped near and returns the resulting dock.
+pub(crate) fn snap_widget_to_edges(app: &AppHandle, label: &str) -> AppResult<Option<WidgetDock>> {
+    let app_handle = app.clone();
+    let label = label.to_string();
+    let (sender, receiver) = std::sync::mpsc::channel::<AppResult<Option<WidgetDock>>>();
+
+    app.run_on_main_thread(move || {
+        let result = (|| -> AppResult<Option<WidgetDock>> {
+            let Some(widget_window) = app_handle.get_webview_window(&label) else {
+                return Ok(None);
+            };
+
+            let (bounds, scale_factor) = read_widget_logical_bounds(&widget_window)?;
+            let monitors = collect_monitor_areas(&app_handle);
+            let Some(monitor) = monitor_for_bounds(&bounds, &monitors) else {
+                return Ok(None);
+            };
+            let Some(dock) = detect_dock(&bounds, monitor) else {
+                return Ok(None);
+            };
+            let next_bounds = apply_dock(bounds, dock, monitor);
+            if next_bounds != bounds {
+                apply_widget_bounds(&widget_window, scale_factor, next_bounds)?;
+            }
+            Ok(Some(dock))
         })();
 
         let _ = sender.send(result);
-    })
-    .map_err(|error| error.to_string())?;
+    })?;
 
     receiver
         .recv()
-        .map_err(|error| format!("failed to receive widget creation result: {error}"))?
+        .map_err(|error| AppError::internal(format!("failed to receive widget snap result: {error}")))?
 }
 
-pub fn focus_main_window(app: &AppHandle) -> Result<(), String> {
+/// Sends the widget to the top-right corner of the named monitor, keeping its size.
+pub fn move_widget_to_monitor(app: &AppHandle, label: &str, monitor_name: &str) -> AppResult<()> {
+    let app_handle = app.clone();
+    let label = label.to_string();
+    let monitor_name = monitor_name.to_string();
+    let (sender, receiver) = std::sync::mpsc::channel::<AppResult<()>>();
+
+    app.run_on_main_thread(move || {
+        let result = (|| -> AppResult<()> {
+            let Some(widget_window) = app_handle.get_webview_window(&label) else {
+                return Err(AppError::window_unavailable(&label));
+            };
+            let monitors = collect_monitor_areas(&app_handle);
+            let Some(monitor) = monitors
+                .iter()
+                .find(|monitor| monitor.name.as_deref() == Some(monitor_name.as_str()))
+            else {
+                return Err(AppError::not_found(format!("monitor `{monitor_name}` is not connected"))
+                    .with_context("monitor", monitor_name.as_str()));
+            };
+
+            let (bounds, scale_factor) = read_widget_logical_bounds(&widget_window)?;
+            apply_widget_bounds(&widget_window, scale_factor, anchor_top_right(monitor, bounds.width, bounds.height))
+        })();
+
+        let _ = sender.send(result);
+    })?;
+
+    receiver
+        .recv()
+        .map_err(|error| AppError::internal(format!("failed to receive widget move result: {error}")))?
+}
+
+/// Hides the widget window `label` if it is open. Hiding goes straight to the window, so it works even when the
+/// widget page itself no longer responds.
+pub fn hide_widget_window(app: &AppHandle, label: &str) -> AppResult<()> {
+    if let Some(widget_window) = app.get_webview_window(label) {
+        widget_window.hide()?;
+    }
+    Ok(())
+}
+
+/// Destroys the widget window `label`, if it is open.
+pub fn close_widget_window(app: &AppHandle, label: &str) -> AppResult<()> {
+    let Some(widget_window) = app.get_webview_window(label) else {
+        return Ok(());
+    };
+
+    widget_window.destroy()?;
+    Ok(())
+}
+
+/// Lists connected monitors in logical coordinates, primary monitor first.
+pub fn list_monitors(app: &AppHandle) -> Vec<MonitorArea> {
+    collect_monitor_areas(app)
+}
+
+/// Polls the monitor layout, since no window event reports a monitor being connected or removed,
+/// and pulls every widget back into view after each change.
+pub fn start_monitor_watcher(app: &AppHandle) -> AppResult<()> {
+    let app_handle = app.clone();
+    std::thread::Builder::new()
+        .name("daily-monitor-watcher".to_string())
+        .spawn(move || {
+            let mut previous = collect_monitor_areas(&app_handle);
+            loop {
+                std::thread::sleep(MONITOR_WATCH_INTERVAL);
+                let current = collect_monitor_areas(&app_handle);
+                if current == previous {
+                    continue;
+                }
+
+                let config = app_handle
+                    .try_state::<ConfigState>()
+                    .map(|state| state.snapshot())
+                    .unwrap_or_default();
+                for label in config.widget_labels() {
+                    let dock = config.widget(&label).and_then(|widget| widget.dock);
+                    let moved = revalidate_widget_placement(&app_handle, &label, dock);
+                    log_project_event(
+                        &app_handle,
+                        "info",
+                        "widget.monitors.changed",
+                        "Monitor layout changed; widget placement revalidated.",
+                        Some(serde_json::json!({
+                            "widget": label,
+                            "monitors": current.iter().map(|monitor| monitor.name.clone()).collect::<Vec<_>>(),
+                            "moved": moved.as_ref().ok(),
+                            "error": moved.as_ref().err(),
+                        })),
+                    );
+                }
+                previous = current;
+            }
+        })
+        .map_err(|error| AppError::internal(format!("failed to spawn monitor watcher: {error}")))?;
+
+    Ok(())
+}
+
+/// Brings the main window back to the front. Closing it only minimizes it, and a minimized window does not take focus.
+pub fn focus_main_window(app: &AppHandle) -> AppResult<()> {
     let Some(window) = app.get_webview_window("main") else {
-        return Err("Main window is not available.".to_string());
+        return Err(AppError::window_unavailable("main"));
     };
 
-    window.show().map_err(|error| error.to_string())?;
-    window.set_focus().map_err(|error| error.to_string())?;
+    window.show()?;
+    window.unminimize()?;
+    window.set_focus()?;
 
     Ok(())
 }
+
+/// Shows the frameless quick-capture window centered on the active monitor. An open capture window is focused and
+/// switched to `kind` instead of being rebuilt, so a half-typed line survives a second press of the shortcut.
+pub fn open_quick_capture_window(app: &AppHandle, kind: CaptureKind) -> AppResult<()> {
+    let app_handle = app.clone();
+    let (sender, receiver) = std::sync::mpsc::channel::<AppResult<()>>();
+
+    app.run_on_main_thread(move || {
+        let result = (|| -> AppResult<()> {
+            if let Some(existing) = app_handle.get_webview_window(QUICK_CAPTURE_LABEL) {
+                existing.emit_to(QUICK_CAPTURE_LABEL, EVENT_QUICK_CAPTURE_KIND, kind)?;
+                existing.show()?;
+                existing.set_focus()?;
+                return Ok(());
+            }
+
+            let capture_url = resolve_mode_url(&app_handle, &format!("mode=capture&kind={}", kind.as_str()));
+            WebviewWindowBuilder::new(&app_handle, QUICK_CAPTURE_LABEL, capture_url)
+                .title("Daily Quick Capture")
+                .initialization_script("window.__DAILY_MODE__ = 'capture';")
+                .inner_size(QUICK_CAPTURE_WINDOW_WIDTH, QUICK_CAPTURE_WINDOW_HEIGHT)
+                .center()
+                .decorations(false)
+                .resizable(false)
+                .always_on_top(true)
+                .skip_taskbar(true)
+                .focused(true)
+                .build()?;
+            Ok(())
+        })();
+
+        let _ = sender.send(result);
+    })?;
+
+    receiver
+        .recv()
+        .map_err(|error| AppError::internal(format!("failed to receive quick capture window result: {error}")))?
+}
@@ -1,4 +1,5 @@
 import { useEffect, useMemo } from "react";
+import { QuickCapturePage } from "./features/capture/QuickCapturePage";
 import { StandardModePage } from "./features/standard/StandardModePage";
 import { WidgetModePage } from "./features/widget/WidgetModePage";
 import { useTodoStore } from "./shared/state/useTodoStore";
@@ -11,8 +12,15 @@ function App() {
   const initializeData = useTodoStore((state) => state.initializeData);
 
   useEffect(() => {
-    void initializeData();
-  }, [initializeData]);
+    // The capture window only talks to the backend, which owns the write to data.json.
+    if (mode !== "capture") {
+      void initializeData();
+    }
+  }, [initializeData, mode]);
+
+  if (mode === "capture") {
+    return <QuickCapturePage />;
+  }
 
   if (!dataInitialized) {
     return <div className="app-loading">Loading...</div>;
@@ -0,0 +1,109 @@
+import { type FormEvent, type KeyboardEvent, useEffect, useRef, useState } from "react";
+import { toAppCommandError } from "../../shared/tauri/errors";
+import { closeCurrentWindow, onQuickCaptureKind, submitQuickCapture } from "../../shared/tauri/window";
+import type { CaptureKind } from "../../shared/types/todo";
+
+const captureKindLabel: Record<CaptureKind, string> = {
+  task: "Task",
+  spark: "Spark",
+};
+
+const captureHint: Record<CaptureKind, string> = {
+  task: "#tag  @2026-10-20 / @today / @tomorrow  then the title",
+  spark: "!global-name  then the idea",
+};
+
+function initialCaptureKind(): CaptureKind {
+  return new URLSearchParams(window.location.search).get("kind") === "task" ? "task" : "spark";
+}
+
+export function QuickCapturePage() {
+  const [kind, setKind] = useState<CaptureKind>(initialCaptureKind);
+  const [input, setInput] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const [submitting, setSubmitting] = useState(false);
+  const inputRef = useRef<HTMLInputElement>(null);
+
+  useEffect(() => {
+    inputRef.current?.focus();
+  }, [kind]);
+
+  useEffect(() => {
+    let isDisposed = false;
+    let unlisten: (() => void) | undefined;
+    void onQuickCaptureKind((nextKind) => {
+      setKind(nextKind);
+      setError(null);
+    }).then((dispose) => {
+      if (isDisposed) {
+        dispose();
+        return;
+      }
+      unlisten = dispose;
+    });
+
+    return () => {
+      isDisposed = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  const onSubmit = (event: FormEvent) => {
+    event.preventDefault();
+    if (!input.trim() || submitting) {
+      return;
+    }
+
+    setSubmitting(true);
+    setError(null);
+    void submitQuickCapture(input, kind)
+      .then(() => {
+        setInput("");
+      })
+      .catch((raw) => {
+        setError(toAppCommandError(raw).message);
+      })
+      .finally(() => {
+        setSubmitting(false);
+      });
+  };
+
+  const onKeyDown = (event: KeyboardEvent<HTMLInputElement>) => {
+    if (event.key === "Escape") {
+      event.preventDefault();
+      void closeCurrentWindow();
+    } else if (event.key === "Tab") {
+      event.preventDefault();
+      setKind((current) => (current === "task" ? "spark" : "task"));
+    }
+  };
+
+  return (
+    <form className="quick-capture" onSubmit={onSubmit}>
+      <div className="quick-capture-row">
+        <button
+          type="button"
+          className={`quick-capture-kind quick-capture-kind-${kind}`}
+          title="Switch with Tab"
+          onClick={() => setKind((current) => (current === "task" ? "spark" : "task"))}
+        >
+          {captureKindLabel[kind]}
+        </button>
+        <input
+          ref={inputRef}
+          className="quick-capture-input"
+          value={input}
+          placeholder={kind === "task" ? "Add a task..." : "Capture a spark..."}
+          disabled={submitting}
+          onChange={(event) => setInput(event.target.value)}
+          onKeyDown={onKeyDown}
+        />
+      </div>
+      <p className={error ? "quick-capture-hint quick-capture-error" : "quick-capture-hint"}>
+        {error ?? `${captureHint[kind]} · Enter to save · Esc to close`}
+      </p>
+    </form>
+  );
+}
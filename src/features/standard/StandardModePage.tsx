@@ -1,28 +1,31 @@
 import { useEffect, useMemo, useState } from "react";
-import {
-  getWidgetHoverOpacity,
-  getWidgetOpacity,
-  getWidgetScale,
-  setWidgetHoverOpacity as persistWidgetHoverOpacity,
-  setWidgetOpacity as persistWidgetOpacity,
-  setWidgetScale as persistWidgetScale,
-} from "../../shared/settings/widget";
+import { takeLegacyWidgetOpacity, takeLegacyWidgetScale } from "../../shared/settings/widget";
+import { loadPersistedAppConfig, updatePersistedAppConfig } from "../../shared/tauri/storage";
 import { getVisibleTasks, useTodoStore } from "../../shared/state/useTodoStore";
 import {
-  emitWidgetSetLock,
   getCurrentWindowLabelSafe,
-  onWidgetAlignmentUpdated,
+  listGlobalShortcuts,
+  listMonitors,
+  moveWidgetToMonitor,
+  onAppConfigUpdated,
+  onQuickCaptureSaved,
+  onTrayNavigate,
+  onWidgetHealth,
   onWidgetSetVisibility,
   onTaskStatusUpdated,
   onTasksStateUpdated,
-  onWidgetTaskViewUpdated,
   onWidgetSetLock,
+  peekWidget,
+  primaryWidgetLabel,
   syncWidgetLockedState,
   syncWidgetVisibilityState,
   resetWidgetWindowPosition,
-  setWidgetWindowVisibility,
+  setWidgetClickThrough,
+  setWidgetContentOpacity,
+  setWidgetDesktopLayer,
 } from "../../shared/tauri/window";
-import type { StandardSection } from "../../shared/types/todo";
+import { clearCrashReports, listCrashReports, readCrashReport } from "../../shared/tauri/logs";
+import type { CrashReportSummary, MonitorArea, StandardSection } from "../../shared/types/todo";
 import { BottomNav } from "../../shared/ui/BottomNav";
 import { FilterTabs } from "../../shared/ui/FilterTabs";
 import { TaskRow } from "../../shared/ui/TaskRow";
@@ -60,10 +63,15 @@ function ComingSoonSection({ section }: { section: StandardSection }) {
 
 export function StandardModePage() {
   const [windowError, setWindowError] = useState<string | null>(null);
-  const [widgetOpacity, setWidgetOpacity] = useState(() => getWidgetOpacity());
-  const [widgetHoverOpacity, setWidgetHoverOpacity] = useState(() => getWidgetHoverOpacity());
-  const [widgetScale, setWidgetScale] = useState(() => getWidgetScale());
+  const [widgetOpacity, setWidgetOpacity] = useState(20);
+  const [widgetHoverOpacity, setWidgetHoverOpacity] = useState(38);
+  const [widgetClickThrough, setWidgetClickThroughState] = useState(true);
+  const [widgetDesktopLayer, setWidgetDesktopLayerState] = useState(false);
+  const [widgetScale, setWidgetScale] = useState(100);
   const [activeSection, setActiveSection] = useState<StandardSection>("home");
+  const [crashReports, setCrashReports] = useState<CrashReportSummary[]>([]);
+  const [crashReportDetail, setCrashReportDetail] = useState<string | null>(null);
+  const [monitors, setMonitors] = useState<MonitorArea[]>([]);
 
   const filter = useTodoStore((state) => state.filter);
   const sortMode = useTodoStore((state) => state.sortMode);
@@ -72,8 +80,9 @@ export function StandardModePage() {
   const toggleTask = useTodoStore((state) => state.toggleTask);
   const applySyncedTaskStatus = useTodoStore((state) => state.applySyncedTaskStatus);
   const applySyncedTasksState = useTodoStore((state) => state.applySyncedTasksState);
-  const applySyncedWidgetTaskView = useTodoStore((state) => state.applySyncedWidgetTaskView);
-  const applySyncedWidgetAlignment = useTodoStore((state) => state.applySyncedWidgetAlignment);
+  const applyCapturedItem = useTodoStore((state) => state.applyCapturedItem);
+  const selectGlobal = useTodoStore((state) => state.selectGlobal);
+  const applySyncedAppConfig = useTodoStore((state) => state.applySyncedAppConfig);
   const widgetLocked = useTodoStore((state) => state.widgetLocked);
   const widgetVisible = useTodoStore((state) => state.widgetVisible);
   const widgetShowAllTasks = useTodoStore((state) => state.widgetShowAllTasks);
@@ -96,19 +105,53 @@ export function StandardModePage() {
     const nextVisible = !widgetVisible;
     try {
       setWindowError(null);
-      await setWidgetWindowVisibility(nextVisible);
+      await syncWidgetVisibilityState(nextVisible, primaryWidgetLabel);
       setWidgetVisible(nextVisible);
-      await syncWidgetVisibilityState(nextVisible);
     } catch (error) {
       setWindowError(error instanceof Error ? error.message : "Unable to update widget visibility.");
     }
   };
 
+  const onShowCrashReport = async () => {
+    const latest = crashReports[0];
+    if (!latest) {
+      return;
+    }
+
+    try {
+      const report = await readCrashReport(latest.fileName);
+      setCrashReportDetail(JSON.stringify(report, null, 2));
+    } catch (error) {
+      setWindowError(error instanceof Error ? error.message : "Unable to read crash report.");
+    }
+  };
+
+  const onClearCrashReports = async () => {
+    try {
+      await clearCrashReports();
+      setCrashReports([]);
+      setCrashReportDetail(null);
+    } catch (error) {
+      setWindowError(error instanceof Error ? error.message : "Unable to clear crash reports.");
+    }
+  };
+
+  useEffect(() => {
+    void listCrashReports()
+      .then(setCrashReports)
+      .catch(() => setCrashReports([]));
+  }, []);
+
+  useEffect(() => {
+    void listMonitors()
+      .then(setMonitors)
+      .catch(() => setMonitors([]));
+  }, [widgetVisible]);
+
   const onToggleWidgetLock = () => {
     const nextLocked = !widgetLocked;
     setWidgetLocked(nextLocked);
-    void syncWidgetLockedState(nextLocked);
-    void emitWidgetSetLock(nextLocked);
+    void syncWidgetLockedState(nextLocked, primaryWidgetLabel);
   };
 
   const onToggleWidgetTaskView = () => {
@@ -141,23 +184,68 @@ export function StandardModePage() {
   }, [setWidgetLocked]);
 
   useEffect(() => {
-    if (!widgetVisible) {
-      return;
-    }
+    let isDisposed = false;
+    let unlisten: (() => void) | undefined;
+    void onWidgetSetVisibility((visible) => {
+      setWidgetVisible(visible);
+    }).then((dispose) => {
+      if (isDisposed) {
+        dispose();
+        return;
+      }
+      unlisten = dispose;
+    });
 
-    void setWidgetWindowVisibility(true).catch(() => {
-      setWindowError("Unable to restore widget window.");
+    return () => {
+      isDisposed = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [setWidgetVisible]);
+
+  useEffect(() => {
+    let isDisposed = false;
+    let unlisten: (() => void) | undefined;
+    void onWidgetHealth((event) => {
+      if (event.status === "recovering" || event.status === "gaveUp") {
+        setWindowError(event.message);
+      } else {
+        setWindowError(null);
+      }
+    }).then((dispose) => {
+      if (isDisposed) {
+        dispose();
+        return;
+      }
+      unlisten = dispose;
     });
-  }, [widgetVisible]);
+
+    return () => {
+      isDisposed = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  useEffect(() => {
+    void listGlobalShortcuts()
+      .then((statuses) => {
+        const failed = statuses.filter((status) => status.status !== "registered");
+        if (failed.length > 0) {
+          const names = failed.map((status) => `${status.action} (${status.accelerator})`).join(", ");
+          setWindowError(`Some global shortcuts could not be registered: ${names}.`);
+        }
+      })
+      .catch(() => {});
+  }, []);
 
   useEffect(() => {
     let isDisposed = false;
     let unlisten: (() => void) | undefined;
-    void onWidgetSetVisibility((visible) => {
-      setWidgetVisible(visible);
-      void setWidgetWindowVisibility(visible).catch(() => {
-        setWindowError("Unable to apply widget visibility from tray.");
-      });
+    void onQuickCaptureSaved((payload) => {
+      applyCapturedItem(payload);
     }).then((dispose) => {
       if (isDisposed) {
         dispose();
@@ -172,10 +260,34 @@ export function StandardModePage() {
         unlisten();
       }
     };
-  }, [setWidgetVisible]);
+  }, [applyCapturedItem]);
+
+  useEffect(() => {
+    let isDisposed = false;
+    let unlisten: (() => void) | undefined;
+    void onTrayNavigate(({ section, id }) => {
+      setActiveSection(section);
+      if (section === "global") {
+        selectGlobal(id);
+      }
+    }).then((dispose) => {
+      if (isDisposed) {
+        dispose();
+        return;
+      }
+      unlisten = dispose;
+    });
+
+    return () => {
+      isDisposed = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [selectGlobal]);
 
   useEffect(() => {
-    void syncWidgetLockedState(widgetLocked);
+    void syncWidgetLockedState(widgetLocked, primaryWidgetLabel);
   }, [widgetLocked]);
 
   useEffect(() => {
@@ -183,8 +295,6 @@ export function StandardModePage() {
     let isDisposed = false;
     let unlistenTaskStatus: (() => void) | undefined;
     let unlistenTaskState: (() => void) | undefined;
-    let unlistenWidgetTaskView: (() => void) | undefined;
-    let unlistenWidgetAlignment: (() => void) | undefined;
     void onTaskStatusUpdated((payload) => {
       if (payload.sourceWindowLabel && payload.sourceWindowLabel === currentLabel) {
         return;
@@ -211,65 +321,121 @@ export function StandardModePage() {
       unlistenTaskState = dispose;
     });
 
-    void onWidgetTaskViewUpdated((payload) => {
-      if (payload.sourceWindowLabel && payload.sourceWindowLabel === currentLabel) {
-        return;
+    return () => {
+      isDisposed = true;
+      if (unlistenTaskStatus) {
+        unlistenTaskStatus();
       }
-      applySyncedWidgetTaskView(payload);
-    }).then((dispose) => {
-      if (isDisposed) {
-        dispose();
-        return;
+      if (unlistenTaskState) {
+        unlistenTaskState();
       }
-      unlistenWidgetTaskView = dispose;
-    });
+    };
+  }, [applySyncedTaskStatus, applySyncedTasksState]);
 
-    void onWidgetAlignmentUpdated((payload) => {
-      if (payload.sourceWindowLabel && payload.sourceWindowLabel === currentLabel) {
+  useEffect(() => {
+    let isDisposed = false;
+    void loadPersistedAppConfig().then(async (config) => {
+      if (isDisposed || !config) {
         return;
       }
-      applySyncedWidgetAlignment(payload);
+
+      const legacy = takeLegacyWidgetOpacity();
+      const opacityPercent = legacy?.opacityPercent ?? config.widgetOpacityPercent;
+      const hoverOpacityPercent = legacy?.hoverOpacityPercent ?? config.widgetHoverOpacityPercent;
+      setWidgetOpacity(opacityPercent);
+      setWidgetHoverOpacity(hoverOpacityPercent);
+      setWidgetClickThroughState(config.widgetClickThrough);
+      setWidgetDesktopLayerState(config.widgetDesktopLayer);
+      const legacyScale = takeLegacyWidgetScale();
+      setWidgetScale(legacyScale ?? config.widgetScalePercent);
+      if (legacy) {
+        await setWidgetContentOpacity(opacityPercent, hoverOpacityPercent);
+      }
+      if (legacyScale !== null) {
+        await updatePersistedAppConfig({ widgetScalePercent: legacyScale });
+      }
+    });
+
+    return () => {
+      isDisposed = true;
+    };
+  }, []);
+
+  // The widget's lock, task view and alignment live in config.json; every change to it is broadcast.
+  useEffect(() => {
+    let isDisposed = false;
+    let unlisten: (() => void) | undefined;
+    void onAppConfigUpdated((config) => {
+      applySyncedAppConfig(config);
+      setWidgetScale(config.widgetScalePercent);
     }).then((dispose) => {
       if (isDisposed) {
         dispose();
         return;
       }
-      unlistenWidgetAlignment = dispose;
+      unlisten = dispose;
     });
 
     return () => {
       isDisposed = true;
-      if (unlistenTaskStatus) {
-        unlistenTaskStatus();
-      }
-      if (unlistenTaskState) {
-        unlistenTaskState();
-      }
-      if (unlistenWidgetTaskView) {
-        unlistenWidgetTaskView();
-      }
-      if (unlistenWidgetAlignment) {
-        unlistenWidgetAlignment();
+      if (unlisten) {
+        unlisten();
       }
     };
-  }, [applySyncedTaskStatus, applySyncedTasksState, applySyncedWidgetTaskView, applySyncedWidgetAlignment]);
+  }, [applySyncedAppConfig]);
 
   const onWidgetOpacityChange = (value: number) => {
     setWidgetOpacity(value);
-    persistWidgetOpacity(value);
+    void setWidgetContentOpacity(value).catch(() => setWindowError("Unable to update widget opacity."));
   };
 
   const onWidgetHoverOpacityChange = (value: number) => {
     setWidgetHoverOpacity(value);
-    persistWidgetHoverOpacity(value);
+    void setWidgetContentOpacity(widgetOpacity, value).catch(() => setWindowError("Unable to update widget opacity."));
+  };
+
+  const onToggleWidgetClickThrough = async () => {
+    const nextClickThrough = !widgetClickThrough;
+    try {
+      setWindowError(null);
+      await setWidgetClickThrough(nextClickThrough);
+      setWidgetClickThroughState(nextClickThrough);
+    } catch (error) {
+      setWindowError(error instanceof Error ? error.message : "Unable to update widget click-through.");
+    }
+  };
+
+  const onToggleWidgetDesktopLayer = async () => {
+    const nextDesktopLayer = !widgetDesktopLayer;
+    try {
+      setWindowError(null);
+      await setWidgetDesktopLayer(nextDesktopLayer);
+      setWidgetDesktopLayerState(nextDesktopLayer);
+    } catch (error) {
+      setWindowError(error instanceof Error ? error.message : "Unable to change the widget layer.");
+    }
+  };
+
+  const onPeekWidgets = async () => {
+    try {
+      setWindowError(null);
+      await peekWidget();
+    } catch (error) {
+      setWindowError(error instanceof Error ? error.message : "Unable to unlock widgets temporarily.");
+    }
   };
   const onWidgetScaleChange = (value: number) => {
     setWidgetScale(value);
-    persistWidgetScale(value);
+    void updatePersistedAppConfig({ widgetScalePercent: value }).catch(() => setWindowError("Unable to update widget scale."));
   };
-  const onResetWidgetScale = () => {
-    setWidgetScale(100);
-    persistWidgetScale(100);
+  const onResetWidgetScale = () => onWidgetScaleChange(100);
+  const onMoveWidgetToMonitor = async (monitorName: string) => {
+    try {
+      setWindowError(null);
+      await moveWidgetToMonitor(monitorName);
+    } catch (error) {
+      setWindowError(error instanceof Error ? error.message : "Unable to move widget to that monitor.");
+    }
   };
   const onResetWidgetPosition = async () => {
     try {
@@ -345,6 +511,36 @@ export function StandardModePage() {
           <button className="sidebar-scale-reset-button" onClick={() => void onResetWidgetPosition()} type="button">
             Reset Widget Position
           </button>
+          <button className="sidebar-scale-reset-button" onClick={() => void onToggleWidgetClickThrough()} type="button">
+            {widgetClickThrough ? "Locked Widget: Click-through" : "Locked Widget: Blocks Clicks"}
+          </button>
+          <button className="sidebar-scale-reset-button" onClick={() => void onToggleWidgetDesktopLayer()} type="button">
+            {widgetDesktopLayer ? "Widget Layer: Desktop" : "Widget Layer: Always on Top"}
+          </button>
+          {widgetLocked ? (
+            <button className="sidebar-scale-reset-button" onClick={() => void onPeekWidgets()} type="button">
+              Unlock Widgets for 10s
+            </button>
+          ) : null}
+          {widgetVisible && monitors.length > 1 ? (
+            <select
+              className="sidebar-monitor-select"
+              aria-label="Move widget to monitor"
+              value=""
+              onChange={(event) => void onMoveWidgetToMonitor(event.currentTarget.value)}
+            >
+              <option value="" disabled>
+                Move Widget to Monitor…
+              </option>
+              {monitors.map((monitor, index) =>
+                monitor.name ? (
+                  <option key={monitor.name} value={monitor.name}>
+                    {index === 0 ? `${monitor.name} (primary)` : monitor.name}
+                  </option>
+                ) : null,
+              )}
+            </select>
+          ) : null}
           <button className="sidebar-widget-button" onClick={onToggleWidgetVisibility} type="button">
             {widgetVisible ? "Hide Widget" : "Show Widget"}
           </button>
@@ -371,6 +567,18 @@ export function StandardModePage() {
           </header>
 
           {windowError ? <p className="standard-error">{windowError}</p> : null}
+          {crashReports.length > 0 ? (
+            <div className="standard-error-row" role="alert">
+              <p className="standard-error">Daily crashed during a previous run: {crashReports[0].message}</p>
+              <button className="standard-error-clear" onClick={() => void onShowCrashReport()} type="button">
+                Show
+              </button>
+              <button className="standard-error-clear" onClick={() => void onClearCrashReports()} type="button">
+                Clear
+              </button>
+            </div>
+          ) : null}
+          {crashReportDetail ? <pre className="crash-report-detail">{crashReportDetail}</pre> : null}
           {persistWriteError ? (
             <div className="standard-error-row" role="alert">
               <p className="standard-error">{persistWriteError}</p>
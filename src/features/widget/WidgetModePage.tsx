@@ -1,80 +1,95 @@
 ﻿import { type CSSProperties, useEffect, useMemo, useState } from "react";
-import {
-  getWidgetHoverOpacity,
-  getWidgetOpacity,
-  getWidgetScale,
-  setWidgetPosition,
-  watchWidgetHoverOpacity,
-  watchWidgetOpacity,
-  watchWidgetScale,
-} from "../../shared/settings/widget";
 import {
   applyWidgetLockState,
   applyWidgetScaleWindowSize,
-  emitWidgetSetLock,
   focusMainWindow,
+  getCurrentWidgetLabel,
   getCurrentWindowLabelSafe,
+  notifyWidgetReady,
+  onAppConfigUpdated,
+  onQuickCaptureSaved,
   onTaskStatusUpdated,
   onTasksStateUpdated,
-  onWidgetAlignmentUpdated,
-  onWidgetForceUnlock,
-  onWidgetMoved,
-  onWidgetSetLock,
-  onWidgetTaskViewUpdated,
+  onWidgetPeekState,
+  primaryWidgetLabel,
   startWidgetDragging,
   syncWidgetLockedState,
+  widgetConfigFor,
+  widgetConfigPatch,
 } from "../../shared/tauri/window";
-import { saveWidgetPositionToPersistedAppConfig } from "../../shared/tauri/storage";
-import { getWidgetTasks, useTodoStore } from "../../shared/state/useTodoStore";
+import { loadPersistedAppConfig, updatePersistedAppConfig } from "../../shared/tauri/storage";
+import { getWidgetViewTasks, useTodoStore } from "../../shared/state/useTodoStore";
+import type { PersistedAppConfig, WidgetInstanceConfig, WidgetView } from "../../shared/types/todo";
 import { WidgetTaskRow } from "./WidgetTaskRow";
 
+const widgetViewShortLabel: Record<WidgetView["kind"], string> = {
+  active: "Act",
+  all: "All",
+  today: "Day",
+  global: "Glb",
+};
+
+const defaultWidgetView: WidgetView = { kind: "active" };
+
 export function WidgetModePage() {
-  const widgetLocked = useTodoStore((state) => state.widgetLocked);
-  const widgetShowAllTasks = useTodoStore((state) => state.widgetShowAllTasks);
-  const widgetAlignMode = useTodoStore((state) => state.widgetAlignMode);
-  const setWidgetLocked = useTodoStore((state) => state.setWidgetLocked);
-  const setWidgetShowAllTasks = useTodoStore((state) => state.setWidgetShowAllTasks);
-  const setWidgetAlignMode = useTodoStore((state) => state.setWidgetAlignMode);
-  const setPersistWriteError = useTodoStore((state) => state.setPersistWriteError);
+  // Every widget, the primary one included, keeps its state in config.json and follows `app-config-updated`.
+  const [widgetLabel] = useState(() => getCurrentWidgetLabel() ?? primaryWidgetLabel);
+  const [widgetConfig, setWidgetConfig] = useState<WidgetInstanceConfig | null>(null);
+
   const toggleTask = useTodoStore((state) => state.toggleTask);
   const applySyncedTaskStatus = useTodoStore((state) => state.applySyncedTaskStatus);
   const applySyncedTasksState = useTodoStore((state) => state.applySyncedTasksState);
-  const applySyncedWidgetTaskView = useTodoStore((state) => state.applySyncedWidgetTaskView);
-  const applySyncedWidgetAlignment = useTodoStore((state) => state.applySyncedWidgetAlignment);
+  const applyCapturedItem = useTodoStore((state) => state.applyCapturedItem);
   const tasks = useTodoStore((state) => state.tasks);
+  const sparks = useTodoStore((state) => state.sparks);
   const sortMode = useTodoStore((state) => state.sortMode);
 
-  const [widgetOpacity, setWidgetOpacity] = useState(() => getWidgetOpacity());
-  const [widgetHoverOpacity, setWidgetHoverOpacity] = useState(() => getWidgetHoverOpacity());
-  const [widgetScale, setWidgetScale] = useState(() => getWidgetScale());
+  const [widgetPeeking, setWidgetPeeking] = useState(false);
   const [widgetHovered, setWidgetHovered] = useState(false);
 
-  const visibleTasks = useMemo(() => getWidgetTasks(tasks, sortMode, widgetShowAllTasks), [tasks, sortMode, widgetShowAllTasks]);
-  const activeBackgroundOpacity = !widgetLocked && widgetHovered ? widgetHoverOpacity : widgetOpacity;
-  const activeScale = widgetScale / 100;
+  const savedWidgetLocked = widgetConfig?.locked ?? false;
+  // A peek makes a locked widget interactive for a few seconds without changing its saved lock.
+  const widgetLocked = savedWidgetLocked && !widgetPeeking;
+  const widgetAlignMode = widgetConfig?.alignMode ?? "right";
+  const widgetView = widgetConfig?.view ?? defaultWidgetView;
+  const widgetScalePercent = widgetConfig?.scalePercent;
+  const canToggleWidgetView = widgetView.kind === "active" || widgetView.kind === "all";
+
+  const visibleTasks = useMemo(
+    () => getWidgetViewTasks(tasks, sparks, sortMode, widgetView),
+    [tasks, sparks, sortMode, widgetView],
+  );
+  const activeBackgroundOpacity = !widgetLocked && widgetHovered
+    ? (widgetConfig?.hoverOpacityPercent ?? 38)
+    : (widgetConfig?.opacityPercent ?? 20);
+  const activeScale = (widgetScalePercent ?? 100) / 100;
+
+  const updateWidgetConfig = (patch: Partial<WidgetInstanceConfig>) => {
+    setWidgetConfig((current) => (current ? { ...current, ...patch } : current));
+    void updatePersistedAppConfig(widgetConfigPatch(widgetLabel, patch));
+  };
 
   const onToggleWidgetLock = () => {
     const nextLocked = !widgetLocked;
-    setWidgetLocked(nextLocked);
+    setWidgetConfig((current) => (current ? { ...current, locked: nextLocked } : current));
     void syncWidgetLockedState(nextLocked);
-    void emitWidgetSetLock(nextLocked);
   };
 
   const onToggleWidgetTaskView = () => {
-    if (widgetLocked) {
+    if (widgetLocked || !canToggleWidgetView) {
       return;
     }
-    setWidgetShowAllTasks(!widgetShowAllTasks);
+    updateWidgetConfig({ view: { kind: widgetView.kind === "all" ? "active" : "all" } });
   };
 
   const onToggleWidgetAlignMode = () => {
-    const nextAlignMode = widgetAlignMode === "right" ? "left" : "right";
-    setWidgetAlignMode(nextAlignMode);
+    updateWidgetConfig({ alignMode: widgetAlignMode === "right" ? "left" : "right" });
   };
 
   useEffect(() => {
     document.documentElement.classList.add("widget-mode");
     document.body.classList.add("widget-mode");
+    void notifyWidgetReady().catch(() => {});
 
     return () => {
       document.documentElement.classList.remove("widget-mode");
@@ -83,81 +98,67 @@ export function WidgetModePage() {
   }, []);
 
   useEffect(() => {
-    void applyWidgetLockState(widgetLocked);
-    void syncWidgetLockedState(widgetLocked);
-  }, [widgetLocked]);
-
-  useEffect(() => {
-    void applyWidgetScaleWindowSize(widgetScale);
-  }, [widgetScale]);
-
-  useEffect(() => {
-    const stopWatchOpacity = watchWidgetOpacity((nextOpacity) => {
-      setWidgetOpacity(nextOpacity);
-    });
-    const stopWatchHoverOpacity = watchWidgetHoverOpacity((nextOpacity) => {
-      setWidgetHoverOpacity(nextOpacity);
-    });
-    const stopWatchScale = watchWidgetScale((nextScale) => {
-      setWidgetScale(nextScale);
-    });
+    const applyConfig = (config: PersistedAppConfig) => {
+      setWidgetConfig(widgetConfigFor(config, widgetLabel));
+    };
 
-    const currentLabel = getCurrentWindowLabelSafe();
     let isDisposed = false;
-    let unlistenForceUnlock: (() => void) | undefined;
-    let unlistenSetLock: (() => void) | undefined;
-    let unlistenMoved: (() => void) | undefined;
-    let unlistenTaskSync: (() => void) | undefined;
-    let unlistenTasksState: (() => void) | undefined;
-    let unlistenWidgetTaskView: (() => void) | undefined;
-    let unlistenWidgetAlignment: (() => void) | undefined;
-    let widgetPositionPersistTimer: ReturnType<typeof setTimeout> | undefined;
-    let lastPendingWidgetPosition: { x: number; y: number } | null = null;
-
-    void onWidgetForceUnlock(() => {
-      setWidgetLocked(false);
-    }).then((dispose) => {
-      if (isDisposed) {
-        dispose();
-        return;
+    let unlistenConfig: (() => void) | undefined;
+    let unlistenPeek: (() => void) | undefined;
+    void loadPersistedAppConfig().then((config) => {
+      if (!isDisposed && config) {
+        applyConfig(config);
       }
-      unlistenForceUnlock = dispose;
     });
-
-    void onWidgetSetLock((locked) => {
-      setWidgetLocked(locked);
-    }).then((dispose) => {
+    void onAppConfigUpdated(applyConfig).then((dispose) => {
       if (isDisposed) {
         dispose();
         return;
       }
-      unlistenSetLock = dispose;
+      unlistenConfig = dispose;
     });
-
-    void onWidgetMoved((position) => {
-      setWidgetPosition(position);
-      lastPendingWidgetPosition = position;
-      if (widgetPositionPersistTimer) {
-        clearTimeout(widgetPositionPersistTimer);
+    void onWidgetPeekState((event) => {
+      if (event.label === widgetLabel) {
+        setWidgetPeeking(event.active);
       }
-      widgetPositionPersistTimer = setTimeout(() => {
-        const pendingPosition = lastPendingWidgetPosition;
-        if (!pendingPosition) {
-          return;
-        }
-        lastPendingWidgetPosition = null;
-        void saveWidgetPositionToPersistedAppConfig(pendingPosition).catch((error) => {
-          setPersistWriteError(error instanceof Error ? error.message : "数据写入失败（config.json）");
-        });
-      }, 220);
     }).then((dispose) => {
       if (isDisposed) {
         dispose();
         return;
       }
-      unlistenMoved = dispose;
+      unlistenPeek = dispose;
     });
 
+    return () => {
+      isDisposed = true;
+      if (unlistenConfig) {
+        unlistenConfig();
+      }
+      if (unlistenPeek) {
+        unlistenPeek();
+      }
+    };
+  }, [widgetLabel]);
+
+  useEffect(() => {
+    if (typeof widgetConfig?.locked !== "undefined") {
+      void applyWidgetLockState(widgetConfig.locked);
+    }
+  }, [widgetConfig?.locked]);
+
+  useEffect(() => {
+    if (typeof widgetScalePercent !== "undefined") {
+      void applyWidgetScaleWindowSize(widgetScalePercent, widgetAlignMode);
+    }
+    // The alignment only picks the edge that stays put; changing it alone must not resize the window.
+  }, [widgetScalePercent]);
+
+  useEffect(() => {
+    const currentLabel = getCurrentWindowLabelSafe();
+    let isDisposed = false;
+    let unlistenTaskSync: (() => void) | undefined;
+    let unlistenTasksState: (() => void) | undefined;
+
     void onTaskStatusUpdated((payload) => {
       if (payload.sourceWindowLabel && payload.sourceWindowLabel === currentLabel) {
         return;
@@ -184,71 +185,37 @@ export function WidgetModePage() {
       unlistenTasksState = dispose;
     });
 
-    void onWidgetTaskViewUpdated((payload) => {
-      if (payload.sourceWindowLabel && payload.sourceWindowLabel === currentLabel) {
-        return;
+    return () => {
+      isDisposed = true;
+      if (unlistenTaskSync) {
+        unlistenTaskSync();
       }
-      applySyncedWidgetTaskView(payload);
-    }).then((dispose) => {
-      if (isDisposed) {
-        dispose();
-        return;
+      if (unlistenTasksState) {
+        unlistenTasksState();
       }
-      unlistenWidgetTaskView = dispose;
-    });
+    };
+  }, [applySyncedTaskStatus, applySyncedTasksState]);
 
-    void onWidgetAlignmentUpdated((payload) => {
-      if (payload.sourceWindowLabel && payload.sourceWindowLabel === currentLabel) {
-        return;
-      }
-      applySyncedWidgetAlignment(payload);
+  useEffect(() => {
+    let isDisposed = false;
+    let unlisten: (() => void) | undefined;
+    void onQuickCaptureSaved((payload) => {
+      applyCapturedItem(payload);
     }).then((dispose) => {
       if (isDisposed) {
         dispose();
         return;
       }
-      unlistenWidgetAlignment = dispose;
+      unlisten = dispose;
     });
 
     return () => {
       isDisposed = true;
-      stopWatchOpacity();
-      stopWatchHoverOpacity();
-      stopWatchScale();
-      if (unlistenForceUnlock) {
-        unlistenForceUnlock();
-      }
-      if (unlistenSetLock) {
-        unlistenSetLock();
-      }
-      if (unlistenMoved) {
-        unlistenMoved();
-      }
-      if (unlistenTaskSync) {
-        unlistenTaskSync();
-      }
-      if (unlistenTasksState) {
-        unlistenTasksState();
-      }
-      if (unlistenWidgetTaskView) {
-        unlistenWidgetTaskView();
-      }
-      if (unlistenWidgetAlignment) {
-        unlistenWidgetAlignment();
-      }
-      if (widgetPositionPersistTimer) {
-        clearTimeout(widgetPositionPersistTimer);
-        widgetPositionPersistTimer = undefined;
-      }
-      if (lastPendingWidgetPosition) {
-        const pendingPosition = lastPendingWidgetPosition;
-        lastPendingWidgetPosition = null;
-        void saveWidgetPositionToPersistedAppConfig(pendingPosition).catch((error) => {
-          setPersistWriteError(error instanceof Error ? error.message : "数据写入失败（config.json）");
-        });
+      if (unlisten) {
+        unlisten();
       }
     };
-  }, [applySyncedTaskStatus, applySyncedTasksState, applySyncedWidgetTaskView, applySyncedWidgetAlignment, setPersistWriteError, setWidgetLocked]);
+  }, [applyCapturedItem]);
 
   return (
     <div className="widget-page">
@@ -273,14 +240,14 @@ export function WidgetModePage() {
             ↗
           </button>
           <button
-            className={`widget-icon-button widget-view-button${widgetShowAllTasks ? " is-all" : ""}`}
-            disabled={widgetLocked}
+            className={`widget-icon-button widget-view-button${widgetView.kind === "all" ? " is-all" : ""}`}
+            disabled={widgetLocked || !canToggleWidgetView}
             onClick={onToggleWidgetTaskView}
             type="button"
-            aria-label={widgetShowAllTasks ? "Show active tasks only" : "Show all tasks"}
+            aria-label={widgetView.kind === "all" ? "Show active tasks only" : "Show all tasks"}
             title={widgetLocked ? "Unlock widget to change task visibility" : undefined}
           >
-            {widgetShowAllTasks ? "All" : "Act"}
+            {widgetViewShortLabel[widgetView.kind]}
           </button>
           <button
             className={`widget-icon-button widget-align-button${widgetAlignMode === "left" ? " is-left" : " is-right"}`}
@@ -26,123 +26,44 @@ function hasLocalStorage() {
   return typeof window !== "undefined" && typeof window.localStorage !== "undefined";
 }
 
-export function getWidgetOpacity() {
+/**
+ * Opacity used to live in localStorage and is now kept per widget in config.json. Returns the legacy values once,
+ * removing them, so the main window can migrate them; `null` when nothing is left to migrate.
+ */
+export function takeLegacyWidgetOpacity() {
   if (!hasLocalStorage()) {
-    return 20;
-  }
-
-  const raw = localStorage.getItem(widgetOpacityKey);
-  if (raw === null) {
-    return 20;
-  }
-
-  return clampOpacity(Number(raw));
-}
-
-export function setWidgetOpacity(opacity: number) {
-  if (!hasLocalStorage()) {
-    return;
-  }
-
-  const normalized = clampOpacity(opacity);
-  localStorage.setItem(widgetOpacityKey, String(normalized));
-}
-
-export function watchWidgetOpacity(onChange: (opacity: number) => void) {
-  if (!hasLocalStorage()) {
-    return () => {};
-  }
-
-  const handler = (event: StorageEvent) => {
-    if (event.key !== widgetOpacityKey) {
-      return;
-    }
-
-    onChange(clampOpacity(Number(event.newValue)));
-  };
-
-  window.addEventListener("storage", handler);
-  return () => window.removeEventListener("storage", handler);
-}
-
-export function getWidgetHoverOpacity() {
-  if (!hasLocalStorage()) {
-    return 38;
-  }
-
-  const raw = localStorage.getItem(widgetHoverOpacityKey);
-  if (raw === null) {
-    return 38;
-  }
-
-  return clampOpacity(Number(raw));
-}
-
-export function setWidgetHoverOpacity(opacity: number) {
-  if (!hasLocalStorage()) {
-    return;
+    return null;
   }
 
-  const normalized = clampOpacity(opacity);
-  localStorage.setItem(widgetHoverOpacityKey, String(normalized));
-}
-
-export function watchWidgetHoverOpacity(onChange: (opacity: number) => void) {
-  if (!hasLocalStorage()) {
-    return () => {};
+  const opacity = localStorage.getItem(widgetOpacityKey);
+  const hoverOpacity = localStorage.getItem(widgetHoverOpacityKey);
+  if (opacity === null && hoverOpacity === null) {
+    return null;
   }
 
-  const handler = (event: StorageEvent) => {
-    if (event.key !== widgetHoverOpacityKey) {
-      return;
-    }
-
-    onChange(clampOpacity(Number(event.newValue)));
+  localStorage.removeItem(widgetOpacityKey);
+  localStorage.removeItem(widgetHoverOpacityKey);
+  return {
+    opacityPercent: opacity === null ? undefined : clampOpacity(Number(opacity)),
+    hoverOpacityPercent: hoverOpacity === null ? undefined : clampOpacity(Number(hoverOpacity)),
   };
-
-  window.addEventListener("storage", handler);
-  return () => window.removeEventListener("storage", handler);
 }
 
-export function getWidgetScale() {
+/** The widget scale moved from localStorage to config.json; returns the legacy value once, removing it. */
+export function takeLegacyWidgetScale() {
   if (!hasLocalStorage()) {
-    return 100;
+    return null;
   }
 
   const raw = localStorage.getItem(widgetScaleKey);
   if (raw === null) {
-    return 100;
+    return null;
   }
 
+  localStorage.removeItem(widgetScaleKey);
   return clampScale(Number(raw));
 }
 
-export function setWidgetScale(scale: number) {
-  if (!hasLocalStorage()) {
-    return;
-  }
-
-  const normalized = clampScale(scale);
-  localStorage.setItem(widgetScaleKey, String(normalized));
-}
-
-export function watchWidgetScale(onChange: (scale: number) => void) {
-  if (!hasLocalStorage()) {
-    return () => {};
-  }
-
-  const handler = (event: StorageEvent) => {
-    if (event.key !== widgetScaleKey) {
-      return;
-    }
-
-    onChange(clampScale(Number(event.newValue)));
-  };
-
-  window.addEventListener("storage", handler);
-  return () => window.removeEventListener("storage", handler);
-}
-
 export function getWidgetLocked() {
   if (!hasLocalStorage()) {
     return false;
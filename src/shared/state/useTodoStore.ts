@@ -1,28 +1,27 @@
 import { create } from "zustand";
 import {
   getWidgetLocked,
-  getWidgetPosition,
   setWidgetLocked as persistWidgetLocked,
   setWidgetPosition as persistWidgetPosition,
 } from "../settings/widget";
 import {
   emitTaskStatusUpdated,
   emitTasksStateUpdated,
-  emitWidgetAlignmentUpdated,
-  emitWidgetTaskViewUpdated,
   getCurrentWindowLabelSafe,
 } from "../tauri/window";
 import {
   loadPersistedAppConfig,
   loadPersistedAppData,
   normalizePersistWriteError,
-  savePersistedAppConfig,
   savePersistedAppData,
+  updatePersistedAppConfig,
 } from "../tauri/storage";
 import type {
+  AppConfigPatch,
   GlobalStatus,
   PersistedAppConfig,
   PersistedAppData,
+  QuickCaptureSavedPayload,
   SortMode,
   TaskFilter,
   TaskLogType,
@@ -34,8 +33,7 @@ import type {
   TodoTask,
   TodoTaskLog,
   WidgetAlignMode,
-  WidgetAlignmentSyncPayload,
-  WidgetTaskViewSyncPayload,
+  WidgetView,
 } from "../types/todo";
 
 interface AddTaskInput {
@@ -103,8 +101,8 @@ interface TodoState {
   toggleTask: (id: string) => void;
   applySyncedTaskStatus: (payload: TaskStatusSyncPayload) => void;
   applySyncedTasksState: (payload: TasksStateSyncPayload) => void;
-  applySyncedWidgetTaskView: (payload: WidgetTaskViewSyncPayload) => void;
-  applySyncedWidgetAlignment: (payload: WidgetAlignmentSyncPayload) => void;
+  applySyncedAppConfig: (config: PersistedAppConfig) => void;
+  applyCapturedItem: (payload: QuickCaptureSavedPayload) => void;
   addTask: (input: AddTaskInput) => void;
   updateTask: (id: string, input: UpdateTaskInput) => void;
   addTaskTag: (taskId: string, tag: string) => void;
@@ -129,7 +127,6 @@ interface TodoState {
 }
 
 const schemaVersion = 2 as const;
-const configSchemaVersion = 1;
 const emptyTasks: TodoTask[] = [];
 const emptyGlobals: TodoGlobal[] = [];
 const emptyTaskLogs: TodoTaskLog[] = [];
@@ -217,17 +214,13 @@ function bySortMode(left: TodoTask, right: TodoTask, sortMode: SortMode) {
   return left.title.localeCompare(right.title);
 }
 
-function toPersistedData(
-  state: Pick<TodoState, "tasks" | "globals" | "taskLogs" | "sparks" | "widgetShowAllTasks" | "widgetAlignMode">,
-): PersistedAppData {
+function toPersistedData(state: Pick<TodoState, "tasks" | "globals" | "taskLogs" | "sparks">): PersistedAppData {
   return {
     schemaVersion,
     tasks: state.tasks,
     globals: state.globals,
     taskLogs: state.taskLogs,
     sparks: state.sparks,
-    widgetShowAllTasks: state.widgetShowAllTasks,
-    widgetAlignMode: state.widgetAlignMode,
   };
 }
 
@@ -244,6 +237,7 @@ function schedulePersist(state: TodoState) {
 
   const payload = toPersistedData(state);
   persistTimer = setTimeout(() => {
+    persistTimer = undefined;
     void savePersistedAppData(payload)
       .then(() => {
         if (useTodoStore.getState().persistWriteError) {
@@ -270,37 +264,16 @@ function normalizePersistedData(data: PersistedAppData | null) {
   return data;
 }
 
-function normalizePersistedConfig(config: PersistedAppConfig | null) {
-  if (!config || config.schemaVersion !== configSchemaVersion) {
-    return null;
-  }
-
-  if (typeof config.widgetVisible !== "boolean") {
-    return null;
-  }
-
-  if (
-    typeof config.widgetPosition !== "undefined"
-    && (
-      typeof config.widgetPosition !== "object"
-      || config.widgetPosition === null
-      || typeof config.widgetPosition.x !== "number"
-      || typeof config.widgetPosition.y !== "number"
-    )
-  ) {
-    return null;
-  }
-
-  return config;
-}
-
-function buildPersistedConfig(widgetVisible: boolean): PersistedAppConfig {
-  const widgetPosition = getWidgetPosition();
-  return {
-    schemaVersion: configSchemaVersion,
-    widgetVisible,
-    widgetPosition: widgetPosition ?? undefined,
-  };
+function persistConfigPatch(patch: AppConfigPatch) {
+  void updatePersistedAppConfig(patch)
+    .then(() => {
+      if (useTodoStore.getState().persistWriteError) {
+        useTodoStore.setState({ persistWriteError: null });
+      }
+    })
+    .catch((error) => {
+      useTodoStore.setState({ persistWriteError: normalizePersistWriteError(error, "config").message });
+    });
 }
 
 function applyMutation(get: () => TodoState, mutate: () => void) {
@@ -333,6 +306,25 @@ export function getWidgetTasks(tasks: TodoTask[], sortMode: SortMode, showAllTas
   return sorted.filter((task) => task.status === "active").slice(0, 8);
 }
 
+export function getWidgetViewTasks(tasks: TodoTask[], sparks: TodoSpark[], sortMode: SortMode, view: WidgetView) {
+  switch (view.kind) {
+    case "active":
+      return getWidgetTasks(tasks, sortMode, false);
+    case "all":
+      return getWidgetTasks(tasks, sortMode, true);
+    case "today": {
+      const todayDateKey = new Date().toISOString().slice(0, 10);
+      return getWidgetTasks(tasks.filter((task) => task.executionDate === todayDateKey), sortMode, true);
+    }
+    case "global": {
+      const linkedTaskIds = new Set(
+        sparks.filter((spark) => spark.globalIds?.includes(view.globalId)).flatMap((spark) => spark.taskIds ?? []),
+      );
+      return getWidgetTasks(tasks.filter((task) => linkedTaskIds.has(task.id)), sortMode, true);
+    }
+  }
+}
+
 export const useTodoStore = create<TodoState>((set, get) => ({
   tasks: emptyTasks,
   globals: emptyGlobals,
@@ -378,6 +370,8 @@ export const useTodoStore = create<TodoState>((set, get) => ({
     }
   },
   applySyncedTaskStatus: (payload) => {
+    // Changes made by the backend (e.g. the tray) are already on disk unless a save from here is still pending.
+    const hasPendingPersist = typeof persistTimer !== "undefined";
     set((state) => ({
       tasks: state.tasks.map((task) => {
         if (task.id !== payload.taskId) {
@@ -391,15 +385,36 @@ export const useTodoStore = create<TodoState>((set, get) => ({
         };
       }),
     }));
+    if (hasPendingPersist) {
+      schedulePersist(get());
+    }
   },
   applySyncedTasksState: ({ tasks }) => {
     set({ tasks });
   },
-  applySyncedWidgetTaskView: ({ showAllTasks }) => {
-    set({ widgetShowAllTasks: showAllTasks });
+  // The primary widget's settings live in config.json; the store only mirrors them for the main window.
+  applySyncedAppConfig: (config) => {
+    if (config.widgetLocked !== get().widgetLocked) {
+      persistWidgetLocked(config.widgetLocked);
+    }
+    set({
+      widgetLocked: config.widgetLocked,
+      widgetShowAllTasks: config.widgetView.kind === "all",
+      widgetAlignMode: config.widgetAlignMode,
+    });
   },
-  applySyncedWidgetAlignment: ({ alignMode }) => {
-    set({ widgetAlignMode: alignMode });
+  applyCapturedItem: (payload) => {
+    // Same as a backend status change: only a pending save from here could drop the item from data.json again.
+    const hasPendingPersist = typeof persistTimer !== "undefined";
+    set((state) => {
+      if (payload.kind === "task") {
+        return state.tasks.some((task) => task.id === payload.item.id) ? {} : { tasks: [payload.item, ...state.tasks] };
+      }
+      return state.sparks.some((spark) => spark.id === payload.item.id) ? {} : { sparks: [payload.item, ...state.sparks] };
+    });
+    if (hasPendingPersist) {
+      schedulePersist(get());
+    }
   },
   addTask: ({ title, executionDate, status, tags }) => {
     const normalizedTitle = title.trim();
@@ -711,39 +726,21 @@ export const useTodoStore = create<TodoState>((set, get) => ({
       persistWidgetLocked(nextLocked);
       return { widgetLocked: nextLocked };
     }),
+  // The backend persists `widgetVisible` and shows or hides the window; this only mirrors the result.
   setWidgetVisible: (widgetVisible) => {
     set({ widgetVisible });
-    void savePersistedAppConfig(buildPersistedConfig(widgetVisible))
-      .then(() => {
-        if (get().persistWriteError) {
-          set({ persistWriteError: null });
-        }
-      })
-      .catch((error) => {
-        set({ persistWriteError: normalizePersistWriteError(error, "config").message });
-      });
   },
   setWidgetShowAllTasks: (showAllTasks) => {
-    applyMutation(get, () => {
-      set({ widgetShowAllTasks: showAllTasks });
-    });
-    void emitWidgetTaskViewUpdated({
-      showAllTasks,
-      sourceWindowLabel: getCurrentWindowLabelSafe(),
-    });
+    set({ widgetShowAllTasks: showAllTasks });
+    persistConfigPatch({ widgetView: { kind: showAllTasks ? "all" : "active" } });
   },
   toggleWidgetShowAllTasks: () => {
     const nextShowAll = !get().widgetShowAllTasks;
     get().setWidgetShowAllTasks(nextShowAll);
   },
   setWidgetAlignMode: (alignMode) => {
-    applyMutation(get, () => {
-      set({ widgetAlignMode: alignMode });
-    });
-    void emitWidgetAlignmentUpdated({
-      alignMode,
-      sourceWindowLabel: getCurrentWindowLabelSafe(),
-    });
+    set({ widgetAlignMode: alignMode });
+    persistConfigPatch({ widgetAlignMode: alignMode });
   },
   toggleWidgetAlignMode: () => {
     const nextAlignMode: WidgetAlignMode = get().widgetAlignMode === "right" ? "left" : "right";
@@ -758,33 +755,43 @@ export const useTodoStore = create<TodoState>((set, get) => ({
     }
 
     try {
-      const persistedConfig = normalizePersistedConfig(await loadPersistedAppConfig());
+      const persistedConfig = await loadPersistedAppConfig();
       const initialWidgetVisible = persistedConfig?.widgetVisible ?? false;
       if (persistedConfig?.widgetPosition) {
         persistWidgetPosition(persistedConfig.widgetPosition);
       }
-      if (!persistedConfig) {
-        try {
-          await savePersistedAppConfig(buildPersistedConfig(initialWidgetVisible));
-        } catch (error) {
-          set({ persistWriteError: normalizePersistWriteError(error, "config").message });
-          throw error;
-        }
+      if (persistedConfig && persistedConfig.widgetLocked !== get().widgetLocked) {
+        persistWidgetLocked(persistedConfig.widgetLocked);
+        set({ widgetLocked: persistedConfig.widgetLocked });
       }
 
       const persisted = normalizePersistedData(await loadPersistedAppData());
       if (persisted) {
+        const widgetAlignMode = persisted.widgetAlignMode ?? persistedConfig?.widgetAlignMode ?? "right";
+        const widgetShowAllTasks = persisted.widgetShowAllTasks ?? persistedConfig?.widgetView.kind === "all";
         set({
           tasks: persisted.tasks,
           globals: persisted.globals,
           taskLogs: persisted.taskLogs,
           sparks: persisted.sparks,
           widgetVisible: initialWidgetVisible,
-          widgetShowAllTasks: persisted.widgetShowAllTasks ?? false,
-          widgetAlignMode: persisted.widgetAlignMode ?? "right",
+          widgetShowAllTasks,
+          widgetAlignMode,
           selectedGlobalId: persisted.globals[0]?.id ?? null,
           dataInitialized: true,
         });
+        const legacyConfigPatch: AppConfigPatch = {};
+        if (persisted.widgetAlignMode) {
+          legacyConfigPatch.widgetAlignMode = widgetAlignMode;
+        }
+        if (typeof persisted.widgetShowAllTasks !== "undefined") {
+          legacyConfigPatch.widgetView = { kind: widgetShowAllTasks ? "all" : "active" };
+        }
+        if (Object.keys(legacyConfigPatch).length > 0) {
+          // Move the legacy data.json fields into config.json, then rewrite data.json without them.
+          persistConfigPatch(legacyConfigPatch);
+          schedulePersist(get());
+        }
         return;
       }
 
@@ -794,8 +801,6 @@ export const useTodoStore = create<TodoState>((set, get) => ({
         globals: [],
         taskLogs: [],
         sparks: [],
-        widgetShowAllTasks: false,
-        widgetAlignMode: "right",
       };
 
       set({
@@ -804,8 +809,8 @@ export const useTodoStore = create<TodoState>((set, get) => ({
         taskLogs: emptyData.taskLogs,
         sparks: emptyData.sparks,
         widgetVisible: initialWidgetVisible,
-        widgetShowAllTasks: emptyData.widgetShowAllTasks ?? false,
-        widgetAlignMode: emptyData.widgetAlignMode ?? "right",
+        widgetShowAllTasks: persistedConfig?.widgetView.kind === "all",
+        widgetAlignMode: persistedConfig?.widgetAlignMode ?? "right",
         selectedGlobalId: null,
         dataInitialized: true,
       });
@@ -0,0 +1,64 @@
+import { invoke, type InvokeArgs } from "@tauri-apps/api/core";
+
+export type AppErrorCode =
+  | "io"
+  | "parse"
+  | "validation"
+  | "conflict"
+  | "not_found"
+  | "locked"
+  | "window_unavailable"
+  | "internal";
+
+export interface AppErrorPayload {
+  code: AppErrorCode;
+  /** English detail, also written to the logs. */
+  message: string;
+  /** What `code` means, in the backend's configured language. */
+  summary?: string;
+  context?: Record<string, unknown>;
+}
+
+export class AppCommandError extends Error {
+  readonly code: AppErrorCode;
+  readonly summary: string | undefined;
+  readonly context: Record<string, unknown>;
+
+  constructor(payload: AppErrorPayload) {
+    super(payload.message);
+    this.name = "AppCommandError";
+    this.code = payload.code;
+    this.summary = payload.summary;
+    this.context = payload.context ?? {};
+  }
+}
+
+export function isAppErrorPayload(raw: unknown): raw is AppErrorPayload {
+  if (typeof raw !== "object" || raw === null) {
+    return false;
+  }
+
+  const candidate = raw as Record<string, unknown>;
+  return typeof candidate.code === "string" && typeof candidate.message === "string";
+}
+
+export function toAppCommandError(raw: unknown) {
+  if (raw instanceof AppCommandError) {
+    return raw;
+  }
+  if (isAppErrorPayload(raw)) {
+    return new AppCommandError(raw);
+  }
+
+  const message = raw instanceof Error ? raw.message : typeof raw === "string" && raw.trim() ? raw : "unknown error";
+  return new AppCommandError({ code: "internal", message });
+}
+
+/** Invokes a backend command and rethrows failures as `AppCommandError` so callers can branch on `code`. */
+export async function invokeCommand<T>(command: string, args?: InvokeArgs) {
+  try {
+    return await invoke<T>(command, args);
+  } catch (error) {
+    throw toAppCommandError(error);
+  }
+}
@@ -0,0 +1,64 @@
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import type { CrashReport, CrashReportSummary, LogEntry, LogQuery, LogQueryPage } from "../types/todo";
+import { invokeCommand } from "./errors";
+
+const logEntryAppendedEvent = "log-entry-appended";
+
+declare global {
+  interface Window {
+    __TAURI_INTERNALS__?: unknown;
+  }
+}
+
+function isTauriRuntime() {
+  return typeof window !== "undefined" && typeof window.__TAURI_INTERNALS__ !== "undefined";
+}
+
+export async function queryLogs(query: LogQuery = {}): Promise<LogQueryPage> {
+  if (!isTauriRuntime()) {
+    return { entries: [], nextCursor: null };
+  }
+
+  return invokeCommand<LogQueryPage>("query_logs", { query });
+}
+
+export async function startLogTail(handler: (entry: LogEntry) => void) {
+  if (!isTauriRuntime()) {
+    return () => {};
+  }
+
+  const current = getCurrentWebviewWindow();
+  const unlisten = await current.listen<LogEntry>(logEntryAppendedEvent, (event) => {
+    handler(event.payload);
+  });
+  await invokeCommand("start_log_tail");
+
+  return () => {
+    unlisten();
+    void invokeCommand("stop_log_tail");
+  };
+}
+
+export async function listCrashReports() {
+  if (!isTauriRuntime()) {
+    return [];
+  }
+
+  return invokeCommand<CrashReportSummary[]>("list_crash_reports");
+}
+
+export async function readCrashReport(fileName: string) {
+  return invokeCommand<CrashReport>("read_crash_report", { fileName });
+}
+
+export async function clearCrashReports() {
+  if (!isTauriRuntime()) {
+    return 0;
+  }
+
+  return invokeCommand<number>("clear_crash_reports");
+}
+
+export async function exportDiagnostics(destination?: string) {
+  return invokeCommand<string>("export_diagnostics", { destination: destination ?? null });
+}
@@ -1,5 +1,5 @@
-import { invoke } from "@tauri-apps/api/core";
-import type { PersistedAppConfig, PersistedAppData, WidgetPosition } from "../types/todo";
+import type { AppConfigPatch, PersistedAppConfig, PersistedAppData, WidgetPosition } from "../types/todo";
+import { AppCommandError, invokeCommand, toAppCommandError, type AppErrorCode } from "./errors";
 
 declare global {
   interface Window {
@@ -11,24 +11,25 @@ function isTauriRuntime() {
   return typeof window !== "undefined" && typeof window.__TAURI_INTERNALS__ !== "undefined";
 }
 
-function normalizeUnknownErrorMessage(raw: unknown) {
-  if (raw instanceof Error && raw.message) {
-    return raw.message;
-  }
-  if (typeof raw === "string" && raw.trim()) {
-    return raw;
+export class PersistWriteError extends Error {
+  readonly code: AppErrorCode;
+  readonly target: "data" | "config";
+
+  constructor(cause: AppCommandError, target: "data" | "config") {
+    const targetFile = target === "data" ? "data.json" : "config.json";
+    super(`${targetFile}: ${cause.summary ? `${cause.summary} (${cause.message})` : cause.message}`);
+    this.name = "PersistWriteError";
+    this.code = cause.code;
+    this.target = target;
   }
-  return "unknown error";
 }
 
 export function normalizePersistWriteError(raw: unknown, target: "data" | "config") {
-  if (raw instanceof Error && raw.message.startsWith("数据写入失败（")) {
+  if (raw instanceof PersistWriteError) {
     return raw;
   }
 
-  const targetFile = target === "data" ? "data.json" : "config.json";
-  const detail = normalizeUnknownErrorMessage(raw);
-  return new Error(`数据写入失败（${targetFile}）: ${detail}`);
+  return new PersistWriteError(toAppCommandError(raw), target);
 }
 
 export async function loadPersistedAppData() {
@@ -36,7 +37,7 @@ export async function loadPersistedAppData() {
     return null;
   }
 
-  const data = await invoke<PersistedAppData | null>("load_app_data");
+  const data = await invokeCommand<PersistedAppData | null>("load_app_data");
   return data;
 }
 
@@ -46,7 +47,7 @@ export async function savePersistedAppData(data: PersistedAppData) {
   }
 
   try {
-    await invoke("save_app_data", { data });
+    await invokeCommand("save_app_data", { data });
   } catch (error) {
     throw normalizePersistWriteError(error, "data");
   }
@@ -57,36 +58,27 @@ export async function loadPersistedAppConfig() {
     return null;
   }
 
-  const config = await invoke<PersistedAppConfig | null>("load_app_config");
+  const config = await invokeCommand<PersistedAppConfig>("load_app_config");
   return config;
 }
 
-export async function savePersistedAppConfig(config: PersistedAppConfig) {
+export async function updatePersistedAppConfig(patch: AppConfigPatch) {
   if (!isTauriRuntime()) {
-    return;
+    return null;
   }
 
   try {
-    await invoke("save_app_config", { config });
+    return await invokeCommand<PersistedAppConfig>("update_app_config", { patch });
   } catch (error) {
     throw normalizePersistWriteError(error, "config");
   }
 }
 
 export async function saveWidgetPositionToPersistedAppConfig(position: WidgetPosition) {
-  if (!isTauriRuntime()) {
-    return;
-  }
-
-  const current = await loadPersistedAppConfig();
-  const next: PersistedAppConfig = {
-    schemaVersion: typeof current?.schemaVersion === "number" ? current.schemaVersion : 1,
-    widgetVisible: typeof current?.widgetVisible === "boolean" ? current.widgetVisible : false,
+  await updatePersistedAppConfig({
     widgetPosition: {
       x: Math.round(position.x),
       y: Math.round(position.y),
     },
-  };
-
-  await savePersistedAppConfig(next);
+  });
 }
@@ -1,24 +1,42 @@
-import { invoke } from "@tauri-apps/api/core";
 import { emit, listen } from "@tauri-apps/api/event";
-import { LogicalPosition, LogicalSize, PhysicalPosition } from "@tauri-apps/api/dpi";
+import { LogicalPosition, LogicalSize } from "@tauri-apps/api/dpi";
 import { getCurrentWebviewWindow, WebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { currentMonitor, primaryMonitor } from "@tauri-apps/api/window";
-import { getWidgetLocked, getWidgetPosition, getWidgetScale, setWidgetPosition } from "../settings/widget";
-import { saveWidgetPositionToPersistedAppConfig } from "./storage";
+import { setWidgetPosition } from "../settings/widget";
+import { loadPersistedAppConfig, saveWidgetPositionToPersistedAppConfig } from "./storage";
+import { invokeCommand } from "./errors";
 import type {
+  AppConfigPatch,
   AppMode,
+  CaptureKind,
+  MonitorArea,
+  PersistedAppConfig,
+  QuickCaptureSavedPayload,
+  ShortcutAction,
+  ShortcutStatus,
+  TrayNavigatePayload,
   TaskStatusSyncPayload,
   TasksStateSyncPayload,
-  WidgetAlignmentSyncPayload,
-  WidgetTaskViewSyncPayload,
+  WidgetAlignMode,
+  WidgetHealthEvent,
+  WidgetInstanceConfig,
+  WidgetInstanceSummary,
+  WidgetPeekEvent,
+  WidgetView,
 } from "../types/todo";
 
 const taskStatusUpdatedEvent = "task-status-updated";
 const tasksStateUpdatedEvent = "tasks-state-updated";
 const widgetSetLockStateEvent = "widget-set-lock-state";
 const widgetSetVisibilityStateEvent = "widget-set-visibility-state";
-const widgetTaskViewUpdatedEvent = "widget-task-view-updated";
-const widgetAlignmentUpdatedEvent = "widget-alignment-updated";
+const appConfigUpdatedEvent = "app-config-updated";
+const widgetHealthEvent = "widget-health";
+const widgetPeekStateEvent = "widget-peek-state";
+const quickCaptureSavedEvent = "quick-capture-saved";
+const quickCaptureKindEvent = "quick-capture-kind";
+const trayNavigateEvent = "tray-navigate";
+export const primaryWidgetLabel = "widget";
+const quickCaptureLabel = "quick-capture";
 const widgetBaseWindowWidth = 360;
 const widgetBaseWindowHeight = 760;
 
@@ -35,6 +53,62 @@ function isTauriRuntime() {
 
 let widgetWindowCreationPromise: Promise<void> | null = null;
 
+/** Widget windows are `widget` (the primary one) or `widget-<name>` for additional instances. */
+export function isWidgetLabel(label: string) {
+  return label === primaryWidgetLabel || label.startsWith(`${primaryWidgetLabel}-`);
+}
+
+/** Flat config.json field holding each setting of the primary widget. */
+const primaryWidgetConfigFields = {
+  view: "widgetView",
+  visible: "widgetVisible",
+  position: "widgetPosition",
+  size: "widgetSize",
+  monitor: "widgetMonitor",
+  dock: "widgetDock",
+  scalePercent: "widgetScalePercent",
+  locked: "widgetLocked",
+  alignMode: "widgetAlignMode",
+  opacityPercent: "widgetOpacityPercent",
+  hoverOpacityPercent: "widgetHoverOpacityPercent",
+  clickThrough: "widgetClickThrough",
+  desktopLayer: "widgetDesktopLayer",
+} as const satisfies Record<keyof WidgetInstanceConfig, keyof PersistedAppConfig>;
+
+/** State of the widget `label`, or `null` when it was never opened; mirrors `AppConfig::widget` in the backend. */
+export function widgetConfigFor(config: PersistedAppConfig, label: string): WidgetInstanceConfig | null {
+  if (label !== primaryWidgetLabel) {
+    return config.widgetInstances[label] ?? null;
+  }
+
+  return {
+    view: config.widgetView,
+    visible: config.widgetVisible,
+    position: config.widgetPosition,
+    size: config.widgetSize,
+    monitor: config.widgetMonitor,
+    dock: config.widgetDock,
+    scalePercent: config.widgetScalePercent,
+    locked: config.widgetLocked,
+    alignMode: config.widgetAlignMode,
+    opacityPercent: config.widgetOpacityPercent,
+    hoverOpacityPercent: config.widgetHoverOpacityPercent,
+    clickThrough: config.widgetClickThrough,
+    desktopLayer: config.widgetDesktopLayer,
+  };
+}
+
+/** Config patch applying `patch` to the widget `label`; mirrors `AppConfig::with_widget` in the backend. */
+export function widgetConfigPatch(label: string, patch: Partial<WidgetInstanceConfig>): AppConfigPatch {
+  if (label !== primaryWidgetLabel) {
+    return { widgetInstances: { [label]: patch } };
+  }
+
+  return Object.fromEntries(
+    Object.entries(patch).map(([field, value]) => [primaryWidgetConfigFields[field as keyof WidgetInstanceConfig], value]),
+  ) as AppConfigPatch;
+}
+
 function resolveWidgetWindowSize(scalePercent: number) {
   const normalizedScale = Math.max(70, Math.min(300, Math.round(scalePercent))) / 100;
   return {
@@ -43,7 +117,7 @@ function resolveWidgetWindowSize(scalePercent: number) {
   };
 }
 
-async function resolveWidgetDefaultTopRightPosition() {
+async function resolveWidgetDefaultTopRightPosition(scalePercent: number) {
   const monitor = (await currentMonitor()) ?? (await primaryMonitor());
   if (!monitor) {
     return null;
@@ -51,7 +125,7 @@ async function resolveWidgetDefaultTopRightPosition() {
 
   const logicalWorkAreaPosition = monitor.workArea.position.toLogical(monitor.scaleFactor);
   const logicalWorkAreaSize = monitor.workArea.size.toLogical(monitor.scaleFactor);
-  const { width } = resolveWidgetWindowSize(getWidgetScale());
+  const { width } = resolveWidgetWindowSize(scalePercent);
 
   return {
     x: Math.round(logicalWorkAreaPosition.x + logicalWorkAreaSize.width - width),
@@ -71,7 +145,7 @@ let widgetLastAppliedLogicalSize: { width: number; height: number } | null = nul
 let widgetScaleUpdateInFlight = false;
 let widgetPendingScalePercent: number | null = null;
 
-async function setWidgetSizeSafe(target: WidgetWindowLike, scalePercent: number) {
+async function setWidgetSizeSafe(target: WidgetWindowLike, scalePercent: number, alignMode: WidgetAlignMode = "right") {
   try {
     const nextLogicalSize = resolveWidgetWindowSize(scalePercent);
     const nextSize = new LogicalSize(nextLogicalSize.width, nextLogicalSize.height);
@@ -91,10 +165,11 @@ async function setWidgetSizeSafe(target: WidgetWindowLike, scalePercent: number)
         currentLogicalWidth = nextLogicalSize.width;
       }
 
-      const anchoredRight = currentLogicalPosition.x + currentLogicalWidth;
+      const nextLogicalX =
+        alignMode === "left" ? currentLogicalPosition.x : currentLogicalPosition.x + currentLogicalWidth - nextLogicalSize.width;
       await target.setSize(nextSize);
       widgetLastAppliedLogicalSize = nextLogicalSize;
-      await target.setPosition(new LogicalPosition(Math.round(anchoredRight - nextLogicalSize.width), Math.round(currentLogicalPosition.y)));
+      await target.setPosition(new LogicalPosition(Math.round(nextLogicalX), Math.round(currentLogicalPosition.y)));
       return;
     }
 
@@ -112,21 +187,28 @@ async function removeWindowShadowSafe(target: { setShadow: (enable: boolean) =>
   }
 }
 
+function detectAppModeFromQuery(): AppMode {
+  const mode = new URLSearchParams(window.location.search).get("mode");
+  return mode === "widget" || mode === "capture" ? mode : "standard";
+}
+
 export function detectAppMode(): AppMode {
-  if (window.__DAILY_MODE__ === "widget") {
-    return "widget";
+  if (window.__DAILY_MODE__ === "widget" || window.__DAILY_MODE__ === "capture") {
+    return window.__DAILY_MODE__;
   }
 
   try {
     if (!isTauriRuntime()) {
-      const params = new URLSearchParams(window.location.search);
-      return params.get("mode") === "widget" ? "widget" : "standard";
+      return detectAppModeFromQuery();
     }
 
-    return getCurrentWebviewWindow().label === "widget" ? "widget" : "standard";
+    const label = getCurrentWebviewWindow().label;
+    if (label === quickCaptureLabel) {
+      return "capture";
+    }
+    return isWidgetLabel(label) ? "widget" : "standard";
   } catch {
-    const params = new URLSearchParams(window.location.search);
-    return params.get("mode") === "widget" ? "widget" : "standard";
+    return detectAppModeFromQuery();
   }
 }
 
@@ -140,99 +222,49 @@ export async function ensureWidgetWindow() {
     return;
   }
 
+  // The backend reuses a healthy window and otherwise (re)creates it with the saved bounds,
+  // clamped to a monitor that still exists.
   const ensure = async () => {
-    const existing = await WebviewWindow.getByLabel("widget");
-    if (existing) {
-      const locked = getWidgetLocked();
-      await setWidgetSizeSafe(existing, getWidgetScale());
-      await removeWindowShadowSafe(existing);
-      await existing.setIgnoreCursorEvents(locked);
-      await existing.setFocusable(!locked);
-      await existing.show();
-      if (!locked) {
-        await existing.setFocus();
-      }
-      return;
-    }
-
-    const widgetUrl = `${window.location.origin}/?mode=widget`;
-    const widgetPosition = getWidgetPosition() ?? (await resolveWidgetDefaultTopRightPosition());
-    const { width, height } = resolveWidgetWindowSize(getWidgetScale());
-    if (widgetPosition) {
-      setWidgetPosition(widgetPosition);
-    }
-
-    const widget = new WebviewWindow("widget", {
-      url: widgetUrl,
-      title: "Daily Widget",
-      width,
-      height,
-      x: widgetPosition?.x,
-      y: widgetPosition?.y,
-      resizable: false,
-      decorations: false,
-      alwaysOnTop: true,
-      skipTaskbar: true,
-      transparent: true,
-      focus: true,
-    });
-
-    await new Promise<void>((resolve, reject) => {
-      const timeout = window.setTimeout(() => {
-        reject(new Error("Widget window creation timeout."));
-      }, 5000);
-
-      void widget.once("tauri://created", async () => {
-        window.clearTimeout(timeout);
-        try {
-          await setWidgetSizeSafe(widget, getWidgetScale());
-          await removeWindowShadowSafe(widget);
-          await widget.show();
-          await widget.setFocus();
-          resolve();
-        } catch (error) {
-          reject(error);
-        }
-      });
-
-      void widget.once("tauri://error", (error) => {
-        window.clearTimeout(timeout);
-        reject(new Error(typeof error.payload === "string" ? error.payload : "Failed to create widget window."));
-      });
-    });
+    await invokeCommand("ensure_widget_window");
   };
 
-  widgetWindowCreationPromise = ensure().catch(async (error) => {
-    const message = error instanceof Error ? error.message : String(error);
-    if (message.toLowerCase().includes("already exists")) {
-      const existing = await WebviewWindow.getByLabel("widget");
-      if (existing) {
-        const locked = getWidgetLocked();
-        await setWidgetSizeSafe(existing, getWidgetScale());
-        await removeWindowShadowSafe(existing);
-        await existing.setIgnoreCursorEvents(locked);
-        await existing.setFocusable(!locked);
-        await existing.show();
-        if (!locked) {
-          await existing.setFocus();
-        }
-        return;
-      }
-    }
-
-    throw error;
-  }).finally(() => {
+  widgetWindowCreationPromise = ensure().finally(() => {
     widgetWindowCreationPromise = null;
   });
 
   await widgetWindowCreationPromise;
 }
 
-export async function applyWidgetScaleWindowSize(scalePercent: number) {
+export async function openWidgetInstance(label: string, view?: WidgetView) {
+  if (!isTauriRuntime()) {
+    return;
+  }
+
+  await invokeCommand("ensure_widget_window", { label, view });
+}
+
+export async function closeWidgetInstance(label: string, forget = false) {
   if (!isTauriRuntime()) {
     return;
   }
 
+  await invokeCommand("close_widget_window", { label, forget });
+}
+
+export async function listWidgetInstances() {
+  if (!isTauriRuntime()) {
+    return [];
+  }
+
+  return invokeCommand<WidgetInstanceSummary[]>("list_widget_instances");
+}
+
+export async function applyWidgetScaleWindowSize(scalePercent: number, alignMode: WidgetAlignMode = "right") {
+  if (!isTauriRuntime()) {
+    return;
+  }
+
+  const label = getCurrentWidgetLabel() ?? primaryWidgetLabel;
   const normalizedScalePercent = Math.max(70, Math.min(300, Math.round(scalePercent)));
   widgetPendingScalePercent = normalizedScalePercent;
   if (widgetScaleUpdateInFlight) {
@@ -246,14 +278,14 @@ export async function applyWidgetScaleWindowSize(scalePercent: number) {
       widgetPendingScalePercent = null;
 
       try {
-        await invoke("resize_widget_window_atomic", { scale_percent: nextScalePercent });
+        await invokeCommand("resize_widget_window_atomic", { label, scalePercent: nextScalePercent, alignMode });
         widgetLastAppliedLogicalSize = resolveWidgetWindowSize(nextScalePercent);
       } catch {
-        const existing = await WebviewWindow.getByLabel("widget");
+        const existing = await WebviewWindow.getByLabel(label);
         if (!existing) {
           continue;
         }
-        await setWidgetSizeSafe(existing, nextScalePercent);
+        await setWidgetSizeSafe(existing, nextScalePercent, alignMode);
       }
     }
   } finally {
@@ -266,7 +298,8 @@ export async function resetWidgetWindowPosition() {
     return;
   }
 
-  const nextPosition = await resolveWidgetDefaultTopRightPosition();
+  const config = await loadPersistedAppConfig();
+  const nextPosition = await resolveWidgetDefaultTopRightPosition(config?.widgetScalePercent ?? 100);
   if (!nextPosition) {
     return;
   }
@@ -274,7 +307,7 @@ export async function resetWidgetWindowPosition() {
   setWidgetPosition(nextPosition);
   await saveWidgetPositionToPersistedAppConfig(nextPosition);
 
-  const existing = await WebviewWindow.getByLabel("widget");
+  const existing = await WebviewWindow.getByLabel(primaryWidgetLabel);
   if (!existing) {
     return;
   }
@@ -282,22 +315,59 @@ export async function resetWidgetWindowPosition() {
   await existing.setPosition(new LogicalPosition(nextPosition.x, nextPosition.y));
 }
 
-export async function setWidgetWindowVisibility(visible: boolean) {
+export async function listMonitors() {
+  if (!isTauriRuntime()) {
+    return [];
+  }
+
+  return invokeCommand<MonitorArea[]>("list_monitors");
+}
+
+export async function moveWidgetToMonitor(monitorName: string, label: string = primaryWidgetLabel) {
+  if (!isTauriRuntime()) {
+    return;
+  }
+
+  await invokeCommand("move_widget_to_monitor", { label, monitorName });
+}
+
+/** Opacity is stored in config.json; widgets pick the change up through `app-config-updated`. */
+export async function setWidgetContentOpacity(
+  opacityPercent: number,
+  hoverOpacityPercent?: number,
+  label: string | null = primaryWidgetLabel,
+) {
   if (!isTauriRuntime()) {
     return;
   }
 
-  if (visible) {
-    await ensureWidgetWindow();
+  await invokeCommand("set_widget_opacity", { label, opacityPercent, hoverOpacityPercent });
+}
+
+export async function setWidgetClickThrough(enabled: boolean, label: string | null = primaryWidgetLabel) {
+  if (!isTauriRuntime()) {
     return;
   }
 
-  const existing = await WebviewWindow.getByLabel("widget");
-  if (!existing) {
+  await invokeCommand("set_widget_click_through", { label, enabled });
+}
+
+/** Rebuilds open widgets, since the desktop layer can only be chosen before a window is shown. */
+export async function setWidgetDesktopLayer(enabled: boolean, label: string | null = primaryWidgetLabel) {
+  if (!isTauriRuntime()) {
     return;
   }
 
-  await existing.hide();
+  await invokeCommand("set_widget_desktop_layer", { label, enabled });
+}
+
+/** Unlocks locked widgets for `seconds` (10 by default); the backend relocks them even if the page hangs. */
+export async function peekWidget(label: string | null = null, seconds?: number) {
+  if (!isTauriRuntime()) {
+    return [];
+  }
+
+  return invokeCommand<string[]>("peek_widget", { label, seconds });
 }
 
 export async function focusMainWindow() {
@@ -320,15 +390,13 @@ export async function applyWidgetLockState(locked: boolean) {
   }
 
   const current = getCurrentWebviewWindow();
-  if (current.label !== "widget") {
+  if (!isWidgetLabel(current.label)) {
     return;
   }
 
-  await current.setAlwaysOnTop(true);
+  // Stacking, click-through and focusability are applied by the backend whenever the lock or a peek changes.
   await current.setResizable(false);
   await removeWindowShadowSafe(current);
-  await current.setIgnoreCursorEvents(locked);
-  await current.setFocusable(!locked);
   if (!locked) {
     await current.setFocus();
   }
@@ -340,22 +408,12 @@ export async function startWidgetDragging() {
   }
 
   const current = getCurrentWebviewWindow();
-  if (current.label !== "widget") {
+  if (!isWidgetLabel(current.label)) {
     return;
   }
 
-  await current.startDragging();
-}
-
-export async function onWidgetForceUnlock(handler: () => void) {
-  if (!isTauriRuntime()) {
-    return () => {};
-  }
-
-  const current = getCurrentWebviewWindow();
-  return current.listen("widget-force-unlock", () => {
-    handler();
-  });
+  // The backend starts the drag so it can snap the widget to nearby edges once the move settles.
+  await invokeCommand("start_widget_drag");
 }
 
 export async function onWidgetToggleLock(handler: () => void) {
@@ -375,7 +433,7 @@ export async function onWidgetSetLock(handler: (locked: boolean) => void) {
   }
 
   const current = getCurrentWebviewWindow();
-  return current.listen<boolean>("widget-set-lock-state", (event) => {
+  return current.listen<boolean>(widgetSetLockStateEvent, (event) => {
     if (typeof event.payload === "boolean") {
       handler(event.payload);
     }
@@ -395,139 +453,192 @@ export async function onWidgetSetVisibility(handler: (visible: boolean) => void)
   });
 }
 
-export async function syncWidgetLockedState(locked: boolean) {
+/** Widget windows address themselves by default; elsewhere a missing label addresses every widget. */
+export async function syncWidgetLockedState(locked: boolean, label: string | null = getCurrentWidgetLabel()) {
   if (!isTauriRuntime()) {
     return;
   }
 
-  await invoke("sync_widget_locked_state", { locked });
+  await invokeCommand("sync_widget_locked_state", { label, locked });
 }
 
-export async function syncWidgetVisibilityState(visible: boolean) {
+export async function syncWidgetVisibilityState(visible: boolean, label: string | null = getCurrentWidgetLabel()) {
   if (!isTauriRuntime()) {
     return;
   }
 
-  await invoke("sync_widget_visibility_state", { visible });
+  await invokeCommand("sync_widget_visibility_state", { label, visible });
 }
 
-export async function onWidgetMoved(handler: (position: { x: number; y: number }) => void) {
+export async function onAppConfigUpdated(handler: (config: PersistedAppConfig) => void) {
   if (!isTauriRuntime()) {
     return () => {};
   }
 
-  const current = getCurrentWebviewWindow();
-  if (current.label !== "widget") {
-    return () => {};
-  }
-
-  return current.onMoved(({ payload }) => {
-    void (async () => {
-      try {
-        const scaleFactor = await current.scaleFactor();
-        const logicalPosition = new PhysicalPosition(payload).toLogical(scaleFactor);
-        handler({
-          x: Math.round(logicalPosition.x),
-          y: Math.round(logicalPosition.y),
-        });
-      } catch {
-        handler({
-          x: Math.round(payload.x),
-          y: Math.round(payload.y),
-        });
-      }
-    })();
+  return listen<PersistedAppConfig>(appConfigUpdatedEvent, (event) => {
+    handler(event.payload);
   });
 }
 
-export function getCurrentWindowLabelSafe() {
+/** Tells the backend watchdog this widget rendered; widgets that never report ready are recreated. */
+export async function notifyWidgetReady() {
   if (!isTauriRuntime()) {
-    return null;
+    return;
   }
 
-  try {
-    return getCurrentWebviewWindow().label;
-  } catch {
-    return null;
+  await invokeCommand("widget_ready");
+}
+
+export async function onWidgetPeekState(handler: (event: WidgetPeekEvent) => void) {
+  if (!isTauriRuntime()) {
+    return () => {};
   }
+
+  return listen<WidgetPeekEvent>(widgetPeekStateEvent, (event) => {
+    handler(event.payload);
+  });
 }
 
-export async function emitTaskStatusUpdated(payload: TaskStatusSyncPayload) {
+export async function onTrayNavigate(handler: (payload: TrayNavigatePayload) => void) {
   if (!isTauriRuntime()) {
-    return;
+    return () => {};
   }
 
-  await emit<TaskStatusSyncPayload>(taskStatusUpdatedEvent, payload);
+  return listen<TrayNavigatePayload>(trayNavigateEvent, (event) => {
+    handler(event.payload);
+  });
 }
 
-export async function onTaskStatusUpdated(handler: (payload: TaskStatusSyncPayload) => void) {
+export async function onWidgetHealth(handler: (event: WidgetHealthEvent) => void) {
   if (!isTauriRuntime()) {
     return () => {};
   }
 
-  return listen<TaskStatusSyncPayload>(taskStatusUpdatedEvent, (event) => {
+  return listen<WidgetHealthEvent>(widgetHealthEvent, (event) => {
     handler(event.payload);
   });
 }
 
-export async function emitTasksStateUpdated(payload: TasksStateSyncPayload) {
+export async function listGlobalShortcuts() {
+  if (!isTauriRuntime()) {
+    return [];
+  }
+
+  return invokeCommand<ShortcutStatus[]>("list_global_shortcuts");
+}
+
+/** Binds `action` to `accelerator`, or unbinds it when `accelerator` is null. */
+export async function setGlobalShortcut(action: ShortcutAction, accelerator: string | null) {
+  if (!isTauriRuntime()) {
+    return [];
+  }
+
+  return invokeCommand<ShortcutStatus[]>("set_global_shortcut", { action, accelerator });
+}
+
+export async function reloadGlobalShortcuts() {
+  if (!isTauriRuntime()) {
+    return [];
+  }
+
+  return invokeCommand<ShortcutStatus[]>("reload_global_shortcuts");
+}
+
+export async function openQuickCapture(kind: CaptureKind = "spark") {
   if (!isTauriRuntime()) {
     return;
   }
 
-  await emit<TasksStateSyncPayload>(tasksStateUpdatedEvent, payload);
+  await invokeCommand("open_quick_capture", { kind });
 }
 
-export async function onTasksStateUpdated(handler: (payload: TasksStateSyncPayload) => void) {
+/** Parses and saves one capture line in the backend, which closes the capture window on success. */
+export async function submitQuickCapture(input: string, kind: CaptureKind) {
+  if (!isTauriRuntime()) {
+    return null;
+  }
+
+  return invokeCommand<QuickCaptureSavedPayload>("submit_quick_capture", { input, kind });
+}
+
+export async function onQuickCaptureSaved(handler: (payload: QuickCaptureSavedPayload) => void) {
   if (!isTauriRuntime()) {
     return () => {};
   }
 
-  return listen<TasksStateSyncPayload>(tasksStateUpdatedEvent, (event) => {
+  return listen<QuickCaptureSavedPayload>(quickCaptureSavedEvent, (event) => {
     handler(event.payload);
   });
 }
 
-export async function emitWidgetSetLock(locked: boolean) {
+/** Sent to an already open capture window when a shortcut asks for a different kind. */
+export async function onQuickCaptureKind(handler: (kind: CaptureKind) => void) {
+  if (!isTauriRuntime()) {
+    return () => {};
+  }
+
+  return getCurrentWebviewWindow().listen<CaptureKind>(quickCaptureKindEvent, (event) => {
+    handler(event.payload);
+  });
+}
+
+export async function closeCurrentWindow() {
   if (!isTauriRuntime()) {
     return;
   }
 
-  await emit<boolean>(widgetSetLockStateEvent, locked);
+  await getCurrentWebviewWindow().close();
+}
+
+export function getCurrentWidgetLabel() {
+  const label = getCurrentWindowLabelSafe();
+  return label && isWidgetLabel(label) ? label : null;
+}
+
+export function getCurrentWindowLabelSafe() {
+  if (!isTauriRuntime()) {
+    return null;
+  }
+
+  try {
+    return getCurrentWebviewWindow().label;
+  } catch {
+    return null;
+  }
 }
 
-export async function emitWidgetTaskViewUpdated(payload: WidgetTaskViewSyncPayload) {
+export async function emitTaskStatusUpdated(payload: TaskStatusSyncPayload) {
   if (!isTauriRuntime()) {
     return;
   }
 
-  await emit<WidgetTaskViewSyncPayload>(widgetTaskViewUpdatedEvent, payload);
+  await emit<TaskStatusSyncPayload>(taskStatusUpdatedEvent, payload);
 }
 
-export async function onWidgetTaskViewUpdated(handler: (payload: WidgetTaskViewSyncPayload) => void) {
+export async function onTaskStatusUpdated(handler: (payload: TaskStatusSyncPayload) => void) {
   if (!isTauriRuntime()) {
     return () => {};
   }
 
-  return listen<WidgetTaskViewSyncPayload>(widgetTaskViewUpdatedEvent, (event) => {
+  return listen<TaskStatusSyncPayload>(taskStatusUpdatedEvent, (event) => {
     handler(event.payload);
   });
 }
 
-export async function emitWidgetAlignmentUpdated(payload: WidgetAlignmentSyncPayload) {
+export async function emitTasksStateUpdated(payload: TasksStateSyncPayload) {
   if (!isTauriRuntime()) {
     return;
   }
 
-  await emit<WidgetAlignmentSyncPayload>(widgetAlignmentUpdatedEvent, payload);
+  await emit<TasksStateSyncPayload>(tasksStateUpdatedEvent, payload);
 }
 
-export async function onWidgetAlignmentUpdated(handler: (payload: WidgetAlignmentSyncPayload) => void) {
+export async function onTasksStateUpdated(handler: (payload: TasksStateSyncPayload) => void) {
   if (!isTauriRuntime()) {
     return () => {};
   }
 
-  return listen<WidgetAlignmentSyncPayload>(widgetAlignmentUpdatedEvent, (event) => {
+  return listen<TasksStateSyncPayload>(tasksStateUpdatedEvent, (event) => {
     handler(event.payload);
   });
 }
@@ -4,7 +4,7 @@ export type TaskFilter = "all" | "active" | "completed";
 
 export type SortMode = "status" | "date";
 
-export type AppMode = "standard" | "widget";
+export type AppMode = "standard" | "widget" | "capture";
 
 export type GlobalStatus = "active" | "completed" | "terminated";
 
@@ -59,6 +59,7 @@ export interface PersistedAppData {
   globals: TodoGlobal[];
   taskLogs: TodoTaskLog[];
   sparks: TodoSpark[];
+  /** Legacy locations; the primary widget's view and alignment now live in config.json and are migrated on first load. */
   widgetShowAllTasks?: boolean;
   widgetAlignMode?: WidgetAlignMode;
 }
@@ -68,10 +69,178 @@ export interface WidgetPosition {
   y: number;
 }
 
+export interface MonitorArea {
+  name: string | null;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export interface WidgetSize {
+  width: number;
+  height: number;
+}
+
+/** Work-area edges the widget is snapped against; a missing axis floats freely. */
+export interface WidgetDock {
+  horizontal?: WidgetAlignMode;
+  vertical?: "top" | "bottom";
+}
+
+/** Which tasks a widget lists; `global` follows the tasks linked to it through sparks. */
+export type WidgetView = { kind: "active" } | { kind: "all" } | { kind: "today" } | { kind: "global"; globalId: string };
+
+/** Saved state of one widget window; the primary widget mirrors the flat `widget*` config fields. */
+export interface WidgetInstanceConfig {
+  view: WidgetView;
+  visible: boolean;
+  position: WidgetPosition | null;
+  size: WidgetSize | null;
+  monitor: string | null;
+  dock: WidgetDock | null;
+  scalePercent: number;
+  locked: boolean;
+  alignMode: WidgetAlignMode;
+  opacityPercent: number;
+  hoverOpacityPercent: number;
+  /** Whether a locked widget lets clicks through to the windows below it. */
+  clickThrough: boolean;
+  /** Pins the widget below other windows, as part of the wallpaper. */
+  desktopLayer: boolean;
+}
+
+export interface WidgetInstanceSummary extends WidgetInstanceConfig {
+  label: string;
+  open: boolean;
+}
+
+export type WidgetHealthStatus = "recovering" | "recovered" | "gaveUp";
+
+export interface WidgetHealthEvent {
+  label: string;
+  status: WidgetHealthStatus;
+  attempt: number;
+  /** Notification text in the backend's configured language. */
+  message: string;
+}
+
+/** Sent when a locked widget is temporarily made interactive, and again when it relocks. */
+export interface WidgetPeekEvent {
+  label: string;
+  active: boolean;
+  remainingMs: number;
+}
+
+export type ShortcutAction =
+  | "toggleWidgetLock"
+  | "toggleWidgetVisibility"
+  | "openMainWindow"
+  | "quickAddTask"
+  | "quickCaptureSpark"
+  | "peekWidgets";
+
+/** Result of registering one entry of the `shortcuts` config map with the OS. */
+export type ShortcutStatus = { action: string; accelerator: string } & (
+  | { status: "registered" }
+  | { status: "unknownAction" }
+  | { status: "invalidAccelerator"; message: string }
+  | { status: "conflict"; withAction: string }
+  | { status: "unavailable"; message: string }
+);
+
+export type CaptureKind = "task" | "spark";
+
+/** Broadcast after the quick-capture window saved a new item to data.json. */
+export type QuickCaptureSavedPayload = { kind: "task"; item: TodoTask } | { kind: "spark"; item: TodoSpark };
+
+/** Sent by the tray when a global or spark entry is picked; the main window opens the matching section. */
+export interface TrayNavigatePayload {
+  section: "global" | "spark";
+  id: string;
+}
+
+export type AppTheme = "system" | "light" | "dark";
+
+/** Language of the texts the backend renders itself: tray labels, notifications and error summaries. */
+export type AppLanguage = "zh-CN" | "en";
+
 export interface PersistedAppConfig {
   schemaVersion: number;
+  widgetView: WidgetView;
   widgetVisible: boolean;
-  widgetPosition?: WidgetPosition;
+  widgetPosition: WidgetPosition | null;
+  widgetSize: WidgetSize | null;
+  widgetMonitor: string | null;
+  widgetDock: WidgetDock | null;
+  widgetScalePercent: number;
+  widgetLocked: boolean;
+  widgetAlignMode: WidgetAlignMode;
+  widgetOpacityPercent: number;
+  widgetHoverOpacityPercent: number;
+  widgetClickThrough: boolean;
+  widgetDesktopLayer: boolean;
+  widgetInstances: Record<string, WidgetInstanceConfig>;
+  theme: AppTheme;
+  /** `null` follows the system locale. */
+  language: AppLanguage | null;
+  shortcuts: Record<string, string>;
+}
+
+/** Partial update merged into config.json by the backend; `null` resets a field to its default. */
+export type AppConfigPatch = {
+  [Key in Exclude<keyof PersistedAppConfig, "schemaVersion" | "widgetInstances">]?: PersistedAppConfig[Key] | null;
+} & {
+  widgetInstances?: Record<string, Partial<WidgetInstanceConfig> | null>;
+};
+
+export type LogLevel = "trace" | "debug" | "info" | "warn" | "error";
+
+export interface LogEntry {
+  timestamp: number;
+  level: LogLevel;
+  event: string;
+  message: string;
+  data?: unknown;
+}
+
+/** Opaque position returned by `query_logs`; pass it back unchanged to read the next, older page. */
+export interface LogCursor {
+  file: string | null;
+  newestRotated: string | null;
+  offset: number;
+}
+
+export interface LogQuery {
+  level?: LogLevel;
+  eventPrefix?: string;
+  from?: number;
+  to?: number;
+  cursor?: LogCursor;
+  limit?: number;
+}
+
+export interface LogQueryPage {
+  entries: LogEntry[];
+  nextCursor: LogCursor | null;
+}
+
+export interface CrashReportSummary {
+  fileName: string;
+  timestamp: number;
+  message: string;
+}
+
+export interface CrashReport {
+  timestamp: number;
+  message: string;
+  location: { file: string; line: number; column: number } | null;
+  thread: string | null;
+  backtrace: string;
+  appVersion: string;
+  os: string;
+  arch: string;
+  recentEvents: LogEntry[];
 }
 
 export interface TaskStatusSyncPayload {
@@ -85,13 +254,3 @@ export interface TasksStateSyncPayload {
   tasks: TodoTask[];
   sourceWindowLabel?: string | null;
 }
-
-export interface WidgetTaskViewSyncPayload {
-  showAllTasks: boolean;
-  sourceWindowLabel?: string | null;
-}
-
-export interface WidgetAlignmentSyncPayload {
-  alignMode: WidgetAlignMode;
-  sourceWindowLabel?: string | null;
-}